@@ -0,0 +1,146 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Compensated arithmetic mini library.
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Error-free transformation of sum: returns (s, e) with s + e == a + b exactly.
+#[inline]
+#[must_use]
+pub fn two_sum(a: f64, b: f64) -> (f64, f64) {
+   let s = a + b;
+   let bb = s - a;
+   (s, (a - (s - bb)) + (b - bb))
+}
+
+// As `two_sum`, but requires |a| >= |b|.
+#[inline]
+#[must_use]
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+   let s = a + b;
+   (s, b - (s - a))
+}
+
+// Error-free transformation of product: returns (p, e) with p + e == a * b exactly.
+#[inline]
+#[must_use]
+pub fn two_product(a: f64, b: f64) -> (f64, f64) {
+   let p = a * b;
+   (p, a.mul_add(b, -p))
+}
+
+// Unevaluated sum of two floats, hi + lo, with |lo| no more than half an ulp of hi.  This gives
+// roughly twice the precision of f64.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DoubleF64 {
+   pub hi: f64,
+   pub lo: f64,
+}
+
+impl DoubleF64 {
+   // Exact sum of two floats.
+   #[inline]
+   #[must_use]
+   pub fn sum(a: f64, b: f64) -> Self {
+      let (hi, lo) = two_sum(a, b);
+      Self { hi, lo }
+   }
+
+   // Exact product of two floats.
+   #[inline]
+   #[must_use]
+   pub fn product(a: f64, b: f64) -> Self {
+      let (hi, lo) = two_product(a, b);
+      Self { hi, lo }
+   }
+
+   #[inline]
+   #[must_use]
+   pub fn to_f64(self) -> f64 {
+      self.hi + self.lo
+   }
+}
+
+impl From<f64> for DoubleF64 {
+   fn from(v: f64) -> Self {
+      Self { hi: v, lo: 0.0 }
+   }
+}
+
+impl Neg for DoubleF64 {
+   type Output = Self;
+   fn neg(self) -> Self {
+      Self { hi: -self.hi, lo: -self.lo }
+   }
+}
+
+impl Add for DoubleF64 {
+   type Output = Self;
+   fn add(self, other: Self) -> Self {
+      let (s, e) = two_sum(self.hi, other.hi);
+      let (t, f) = two_sum(self.lo, other.lo);
+      let (s, e) = quick_two_sum(s, e + t);
+      let (hi, lo) = quick_two_sum(s, e + f);
+      Self { hi, lo }
+   }
+}
+
+impl Sub for DoubleF64 {
+   type Output = Self;
+   fn sub(self, other: Self) -> Self {
+      self + (-other)
+   }
+}
+
+impl Mul for DoubleF64 {
+   type Output = Self;
+   fn mul(self, other: Self) -> Self {
+      let (p, e) = two_product(self.hi, other.hi);
+      let e = self.hi.mul_add(other.lo, self.lo.mul_add(other.hi, e));
+      let (hi, lo) = quick_two_sum(p, e);
+      Self { hi, lo }
+   }
+}
+
+impl Mul<f64> for DoubleF64 {
+   type Output = Self;
+   fn mul(self, other: f64) -> Self {
+      let (p, e) = two_product(self.hi, other);
+      let (hi, lo) = quick_two_sum(p, self.lo.mul_add(other, e));
+      Self { hi, lo }
+   }
+}
+
+impl Div for DoubleF64 {
+   type Output = Self;
+   fn div(self, other: Self) -> Self {
+      let q_1 = self.hi / other.hi;
+      let r = self - other * q_1;
+      let q_2 = r.hi / other.hi;
+      let r = r - other * q_2;
+      let q_3 = r.hi / other.hi;
+      let (hi, lo) = quick_two_sum(q_1, q_2);
+      Self { hi, lo } + Self::from(q_3)
+   }
+}
+
+// Power of two that scales v roughly into [1, 2).  Scaling by it is exact, barring underflow.
+#[must_use]
+pub fn power_2_normalizer(v: f64) -> f64 {
+   if v == 0.0 || !v.is_finite() {
+      return 1.0;
+   }
+   (-v.abs().log2().floor()).exp2()
+}
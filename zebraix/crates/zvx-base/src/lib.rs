@@ -12,16 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod compensated;
 pub mod matrix;
 pub mod pieces;
 pub mod utils;
+pub use crate::compensated::DoubleF64;
 pub use crate::matrix::{
-   q_mat_power_to_weighted, q_mat_weighted_to_power, q_reduce, rat_quad_expand_power,
+   homog_cross, q_mat_adjugate, q_mat_determinant, q_mat_power_to_weighted, q_mat_product,
+   q_mat_transpose, q_mat_vec_product, q_mat_weighted_to_power, q_reduce, rat_quad_expand_power,
    rat_quad_expand_weighted, rat_quad_rq_eval, CurveCubicMatrix, CurveMatrix, QMat,
 };
 pub use pieces::{
    ArcPath, CubicFourPoint, CubicHomog, CubicPath, HyperbolicPath, OneOfSegment, PolylinePath,
-   RatQuadHomog, RatQuadHomogPower, RatQuadHomogWeighted,
+   RatCubicHomog, RatCubicHomogWeighted, RatQuadHomog, RatQuadHomogPower, RatQuadHomogWeighted,
 };
 
 const BASIC_ABS_TOLERANCE: f64 = 0.0001;
@@ -14,6 +14,7 @@
 
 /// Curve matrix mini library.
 use crate::CubicHomog;
+use crate::RatCubicHomog;
 use crate::RatQuadHomog;
 
 // Transforms are row-major, that is each row nested.
@@ -113,6 +114,70 @@ pub fn q_mat_power_to_weighted(r: &[f64; 2]) -> QMat {
    [[1.0, 2.0, 1.0], [v, v + w, w], [v * v, 2.0 * v * w, w * w]]
 }
 
+// Product of transforms, applying `first` and then `second`, that is in row-major form
+// `first` * `second`.
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+pub fn q_mat_product(first: &QMat, second: &QMat) -> QMat {
+   let mut ret_val = [[0.0; 3]; 3];
+   for (i, row) in ret_val.iter_mut().enumerate() {
+      for (j, v) in row.iter_mut().enumerate() {
+         *v = first[i][0] * second[0][j] + first[i][1] * second[1][j] + first[i][2] * second[2][j];
+      }
+   }
+   ret_val
+}
+
+#[must_use]
+pub const fn q_mat_transpose(m: &QMat) -> QMat {
+   [[m[0][0], m[1][0], m[2][0]], [m[0][1], m[1][1], m[2][1]], [m[0][2], m[1][2], m[2][2]]]
+}
+
+#[must_use]
+pub fn q_mat_vec_product(m: &QMat, v: &[f64; 3]) -> [f64; 3] {
+   q_power_eval_single(m, v)
+}
+
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+pub fn q_mat_determinant(m: &QMat) -> f64 {
+   m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+      - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+      + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+// Adjugate, that is the inverse scaled by the determinant.  This is preferred for homogeneous
+// manipulations, since it is well-defined for singular matrices.
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+pub fn q_mat_adjugate(m: &QMat) -> QMat {
+   [
+      [
+         m[1][1] * m[2][2] - m[1][2] * m[2][1],
+         m[0][2] * m[2][1] - m[0][1] * m[2][2],
+         m[0][1] * m[1][2] - m[0][2] * m[1][1],
+      ],
+      [
+         m[1][2] * m[2][0] - m[1][0] * m[2][2],
+         m[0][0] * m[2][2] - m[0][2] * m[2][0],
+         m[0][2] * m[1][0] - m[0][0] * m[1][2],
+      ],
+      [
+         m[1][0] * m[2][1] - m[1][1] * m[2][0],
+         m[0][1] * m[2][0] - m[0][0] * m[2][1],
+         m[0][0] * m[1][1] - m[0][1] * m[1][0],
+      ],
+   ]
+}
+
+// Cross product of homogeneous 3-vectors.  This gives the line through two points, and also
+// the point of intersection of two lines.
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+pub fn homog_cross(u: &[f64; 3], v: &[f64; 3]) -> [f64; 3] {
+   [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]]
+}
+
 pub trait CurveMatrix {
    fn normalize(&mut self);
 
@@ -237,3 +302,53 @@ impl CurveCubicMatrix for CubicHomog {
       ])
    }
 }
+
+// Apply transform to a single row (coordinate or denominator) of a cubic homogeneous curve.
+#[inline]
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+fn c_mat_apply_row(in_row: &[f64; 4], tran_c_mat: &CMat) -> [f64; 4] {
+   [
+      in_row[0] * tran_c_mat[0][0]
+         + in_row[1] * tran_c_mat[1][0]
+         + in_row[2] * tran_c_mat[2][0]
+         + in_row[3] * tran_c_mat[3][0],
+      in_row[0] * tran_c_mat[0][1]
+         + in_row[1] * tran_c_mat[1][1]
+         + in_row[2] * tran_c_mat[2][1]
+         + in_row[3] * tran_c_mat[3][1],
+      in_row[0] * tran_c_mat[0][2]
+         + in_row[1] * tran_c_mat[1][2]
+         + in_row[2] * tran_c_mat[2][2]
+         + in_row[3] * tran_c_mat[3][2],
+      in_row[0] * tran_c_mat[0][3]
+         + in_row[1] * tran_c_mat[1][3]
+         + in_row[2] * tran_c_mat[2][3]
+         + in_row[3] * tran_c_mat[3][3],
+   ]
+}
+
+// CurveMath: Normalize rational cubic.
+//
+// As for RQC, the scaling is such that the denominator coefficients have unit norm.
+#[allow(clippy::suboptimal_flops)]
+impl CurveCubicMatrix for RatCubicHomog {
+   fn normalize(&mut self) {
+      let a = &self.0[2];
+      let f = 1.0 / (a[0] * a[0] + a[1] * a[1] + a[2] * a[2] + a[3] * a[3]).abs().sqrt();
+
+      for row in &mut self.0 {
+         for v in row {
+            *v *= f;
+         }
+      }
+   }
+
+   fn apply_c_mat(&self, tran_c_mat: &CMat) -> Self {
+      Self([
+         c_mat_apply_row(&self.0[0], tran_c_mat),
+         c_mat_apply_row(&self.0[1], tran_c_mat),
+         c_mat_apply_row(&self.0[2], tran_c_mat),
+      ])
+   }
+}
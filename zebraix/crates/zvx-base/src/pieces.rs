@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::is_default;
-use crate::matrix::CurveMatrix;
+use crate::matrix::{CurveCubicMatrix, CurveMatrix};
 use crate::{
    default_unit_ratio, is_default_unit_ratio, q_mat_power_to_weighted, q_mat_weighted_to_power,
 };
@@ -37,6 +37,8 @@ pub struct ArcPath {
 pub struct CubicHomog(pub [[f64; 4]; 2]); // Denominator assumed to be a power series.
 #[derive(Debug, Serialize, Deserialize, DefaultFromSerde, PartialEq, Clone)]
 pub struct RatQuadHomog(pub [[f64; 3]; 3]); // "Denominator" in third row.
+#[derive(Debug, Serialize, Deserialize, DefaultFromSerde, PartialEq, Clone)]
+pub struct RatCubicHomog(pub [[f64; 4]; 3]); // "Denominator" in third row.
 
 // r[0] is the value of t at p[0], and r[1] is value of t at p[3].
 #[derive(Debug, Serialize, Deserialize, Clone, DefaultFromSerde, PartialEq)]
@@ -72,6 +74,17 @@ pub struct RatQuadHomogWeighted {
    pub sigma: (f64, f64),
 }
 
+// Weighted form, with binomial factors folded into the coefficients, as for `CubicPath`.  The
+// weights of the four control points are in the denominator row, and the numerator rows are
+// the weighted control-point coordinates.
+#[derive(Debug, Serialize, Deserialize, DefaultFromSerde, PartialEq, Clone)]
+pub struct RatCubicHomogWeighted {
+   pub r: [f64; 2], // Range.
+   pub h: RatCubicHomog,
+   #[serde(skip_serializing_if = "is_default_unit_ratio", default = "default_unit_ratio")]
+   pub sigma: (f64, f64),
+}
+
 impl From<&RatQuadHomogWeighted> for RatQuadHomogPower {
    fn from(weighted: &RatQuadHomogWeighted) -> Self {
       let r = &weighted.r;
@@ -94,6 +107,19 @@ impl From<&RatQuadHomogPower> for RatQuadHomogWeighted {
    }
 }
 
+// Degree elevation, exact.  Numerator and denominator are both multiplied by (a + b).
+impl From<&RatQuadHomogWeighted> for RatCubicHomogWeighted {
+   fn from(quad: &RatQuadHomogWeighted) -> Self {
+      let elevate =
+         |row: &[f64; 3]| -> [f64; 4] { [row[0], row[0] + row[1], row[1] + row[2], row[2]] };
+      Self {
+         r: quad.r,
+         h: RatCubicHomog([elevate(&quad.h.0[0]), elevate(&quad.h.0[1]), elevate(&quad.h.0[2])]),
+         sigma: quad.sigma,
+      }
+   }
+}
+
 impl RatQuadHomogPower {
    #[must_use]
    pub fn normalize(&self) -> Self {
@@ -112,6 +138,15 @@ impl RatQuadHomogWeighted {
    }
 }
 
+impl RatCubicHomogWeighted {
+   #[must_use]
+   pub fn normalize(&self) -> Self {
+      let mut retval = self.clone();
+      retval.h.normalize();
+      retval
+   }
+}
+
 pub type PolylinePath = Vec<[f64; 2]>;
 
 // Path is:
@@ -129,7 +164,7 @@ pub struct HyperbolicPath {
    pub sigma: (f64, f64),
 }
 
-#[derive(Serialize, Debug, Default, PartialEq)]
+#[derive(Serialize, Debug, Default, PartialEq, Clone)]
 pub enum OneOfSegment {
    #[default]
    Neither,
@@ -137,4 +172,5 @@ pub enum OneOfSegment {
    Cubic(CubicPath),
    Hyperbolic(HyperbolicPath),
    Polyline(PolylinePath),
+   RatCubic(RatCubicHomogWeighted),
 }
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{CubicHomog, CubicPath, RatQuadHomog, RatQuadHomogPower, RatQuadHomogWeighted};
+use crate::{
+   CubicHomog, CubicPath, RatCubicHomog, RatCubicHomogWeighted, RatQuadHomog, RatQuadHomogPower,
+   RatQuadHomogWeighted,
+};
 use approx::AbsDiffEq;
 
 #[derive(PartialEq, Debug)]
@@ -182,6 +185,23 @@ impl<'a> HomogContainer<'a> for RatQuadHomogWeighted {
    }
 }
 
+impl<'a> HomogContainer<'a> for RatCubicHomogWeighted {
+   type Homog = RatCubicHomog;
+   type HomogWrapped = RatCubicHomogWrapped<'a>;
+   #[inline]
+   fn h(&'a self) -> &'a RatCubicHomog {
+      &self.h
+   }
+   #[inline]
+   fn r(&self) -> [f64; 2] {
+      self.r
+   }
+   #[inline]
+   fn sigma(&self) -> (f64, f64) {
+      self.sigma
+   }
+}
+
 #[allow(clippy::elidable_lifetime_names)]
 impl<'a, P> AbsDiffEq for PathWrapped<'a, P>
 where
@@ -273,3 +293,36 @@ impl<'a> AbsDiffEq for RatQuadHomogWrapped<'a> {
       true
    }
 }
+
+#[derive(PartialEq, Debug)]
+pub struct RatCubicHomogWrapped<'a> {
+   v: &'a RatCubicHomog,
+}
+
+impl<'a> From<&'a RatCubicHomog> for RatCubicHomogWrapped<'a> {
+   fn from(unwrapped: &'a RatCubicHomog) -> Self {
+      RatCubicHomogWrapped { v: unwrapped }
+   }
+}
+
+#[allow(clippy::elidable_lifetime_names)]
+impl<'a> AbsDiffEq for RatCubicHomogWrapped<'a> {
+   type Epsilon = f64;
+
+   fn default_epsilon() -> f64 {
+      1.0e-06
+   }
+
+   fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+      for k in 0..3 {
+         if !F64ArrayWrapped::<4>::abs_diff_eq(
+            &F64ArrayWrapped::<4>::from(&self.v.0[k]),
+            &F64ArrayWrapped::<4>::from(&other.v.0[k]),
+            epsilon,
+         ) {
+            return false;
+         }
+      }
+      true
+   }
+}
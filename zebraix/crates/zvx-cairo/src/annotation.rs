@@ -0,0 +1,262 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Element attributes in SVG output.
+//
+// The Cairo SVG surface does not write document structure, such as ids and links.  Instead,
+// the renderer draws markers, zero-length strokes whose miter limit encodes the start or end
+// of an annotated group.  The output stream is filtered, and each marker path element is
+// replaced by opening or closing tags.  Marker miter limits are in a range that is reserved,
+// in that the miter limits of drawables are clamped below it.  The filter works on elements,
+// and so does not depend on how the output is laid out in lines.
+//
+// If the diagram has a title or description, the filter also adds these to the document, and
+// gives the root element the image role, labelled by them.  Their ids are prefixed, so that
+// diagrams inlined together in one page can be given distinct ids.
+//
+// Drawables on translucent layers are rendered into a pushed group, which the SVG surface
+// writes as a definition that is then used with a mask.  The markers are written inside the
+// definition, and so annotated groups on such layers are tagged there.
+
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::rc::Rc;
+use zvx_drawable::kinds::MAX_MITER_LIMIT;
+
+// Miter limits from here on are markers.  The code above the base is twice the annotation
+// index, plus one for the end of the group.
+pub const MARKER_MITER_BASE: f64 = 9.0 * MAX_MITER_LIMIT;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ElementAnnotation {
+   pub id: Option<String>,
+   pub class: Option<String>,
+   pub link: Option<String>,
+   pub tooltip: Option<String>,
+   pub alt_text: Option<String>,
+}
+
+impl ElementAnnotation {
+   #[must_use]
+   pub const fn is_empty(&self) -> bool {
+      self.id.is_none()
+         && self.class.is_none()
+         && self.link.is_none()
+         && self.tooltip.is_none()
+         && self.alt_text.is_none()
+   }
+
+   fn opening_tags(&self) -> String {
+      let mut tags = String::new();
+      if let Some(link) = &self.link {
+         let _ = writeln!(tags, "<a xlink:href=\"{}\">", escape_xml(link));
+      }
+      tags.push_str("<g");
+      if let Some(id) = &self.id {
+         let _ = write!(tags, " id=\"{}\"", escape_xml(id));
+      }
+      if let Some(class) = &self.class {
+         let _ = write!(tags, " class=\"{}\"", escape_xml(class));
+      }
+      if let Some(alt_text) = &self.alt_text {
+         let _ = write!(tags, " role=\"img\" aria-label=\"{}\"", escape_xml(alt_text));
+      }
+      tags.push('>');
+      if let Some(tooltip) = &self.tooltip {
+         let _ = write!(tags, "\n<title>{}</title>", escape_xml(tooltip));
+      }
+      tags
+   }
+
+   fn closing_tags(&self) -> String {
+      if self.link.is_some() {
+         "</g>\n</a>".to_string()
+      } else {
+         "</g>".to_string()
+      }
+   }
+}
+
+pub type SharedAnnotations = Rc<RefCell<Vec<ElementAnnotation>>>;
+
+// Accessible title and description of the whole document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentAnnotation {
+   pub title: Option<String>,
+   pub description: Option<String>,
+   pub id_prefix: String,
+}
+
+impl Default for DocumentAnnotation {
+   fn default() -> Self {
+      Self { title: None, description: None, id_prefix: Self::DEFAULT_ID_PREFIX.to_string() }
+   }
+}
+
+impl DocumentAnnotation {
+   pub const DEFAULT_ID_PREFIX: &'static str = "diagram";
+
+   // Adds the role and labelling to the root element tag, and follows it with the title and
+   // description.
+   fn annotate_root(&self, tag: &[u8]) -> Option<Vec<u8>> {
+      let open_end = tag.strip_prefix(b"<svg")?.strip_suffix(b">")?;
+      if !open_end.first().is_some_and(u8::is_ascii_whitespace) {
+         return None;
+      }
+      let prefix = escape_xml(&self.id_prefix);
+      let mut labelled_by = Vec::new();
+      let mut children = String::new();
+      if let Some(title) = &self.title {
+         let id = format!("{prefix}-title");
+         let _ = write!(children, "\n<title id=\"{id}\">{}</title>", escape_xml(title));
+         labelled_by.push(id);
+      }
+      if let Some(description) = &self.description {
+         let id = format!("{prefix}-description");
+         let _ = write!(children, "\n<desc id=\"{id}\">{}</desc>", escape_xml(description));
+         labelled_by.push(id);
+      }
+
+      let mut annotated = b"<svg".to_vec();
+      annotated.extend_from_slice(open_end);
+      annotated.extend_from_slice(
+         format!(" role=\"img\" aria-labelledby=\"{}\"", labelled_by.join(" ")).as_bytes(),
+      );
+      annotated.push(b'>');
+      annotated.extend_from_slice(children.as_bytes());
+      Some(annotated)
+   }
+}
+
+fn escape_xml(text: &str) -> String {
+   let mut escaped = String::with_capacity(text.len());
+   for c in text.chars() {
+      match c {
+         '&' => escaped.push_str("&amp;"),
+         '<' => escaped.push_str("&lt;"),
+         '>' => escaped.push_str("&gt;"),
+         '"' => escaped.push_str("&quot;"),
+         _ => escaped.push(c),
+      }
+   }
+   escaped
+}
+
+// Extracts the marker code from a path element tag, in either attribute or style form.
+fn marker_code(tag: &[u8]) -> Option<usize> {
+   const KEY: &[u8] = b"stroke-miterlimit";
+   if !tag.strip_prefix(b"<path")?.first().is_some_and(u8::is_ascii_whitespace) {
+      return None;
+   }
+   let key_start = tag.windows(KEY.len()).position(|w| w == KEY)?;
+   let rest = &tag[key_start + KEY.len()..];
+   let rest = rest.strip_prefix(b"=\"").or_else(|| rest.strip_prefix(b":"))?;
+   let value_end =
+      rest.iter().position(|c| !(c.is_ascii_digit() || *c == b'.')).unwrap_or(rest.len());
+   let value: f64 = std::str::from_utf8(&rest[..value_end]).ok()?.parse().ok()?;
+   if value < MARKER_MITER_BASE {
+      return None;
+   }
+   #[allow(clippy::cast_possible_truncation)]
+   #[allow(clippy::cast_sign_loss)]
+   Some((value - MARKER_MITER_BASE).round() as usize)
+}
+
+// End of the tag that starts the buffer, skipping quoted attribute values.
+fn tag_end(buffer: &[u8]) -> Option<usize> {
+   let mut quote = None;
+   for (i, c) in buffer.iter().enumerate().skip(1) {
+      match (quote, *c) {
+         (None, b'"' | b'\'') => quote = Some(*c),
+         (Some(q), _) if q == *c => quote = None,
+         (None, b'>') => return Some(i),
+         _ => {}
+      }
+   }
+   None
+}
+
+// Output stream filter that replaces markers.  Text between tags is passed through, and each
+// tag is checked once it is complete.
+pub struct AnnotatingWriter<W: Write> {
+   inner: W,
+   annotations: SharedAnnotations,
+   // Taken when the root element is annotated, and None if there is nothing to add.
+   document: Option<DocumentAnnotation>,
+   pending: Vec<u8>,
+}
+
+impl<W: Write> AnnotatingWriter<W> {
+   pub fn new(inner: W, annotations: SharedAnnotations, document: DocumentAnnotation) -> Self {
+      let document = if document.title.is_some() || document.description.is_some() {
+         Some(document)
+      } else {
+         None
+      };
+      Self { inner, annotations, document, pending: Vec::new() }
+   }
+
+   #[allow(clippy::missing_errors_doc)]
+   pub fn into_inner(mut self) -> std::io::Result<W> {
+      let remainder = std::mem::take(&mut self.pending);
+      self.inner.write_all(&remainder)?;
+      Ok(self.inner)
+   }
+
+   fn write_tag(&mut self, tag: &[u8]) -> std::io::Result<()> {
+      if let Some(document) = &self.document {
+         if let Some(annotated) = document.annotate_root(tag) {
+            self.document = None;
+            return self.inner.write_all(&annotated);
+         }
+      }
+      let replacement = marker_code(tag).and_then(|code| {
+         let annotations = self.annotations.borrow();
+         let annotation = annotations.get(code / 2)?;
+         Some(if code % 2 == 0 { annotation.opening_tags() } else { annotation.closing_tags() })
+      });
+      match replacement {
+         Some(tags) => self.inner.write_all(tags.as_bytes()),
+         None => self.inner.write_all(tag),
+      }
+   }
+}
+
+impl<W: Write> Write for AnnotatingWriter<W> {
+   fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+      self.pending.extend_from_slice(buf);
+      loop {
+         let Some(tag_start) = self.pending.iter().position(|c| *c == b'<') else {
+            let text = std::mem::take(&mut self.pending);
+            self.inner.write_all(&text)?;
+            break;
+         };
+         if tag_start > 0 {
+            let text: Vec<u8> = self.pending.drain(..tag_start).collect();
+            self.inner.write_all(&text)?;
+         }
+         let Some(end) = tag_end(&self.pending) else {
+            break;
+         };
+         let tag: Vec<u8> = self.pending.drain(..=end).collect();
+         self.write_tag(&tag)?;
+      }
+      Ok(buf.len())
+   }
+
+   fn flush(&mut self) -> std::io::Result<()> {
+      self.inner.flush()
+   }
+}
@@ -0,0 +1,36 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Capabilities of the Cairo-Pango backend, invoked through `ZvxCapabilities`.
+
+pub use cairo::SvgUnit;
+use zvx_drawable::extension::Capability;
+
+// Unit of the SVG document, which by default is points.  Set before rendering.
+pub struct SvgDocumentUnit;
+
+impl Capability for SvgDocumentUnit {
+   const NAME: &'static str = "cairo.svg-document-unit";
+   type Argument = SvgUnit;
+   type Output = ();
+}
+
+// Pango font description of a text layout, after `set_layout`, such as "sans Bold 10".
+pub struct PangoFontDescription;
+
+impl Capability for PangoFontDescription {
+   const NAME: &'static str = "pango.font-description";
+   type Argument = ();
+   type Output = String;
+}
@@ -22,20 +22,21 @@ use zvx_drawable::interface::ZvxRenderEngine;
 // This may seem odd, but is Rust-inspired. The diagram and the renderer can be separately
 // borrowed with different mutability.
 // #[derive(Debug)]
-pub struct CairoSpartanCombo {
+pub struct CairoSpartanCombo<W> {
    pub drawable_diagram: DrawableDiagram,
-   pub render_engine: Box<dyn ZvxRenderEngine>,
+   pub render_engine: Box<dyn ZvxRenderEngine<Output = W>>,
 }
 
-impl CairoSpartanCombo {
+impl<W: Write + 'static> CairoSpartanCombo<W> {
+   // Renders the diagram, returning the output stream.
    #[allow(clippy::missing_errors_doc)]
    #[allow(clippy::missing_panics_doc)]
-   pub fn render_diagram(&mut self) -> Result<Box<dyn core::any::Any>, Box<dyn Error>> {
+   pub fn render_diagram(&mut self) -> Result<W, Box<dyn Error>> {
       self.render_engine.render_drawables(&self.drawable_diagram.drawables)
    }
 
    #[allow(clippy::missing_panics_doc)]
-   pub fn create_for_stream<W: Write + 'static>(
+   pub fn create_for_stream(
       out_stream: W,
       preparation: &SpartanPreparation,
    ) -> Self {
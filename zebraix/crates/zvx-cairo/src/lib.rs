@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod annotation;
+pub mod capabilities;
 pub mod combination;
 pub mod render;
 
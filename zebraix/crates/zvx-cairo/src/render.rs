@@ -12,30 +12,54 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::annotation::{
+   AnnotatingWriter, DocumentAnnotation, ElementAnnotation, SharedAnnotations, MARKER_MITER_BASE,
+};
+use crate::capabilities::{PangoFontDescription, SvgDocumentUnit};
 use cairo::Context as CairoContext;
+use cairo::ImageSurface;
 use cairo::Matrix;
 use cairo::SvgSurface;
 use cairo::SvgUnit::Pt;
+use cairo::{Gradient, LinearGradient, RadialGradient};
 use pango::Context as PangoContext;
 use pango::FontDescription;
 use pango::Layout as PangoLayout;
 use pangocairo::functions::create_context as pangocairo_create_context;
 use pangocairo::functions::show_layout as pangocairo_show_layout;
+use std::any::Any;
 use std::error::Error;
 use std::f64::consts::PI;
+use std::io::Cursor;
 use std::io::Write;
-use zvx_base::{ArcPath, CubicPath, HyperbolicPath, OneOfSegment, PolylinePath};
+use std::marker::PhantomData;
+use zvx_base::{
+   is_near_float, ArcPath, CubicPath, HyperbolicPath, OneOfSegment, PolylinePath,
+   RatCubicHomogWeighted,
+};
 use zvx_curves::base::TEval;
+use zvx_curves::rat_cubic::rc_weighted_as_polynomial;
+use zvx_curves::{
+   bisect_directions, segment_ends, segment_interior_placements, trim_segment, ArcLengthSampling,
+   CurveEval, SegmentPlacement,
+};
 use zvx_drawable::choices::{
-   CanvasLayout, ColorChoice, ContinuationChoice, DiagramChoices, LineChoice, LineClosureChoice,
-   PathCompletion, PointChoice, TextAnchorChoice, TextAnchorHorizontal, TextAnchorVertical,
-   TextOffsetChoice, TextSizeChoice,
+   CanvasLayout, ColorChoice, ContinuationChoice, DiagramChoices, FontChoices, FontSlantChoice,
+   FontStretchChoice, FontWeightChoice, LayerChoices, LineCapChoice, LineClosureChoice,
+   LineJoinChoice, MarkerChoice, ParagraphChoices, PathCompletion, PointChoice, TextAlignChoice,
+   TextAnchorChoice, TextAnchorHorizontal, TextAnchorVertical, TextOffsetChoice, TextSizeChoice,
+};
+use zvx_drawable::extension::{invoke_handler, Capability, ZvxExtensible};
+use zvx_drawable::interface::{
+   ClusterExtent, LineMetrics, TextMetrics, ZvxRenderEngine, ZvxTextLayout,
 };
-use zvx_drawable::interface::{TextMetrics, ZvxRenderEngine, ZvxTextLayout};
 use zvx_drawable::kinds::{
-   CirclesSet, LinesSetSet, MarkupChoice, OneOfDrawable, PathChoices, PointsDrawable,
-   QualifiedDrawable, SegmentChoices, SegmentSequence, Strokeable, TextDrawable, TextSingle,
+   CirclesSet, ClipRegion, ColorStop, FillChoices, FillPattern, GroupDrawable, HatchChoices,
+   ImageDrawable, ImageInterpolation, ImageSource, LinesSetSet, MarkerChoices, MarkupChoice,
+   OneOfDrawable, PathChoices, PointsDrawable, QualifiedDrawable, SegmentChoices, SegmentSequence,
+   Strokeable, TextDrawable, TextPathDrawable, TextSingle,
 };
+use zvx_drawable::markup::auto_markup;
 
 #[derive(Debug)]
 pub struct UnfixedCairoSpartanRender {
@@ -44,18 +68,26 @@ pub struct UnfixedCairoSpartanRender {
    pub transform_saver: TransformSaver,
    pub pango_context: PangoContext,
    pub num_segments_hyperbolic: i32, // Actually fixed, but more convenient here.
+   // Element attributes, indexed by marker code, shared with the output stream filter.
+   pub annotations: SharedAnnotations,
 }
 
 #[derive(Debug)]
 pub struct TransformSaver {
    pub saved_matrix: Matrix,
+   // Transform of the enclosing drawable groups, from group to diagram coordinates.
+   pub group_matrix: Matrix,
 }
 
+// Renders to SVG, written to an output stream of type W.  The stream is returned when rendering
+// is finished.
 #[derive(Debug)]
-pub struct CairoSpartanRender {
+pub struct CairoSpartanRender<W> {
    pub unfixed: UnfixedCairoSpartanRender,
    pub canvas_layout: CanvasLayout,
    pub diagram_choices: DiagramChoices,
+   // The surface owns the stream, wrapped in an annotating writer.
+   stream: PhantomData<fn() -> W>,
 }
 
 pub struct ZvxPangoTextLayout<'parent> {
@@ -83,30 +115,38 @@ impl<'parent> ZvxPangoTextLayout<'parent> {
    }
 }
 
-impl CairoSpartanRender {
-   // Since SvgSurface::for_stream will be monomorphized, this function cannot be made
-   // non-templated.
+impl<W: Write + 'static> CairoSpartanRender<W> {
    #[allow(clippy::missing_panics_doc)]
-   pub fn create_for_stream<W: Write + 'static>(
+   pub fn create_for_stream(
       out_stream: W,
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
-   ) -> Box<dyn ZvxRenderEngine> {
+   ) -> Box<dyn ZvxRenderEngine<Output = W>> {
       Box::new(Self::create_not_boxed_for_stream(out_stream, canvas_layout, diagram_choices))
    }
 
-   // Since SvgSurface::for_stream will be monomorphized, this function cannot be made
-   // non-templated.
    #[allow(clippy::missing_panics_doc)]
-   pub fn create_not_boxed_for_stream<W: Write + 'static>(
+   pub fn create_not_boxed_for_stream(
       out_stream: W,
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
    ) -> Self {
+      let annotations = SharedAnnotations::default();
       let mut surface = SvgSurface::for_stream(
          canvas_layout.canvas_size[0],
          canvas_layout.canvas_size[1],
-         out_stream,
+         AnnotatingWriter::new(
+            out_stream,
+            annotations.clone(),
+            DocumentAnnotation {
+               title: diagram_choices.title.clone(),
+               description: diagram_choices.description.clone(),
+               id_prefix: diagram_choices
+                  .document_id
+                  .clone()
+                  .unwrap_or_else(|| DocumentAnnotation::DEFAULT_ID_PREFIX.to_string()),
+            },
+         ),
       )
       .unwrap();
       surface.set_document_unit(Pt);
@@ -119,11 +159,58 @@ impl CairoSpartanRender {
             context,
             surface,
             pango_context,
-            transform_saver: TransformSaver { saved_matrix: Matrix::default() },
+            transform_saver: TransformSaver {
+               saved_matrix: Matrix::default(),
+               group_matrix: Matrix::identity(),
+            },
             num_segments_hyperbolic: Self::default_num_segments_hyperbolic(),
+            annotations,
          },
          canvas_layout: canvas_layout.clone(),
          diagram_choices: diagram_choices.clone(),
+         stream: PhantomData,
+      }
+   }
+
+   // Finishes the surface, and returns the original output stream.
+   #[allow(clippy::missing_errors_doc)]
+   pub fn finish_stream(&mut self) -> Result<W, Box<dyn Error>> {
+      self.unfixed.surface.flush();
+      match self.unfixed.surface.finish_output_stream() {
+         Ok(stream) => {
+            let writer = stream
+               .downcast::<AnnotatingWriter<W>>()
+               .map_err(|_| "Finished stream is not the annotating writer.")?;
+            Ok(writer.into_inner()?)
+         }
+         // SvgSurface keeps the stream, and returns when there is an error, but we just drop it
+         // and pass the error.
+         Err(stream_with_error) => Err(Box::new(stream_with_error.error)),
+      }
+   }
+}
+
+impl ZvxExtensible for ZvxPangoTextLayout<'_> {
+   fn capability_names(&self) -> Vec<&'static str> {
+      vec![PangoFontDescription::NAME]
+   }
+
+   fn invoke_erased(
+      &mut self,
+      name: &str,
+      argument: Box<dyn Any>,
+   ) -> Result<Box<dyn Any>, Box<dyn Error>> {
+      match name {
+         PangoFontDescription::NAME => {
+            invoke_handler::<_, PangoFontDescription>(self, argument, |layout, ()| {
+               let font_description = layout
+                  .pango_text_layout
+                  .font_description()
+                  .ok_or("Font description is not set.  Call set_layout first.")?;
+               Ok(font_description.to_string())
+            })
+         }
+         _ => Err(format!("Capability `{name}` is not supported.").into()),
       }
    }
 }
@@ -131,10 +218,35 @@ impl CairoSpartanRender {
 #[allow(clippy::elidable_lifetime_names)]
 impl<'parent> ZvxTextLayout for ZvxPangoTextLayout<'parent> {
    // Not a great method name.
-   fn set_layout(&mut self, font_family: &str, font_size: f64, single_text: &TextSingle) {
+   fn set_layout(
+      &mut self,
+      font_choices: &FontChoices,
+      paragraph_choices: &ParagraphChoices,
+      font_size: f64,
+      single_text: &TextSingle,
+   ) {
       let mut font_description = FontDescription::new();
 
-      font_description.set_family(font_family);
+      font_description.set_family(&font_choices.family_list());
+      font_description.set_weight(match font_choices.weight {
+         FontWeightChoice::Light => pango::Weight::Light,
+         FontWeightChoice::Normal => pango::Weight::Normal,
+         FontWeightChoice::SemiBold => pango::Weight::Semibold,
+         FontWeightChoice::Bold => pango::Weight::Bold,
+         FontWeightChoice::Heavy => pango::Weight::Heavy,
+      });
+      font_description.set_style(match font_choices.slant {
+         FontSlantChoice::Normal => pango::Style::Normal,
+         FontSlantChoice::Italic => pango::Style::Italic,
+         FontSlantChoice::Oblique => pango::Style::Oblique,
+      });
+      font_description.set_stretch(match font_choices.stretch {
+         FontStretchChoice::Condensed => pango::Stretch::Condensed,
+         FontStretchChoice::SemiCondensed => pango::Stretch::SemiCondensed,
+         FontStretchChoice::Normal => pango::Stretch::Normal,
+         FontStretchChoice::SemiExpanded => pango::Stretch::SemiExpanded,
+         FontStretchChoice::Expanded => pango::Stretch::Expanded,
+      });
       font_description.set_absolute_size(font_size * f64::from(pango::SCALE));
       self.pango_text_layout.set_font_description(Some(&font_description));
 
@@ -150,13 +262,41 @@ impl<'parent> ZvxTextLayout for ZvxPangoTextLayout<'parent> {
       let even_half_height =
          f64::max(font_ascent - strikethrough_center, font_descent + strikethrough_center);
 
+      self.pango_text_layout.set_alignment(match paragraph_choices.alignment {
+         TextAlignChoice::Left | TextAlignChoice::Justified => pango::Alignment::Left,
+         TextAlignChoice::Center => pango::Alignment::Center,
+         TextAlignChoice::Right => pango::Alignment::Right,
+      });
+      self.pango_text_layout.set_justify(paragraph_choices.alignment == TextAlignChoice::Justified);
+      if paragraph_choices.max_width > 0.0 {
+         #[allow(clippy::cast_possible_truncation)]
+         self
+            .pango_text_layout
+            .set_width((paragraph_choices.max_width * f64::from(pango::SCALE)).round() as i32);
+         self.pango_text_layout.set_wrap(pango::WrapMode::Word);
+      }
+      // Pango's factor-based spacing differs slightly from natural spacing, even for unity.
+      if !is_near_float(paragraph_choices.line_spacing, 1.0) {
+         #[allow(clippy::cast_possible_truncation)]
+         self.pango_text_layout.set_line_spacing(paragraph_choices.line_spacing as f32);
+      }
+
       // Text content dependence below.
 
       match single_text.markup {
-         MarkupChoice::Auto | MarkupChoice::Plain => {
+         MarkupChoice::Plain => {
             self.pango_text_layout.set_text(&single_text.content);
-            // text_layout.set_layout("sans", font_size);
          }
+         MarkupChoice::Auto => match auto_markup(&single_text.content) {
+            // Malformed markup is resolved as plain text, and attribute values that Pango
+            // rejects are likewise shown as plain text.
+            Some(markup) if pango::parse_markup(&markup, '\0').is_ok() => {
+               self.pango_text_layout.set_markup(&markup);
+            }
+            _ => {
+               self.pango_text_layout.set_text(&single_text.content);
+            }
+         },
          MarkupChoice::Pango => {
             let accel_marker = '_';
             // let (attr_list, plain_text, accel_char) =
@@ -165,9 +305,27 @@ impl<'parent> ZvxTextLayout for ZvxPangoTextLayout<'parent> {
          }
       }
 
-      let (layout_text_width, layout_text_height) = self.pango_text_layout.size();
-      let text_width = f64::from(layout_text_width);
-      let text_height = f64::from(layout_text_height);
+      // Wrapped lines are aligned within the maximum width, so the logical extent may not
+      // start at the layout origin.
+      let (_, logical_extent) = self.pango_text_layout.extents();
+      let text_start = f64::from(logical_extent.x());
+      let text_width = f64::from(logical_extent.width());
+      let text_height = f64::from(logical_extent.height());
+
+      let mut lines = Vec::new();
+      let mut iter = self.pango_text_layout.iter();
+      loop {
+         let (_, line_extent) = iter.line_extents();
+         lines.push(LineMetrics {
+            start: f64::from(line_extent.x()),
+            width: f64::from(line_extent.width()),
+            baseline: f64::from(iter.baseline()),
+            height: f64::from(line_extent.height()),
+         });
+         if !iter.next_line() {
+            break;
+         }
+      }
 
       self.metrics = Some(TextMetrics {
          strikethrough_center,
@@ -175,8 +333,10 @@ impl<'parent> ZvxTextLayout for ZvxPangoTextLayout<'parent> {
          font_ascent,
          font_descent,
          font_height,
+         text_start,
          text_width,
          text_height,
+         lines,
       });
    }
 
@@ -191,25 +351,50 @@ impl<'parent> ZvxTextLayout for ZvxPangoTextLayout<'parent> {
       Ok(())
    }
 
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_0(&mut self) -> Result<(), Box<dyn Error>> {
-      Err("Cairo-pango text layout does not implement `special_function_0`.".into())
-   }
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_1(&mut self) -> Result<(), Box<dyn Error>> {
-      Err("Cairo-pango text layout does not implement `special_function_1`.".into())
-   }
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_2(&mut self) -> Result<(), Box<dyn Error>> {
-      Err("Cairo-pango text layout does not implement `special_function_2`.".into())
+   fn get_clusters(&mut self) -> Vec<ClusterExtent> {
+      let text = self.pango_text_layout.text();
+      let mut positions: Vec<(usize, f64, f64)> = Vec::new();
+      let mut iter = self.pango_text_layout.iter();
+      loop {
+         let (_, logical) = iter.cluster_extents();
+         let index = usize::try_from(iter.index()).unwrap_or(text.len()).min(text.len());
+         positions.push((index, f64::from(logical.x()), f64::from(logical.width())));
+         if !iter.next_cluster() {
+            break;
+         }
+      }
+
+      let mut clusters = Vec::with_capacity(positions.len());
+      for (i, (index, start, width)) in positions.iter().enumerate() {
+         let end = positions.get(i + 1).map_or(text.len(), |next| next.0);
+         if end > *index {
+            clusters.push(ClusterExtent {
+               content: text[*index..end].to_string(),
+               start: *start,
+               width: *width,
+            });
+         }
+      }
+      clusters
    }
+
    #[allow(clippy::missing_errors_doc)]
-   fn special_function_3(&mut self) -> Result<(), Box<dyn Error>> {
-      Err("Cairo-pango text layout does not implement `special_function_3`.".into())
+   fn render_layout_rotated(&mut self, adjust: [f64; 2], angle: f64) -> Result<(), Box<dyn Error>> {
+      let (x, y) = self.parent_cairo.current_point()?;
+      self.parent_cairo.save()?;
+      self.parent_cairo.translate(x, y);
+      // Canvas y runs downwards, so anticlockwise rotation is negative.
+      self.parent_cairo.rotate(-angle);
+      self
+         .parent_cairo
+         .move_to(-adjust[0] / f64::from(pango::SCALE), -adjust[1] / f64::from(pango::SCALE));
+      pangocairo_show_layout(self.parent_cairo, &self.pango_text_layout);
+      self.parent_cairo.restore()?;
+      Ok(())
    }
 }
 
-impl CairoSpartanRender {
+impl<W> CairoSpartanRender<W> {
    #[must_use]
    pub const fn default_num_segments_hyperbolic() -> i32 {
       50
@@ -228,6 +413,41 @@ impl TransformSaver {
          canvas_layout.canvas_size[1] - canvas_layout.offset[1],
       );
       context.scale(canvas_layout.scale[0], -canvas_layout.scale[1]);
+      context.transform(self.group_matrix);
+   }
+
+   // Inverse of the path transform, so that patterns can be specified in diagram coordinates
+   // but applied on the canvas.
+   #[must_use]
+   pub fn pattern_matrix(&self, canvas_layout: &CanvasLayout) -> Matrix {
+      let mut matrix = Matrix::identity();
+      matrix.translate(
+         canvas_layout.offset[0],
+         canvas_layout.canvas_size[1] - canvas_layout.offset[1],
+      );
+      matrix.scale(canvas_layout.scale[0], -canvas_layout.scale[1]);
+      let mut matrix = Matrix::multiply(&self.group_matrix, &matrix);
+      matrix.invert();
+      matrix
+   }
+
+   // Approximate scaling of diagram distances, along each axis, onto the canvas.  This is exact
+   // unless groups skew or unevenly scale their contents.
+   #[must_use]
+   pub fn path_metric(&self, canvas_layout: &CanvasLayout) -> [f64; 2] {
+      [
+         canvas_layout.scale[0].abs() * self.group_matrix.xx().hypot(self.group_matrix.yx()),
+         canvas_layout.scale[1].abs() * self.group_matrix.xy().hypot(self.group_matrix.yy()),
+      ]
+   }
+
+   // Prepends a group transform, returning the previous for restoration.
+   pub fn push_group_transform(&mut self, transform: [f64; 4], offset: [f64; 2]) -> Matrix {
+      let previous = self.group_matrix;
+      let local =
+         Matrix::new(transform[0], transform[1], transform[2], transform[3], offset[0], offset[1]);
+      self.group_matrix = Matrix::multiply(&local, &previous);
+      previous
    }
 
    // Be sure to restore the original transform before stroking out a path with a pen.  This is
@@ -239,30 +459,191 @@ impl TransformSaver {
 
 impl UnfixedCairoSpartanRender {
    #[allow(clippy::unused_self)]
-   fn set_color(context: &CairoContext, _diagram_choices: &DiagramChoices, color: &ColorChoice) {
-      let (r, g, b) = color.to_rgb();
-      context.set_source_rgb(r, g, b);
+   fn set_color(context: &CairoContext, diagram_choices: &DiagramChoices, color: &ColorChoice) {
+      Self::set_color_with_opacity(context, diagram_choices, color, 1.0);
+   }
+
+   fn set_color_with_opacity(
+      context: &CairoContext,
+      diagram_choices: &DiagramChoices,
+      color: &ColorChoice,
+      opacity: f64,
+   ) {
+      let (r, g, b, a) = diagram_choices.palette.resolve(color).to_rgba();
+      context.set_source_rgba(r, g, b, a * opacity);
+   }
+
+   fn add_color_stops(
+      gradient: &Gradient,
+      stops: &[ColorStop],
+      opacity: f64,
+      diagram_choices: &DiagramChoices,
+   ) {
+      for stop in stops {
+         let (r, g, b, a) = diagram_choices.palette.resolve(&stop.color).to_rgba();
+         gradient.add_color_stop_rgba(stop.offset, r, g, b, a * opacity);
+      }
+   }
+
+   // Limit on the number of hatch lines across the diagonal of the filled extent.
+   const MAX_HATCH_LINES: f64 = 10_000.0;
+
+   // Hatch lines are aligned to the canvas origin, so that adjacent shapes hatch consistently.
+   // Returns false, drawing nothing, if the lines cannot be laid out, such as when the spacing is
+   // zero or so fine that the lines are innumerable.
+   #[allow(clippy::cast_possible_truncation)]
+   fn fill_preserve_with_hatch(
+      context: &CairoContext,
+      hatch_choices: &HatchChoices,
+      color: &ColorChoice,
+      opacity: f64,
+      diagram_choices: &DiagramChoices,
+   ) -> bool {
+      let hatch_parameters = hatch_choices.to_hatch_parameters(diagram_choices);
+      let spacing = hatch_parameters.spacing;
+      if !(hatch_choices.is_valid() && spacing.is_finite() && spacing > 0.0) {
+         return false;
+      }
+      let (x0, y0, x1, y1) = context.fill_extents().unwrap();
+      if (x1 - x0).hypot(y1 - y0) > Self::MAX_HATCH_LINES * spacing {
+         return false;
+      }
+      let outline = context.copy_path().unwrap();
+      let corners = [[x0, y0], [x1, y0], [x0, y1], [x1, y1]];
+
+      context.save().unwrap();
+      context.clip();
+      context.set_line_width(hatch_parameters.line_width);
+      context.set_dash(&[], 0.0);
+      Self::set_color_with_opacity(context, diagram_choices, color, opacity);
+
+      let mut angles = vec![hatch_choices.angle];
+      if hatch_choices.cross {
+         angles.push(hatch_choices.angle + 90.0);
+      }
+      for angle in angles {
+         // Canvas y runs downwards, so anticlockwise is negative y.
+         let (sin, cos) = angle.to_radians().sin_cos();
+         let direction = [cos, -sin];
+         let normal = [sin, cos];
+         let project = |v: [f64; 2], onto: [f64; 2]| v[0].mul_add(onto[0], v[1] * onto[1]);
+         let along: Vec<f64> = corners.iter().map(|c| project(*c, direction)).collect();
+         let across: Vec<f64> = corners.iter().map(|c| project(*c, normal)).collect();
+         let along_min = along.iter().copied().fold(f64::INFINITY, f64::min);
+         let along_max = along.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+         let across_min = across.iter().copied().fold(f64::INFINITY, f64::min);
+         let across_max = across.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+         for i in (across_min / spacing).floor() as i32..=(across_max / spacing).ceil() as i32 {
+            let offset = f64::from(i) * spacing;
+            let base = [offset * normal[0], offset * normal[1]];
+            context.move_to(
+               along_min.mul_add(direction[0], base[0]),
+               along_min.mul_add(direction[1], base[1]),
+            );
+            context.line_to(
+               along_max.mul_add(direction[0], base[0]),
+               along_max.mul_add(direction[1], base[1]),
+            );
+         }
+      }
+      context.stroke().unwrap();
+      context.restore().unwrap();
+      context.append_path(&outline);
+      true
+   }
+
+   fn fill_preserve(
+      context: &CairoContext,
+      fill_choices: &FillChoices,
+      transform_saver: &TransformSaver,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) {
+      let opacity = fill_choices.opacity;
+      match &fill_choices.pattern {
+         FillPattern::Solid => {
+            Self::set_color_with_opacity(context, diagram_choices, &fill_choices.color, opacity);
+         }
+         FillPattern::Linear(linear) => {
+            let gradient =
+               LinearGradient::new(linear.start[0], linear.start[1], linear.end[0], linear.end[1]);
+            Self::add_color_stops(&gradient, &linear.stops, opacity, diagram_choices);
+            gradient.set_matrix(transform_saver.pattern_matrix(canvas_layout));
+            context.set_source(&gradient).unwrap();
+         }
+         FillPattern::Radial(radial) => {
+            let focus = radial.focus.unwrap_or(radial.center);
+            let gradient = RadialGradient::new(
+               focus[0],
+               focus[1],
+               0.0,
+               radial.center[0],
+               radial.center[1],
+               radial.radius,
+            );
+            Self::add_color_stops(&gradient, &radial.stops, opacity, diagram_choices);
+            gradient.set_matrix(transform_saver.pattern_matrix(canvas_layout));
+            context.set_source(&gradient).unwrap();
+         }
+         FillPattern::Hatch(hatch_choices) => {
+            if Self::fill_preserve_with_hatch(
+               context,
+               hatch_choices,
+               &fill_choices.color,
+               opacity,
+               diagram_choices,
+            ) {
+               return;
+            }
+            // Hatching that cannot be laid out falls back to a solid fill.
+            Self::set_color_with_opacity(context, diagram_choices, &fill_choices.color, opacity);
+         }
+      }
+      context.fill_preserve().unwrap();
    }
 
    fn set_line_choice(
       context: &CairoContext,
-      line_choice: LineChoice,
+      path_choices: &PathChoices,
       diagram_choices: &DiagramChoices,
    ) {
-      let line_parameters = line_choice.to_line_parameters(diagram_choices);
+      let line_parameters = path_choices.line_choice.to_line_parameters(diagram_choices);
       context.set_line_width(line_parameters.line_width);
-      context.set_dash(&line_parameters.dashes, line_parameters.dash_offset);
+      // Invalid dashes would put the context into an error state, so the line is drawn solid.
+      if line_parameters.has_valid_dashes() {
+         context.set_dash(&line_parameters.dashes, line_parameters.dash_offset);
+      } else {
+         context.set_dash(&[], 0.0);
+      }
+      context.set_line_cap(match path_choices.line_cap {
+         LineCapChoice::Butt => cairo::LineCap::Butt,
+         LineCapChoice::Round => cairo::LineCap::Round,
+         LineCapChoice::Square => cairo::LineCap::Square,
+      });
+      context.set_line_join(match path_choices.line_join {
+         LineJoinChoice::Miter => cairo::LineJoin::Miter,
+         LineJoinChoice::Round => cairo::LineJoin::Round,
+         LineJoinChoice::Bevel => cairo::LineJoin::Bevel,
+      });
+      context.set_miter_limit(path_choices.clamped_miter_limit());
    }
 
    fn stroke_and_fill(
       &self,
       context: &CairoContext,
+      canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
       path_choices: &PathChoices,
    ) {
       if path_choices.fill_choices.opacity != 0.0 {
-         Self::set_color(context, diagram_choices, &path_choices.fill_choices.color);
-         self.context.fill_preserve().unwrap();
+         Self::fill_preserve(
+            context,
+            &path_choices.fill_choices,
+            &self.transform_saver,
+            canvas_layout,
+            diagram_choices,
+         );
       }
       Self::set_color(context, diagram_choices, &path_choices.color);
       self.context.stroke().unwrap();
@@ -274,7 +655,7 @@ impl UnfixedCairoSpartanRender {
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
    ) {
-      Self::set_line_choice(&self.context, drawable.path_choices.line_choice, diagram_choices);
+      Self::set_line_choice(&self.context, &drawable.path_choices, diagram_choices);
       Self::set_color(&self.context, diagram_choices, &drawable.path_choices.color);
 
       self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
@@ -299,82 +680,170 @@ impl UnfixedCairoSpartanRender {
       self.context.stroke().unwrap();
    }
 
+   // Ratio of point marker radius to the diagram point size.
+   const POINT_RADIUS_RATIO: f64 = 2.8 / 15.0;
+
+   // Vertices of a regular polygon, or of a star if given an inner radius, in device
+   // coordinates.  The first vertex is at the given angle, in degrees anticlockwise.
+   #[allow(clippy::cast_precision_loss)]
+   fn polygon_vertices(
+      center: (f64, f64),
+      count: usize,
+      radius: f64,
+      inner_radius: Option<f64>,
+      start_angle: f64,
+   ) -> Vec<(f64, f64)> {
+      let step = if inner_radius.is_some() { 180.0 } else { 360.0 } / count as f64;
+      let vertex_count = if inner_radius.is_some() { 2 * count } else { count };
+      (0..vertex_count)
+         .map(|i| {
+            let r = if i % 2 == 1 { inner_radius.unwrap_or(radius) } else { radius };
+            let (sin, cos) = (i as f64).mul_add(step, start_angle).to_radians().sin_cos();
+            (r.mul_add(cos, center.0), (-r).mul_add(sin, center.1))
+         })
+         .collect()
+   }
+
+   fn polygon_path(context: &CairoContext, vertices: &[(f64, f64)]) {
+      for (i, (x, y)) in vertices.iter().enumerate() {
+         if i == 0 {
+            context.move_to(*x, *y);
+         } else {
+            context.line_to(*x, *y);
+         }
+      }
+      context.close_path();
+   }
+
+   #[allow(clippy::too_many_lines)]
    fn draw_points_set(
       &mut self,
       drawable: &PointsDrawable,
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
    ) {
-      Self::set_line_choice(&self.context, LineChoice::Ordinary, diagram_choices);
+      if drawable.point_choice == PointChoice::Custom {
+         self.draw_custom_points(drawable, canvas_layout, diagram_choices);
+         return;
+      }
+
+      Self::set_line_choice(&self.context, &PathChoices::default(), diagram_choices);
       Self::set_color(&self.context, diagram_choices, &drawable.color_choice);
 
-      match drawable.point_choice {
-         PointChoice::Circle => {
-            for center in &drawable.centers {
-               self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
-               let (cx, cy) = self.context.user_to_device(center[0], center[1]);
-               self.transform_saver.restore_transform(&self.context);
-               self.context.move_to(cx + 2.8, cy);
-               self.context.arc(cx, cy, 2.8, 0.0 * PI, 2.0 * PI);
-               self.context.close_path();
+      let radius = diagram_choices.point_size * Self::POINT_RADIUS_RATIO * drawable.size;
+      let is_filled = matches!(
+         drawable.point_choice,
+         PointChoice::Dot
+            | PointChoice::FilledSquare
+            | PointChoice::FilledTriangle
+            | PointChoice::FilledTriangleDown
+            | PointChoice::FilledDiamond
+            | PointChoice::FilledStar
+      );
+      for center in &drawable.centers {
+         self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
+         let (cx, cy) = self.context.user_to_device(center[0], center[1]);
+         self.transform_saver.restore_transform(&self.context);
+         let context = &self.context;
+
+         match drawable.point_choice {
+            PointChoice::Circle => {
+               context.move_to(cx + radius, cy);
+               context.arc(cx, cy, radius, 0.0 * PI, 2.0 * PI);
+               context.close_path();
             }
-         }
-         PointChoice::Dot => {
-            for center in &drawable.centers {
-               self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
-               let (cx, cy) = self.context.user_to_device(center[0], center[1]);
-               self.transform_saver.restore_transform(&self.context);
-               #[allow(clippy::suboptimal_flops)]
-               self.context.move_to(cx + 2.8 * 0.92, cy);
-               #[allow(clippy::suboptimal_flops)]
-               self.context.arc(cx, cy, 2.8 * 0.92, 0.0 * PI, 2.0 * PI);
-               self.context.fill().unwrap();
-               self.context.close_path();
+            PointChoice::Dot => {
+               let dot_radius = radius * 0.92;
+               context.move_to(cx + dot_radius, cy);
+               context.arc(cx, cy, dot_radius, 0.0 * PI, 2.0 * PI);
             }
-         }
-         PointChoice::Plus => {
-            for center in &drawable.centers {
-               self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
-               let (cx, cy) = self.context.user_to_device(center[0], center[1]);
-               self.transform_saver.restore_transform(&self.context);
-               let plus_delta = 2.8 * 1.48;
-               self.context.move_to(cx, cy - plus_delta);
-               self.context.line_to(cx, cy + plus_delta);
-               self.context.move_to(cx + plus_delta, cy);
-               self.context.line_to(cx - plus_delta, cy);
-               self.context.close_path();
+            PointChoice::Plus => {
+               let plus_delta = radius * 1.48;
+               context.move_to(cx, cy - plus_delta);
+               context.line_to(cx, cy + plus_delta);
+               context.move_to(cx + plus_delta, cy);
+               context.line_to(cx - plus_delta, cy);
+               context.close_path();
             }
-         }
-         PointChoice::Times => {
-            for center in &drawable.centers {
-               self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
-               let (cx, cy) = self.context.user_to_device(center[0], center[1]);
-               self.transform_saver.restore_transform(&self.context);
-               let times_delta = 2.8 * 1.48 * (0.5_f64).sqrt();
-               self.context.move_to(cx - times_delta, cy - times_delta);
-               self.context.line_to(cx + times_delta, cy + times_delta);
-               self.context.move_to(cx + times_delta, cy - times_delta);
-               self.context.line_to(cx - times_delta, cy + times_delta);
-               self.context.close_path();
+            PointChoice::Times => {
+               let times_delta = radius * 1.48 * (0.5_f64).sqrt();
+               context.move_to(cx - times_delta, cy - times_delta);
+               context.line_to(cx + times_delta, cy + times_delta);
+               context.move_to(cx + times_delta, cy - times_delta);
+               context.line_to(cx - times_delta, cy + times_delta);
+               context.close_path();
             }
-         }
-         PointChoice::Square => {
-            for center in &drawable.centers {
-               self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
-               let (cx, cy) = self.context.user_to_device(center[0], center[1]);
-               self.transform_saver.restore_transform(&self.context);
-               let square_delta = 2.8 * 1.1 * (0.5_f64).sqrt();
-               self.context.move_to(cx - square_delta, cy - square_delta);
-               self.context.line_to(cx + square_delta, cy - square_delta);
-               self.context.line_to(cx + square_delta, cy + square_delta);
-               self.context.line_to(cx - square_delta, cy + square_delta);
-               self.context.close_path();
+            PointChoice::Square | PointChoice::FilledSquare => {
+               let square_delta = radius * 1.1 * (0.5_f64).sqrt();
+               context.move_to(cx - square_delta, cy - square_delta);
+               context.line_to(cx + square_delta, cy - square_delta);
+               context.line_to(cx + square_delta, cy + square_delta);
+               context.line_to(cx - square_delta, cy + square_delta);
+               context.close_path();
+            }
+            PointChoice::Triangle | PointChoice::FilledTriangle => {
+               Self::polygon_path(
+                  context,
+                  &Self::polygon_vertices((cx, cy), 3, radius * 1.3, None, 90.0),
+               );
+            }
+            PointChoice::TriangleDown | PointChoice::FilledTriangleDown => {
+               Self::polygon_path(
+                  context,
+                  &Self::polygon_vertices((cx, cy), 3, radius * 1.3, None, 270.0),
+               );
+            }
+            PointChoice::Diamond | PointChoice::FilledDiamond => {
+               Self::polygon_path(
+                  context,
+                  &Self::polygon_vertices((cx, cy), 4, radius * 1.2, None, 90.0),
+               );
             }
+            PointChoice::Star | PointChoice::FilledStar => {
+               Self::polygon_path(
+                  context,
+                  &Self::polygon_vertices((cx, cy), 5, radius * 1.4, Some(radius * 0.6), 90.0),
+               );
+            }
+            PointChoice::Custom => {}
+         }
+         if is_filled {
+            context.fill().unwrap();
          }
       }
       self.context.stroke().unwrap();
    }
 
+   // Custom markers are drawn as segment sequences, with a group transform that takes the
+   // marker path to the canvas around each center.
+   fn draw_custom_points(
+      &mut self,
+      drawable: &PointsDrawable,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) {
+      let radius = diagram_choices.point_size * Self::POINT_RADIUS_RATIO * drawable.size;
+      let mut marker = drawable.custom_marker.clone();
+      marker.path_choices = marker
+         .path_choices
+         .inherit_from(&PathChoices { color: drawable.color_choice.clone(), ..Default::default() });
+
+      let previous = self.transform_saver.group_matrix;
+      for center in &drawable.centers {
+         let (x, y) = previous.transform_point(center[0], center[1]);
+         self.transform_saver.group_matrix = Matrix::new(
+            radius / canvas_layout.scale[0],
+            0.0,
+            0.0,
+            radius / canvas_layout.scale[1],
+            x,
+            y,
+         );
+         self.draw_segment_sequence(&marker, canvas_layout, diagram_choices);
+      }
+      self.transform_saver.group_matrix = previous;
+   }
+
    fn draw_arc(
       &mut self,
       path: &ArcPath,
@@ -383,7 +852,7 @@ impl UnfixedCairoSpartanRender {
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
    ) {
-      Self::set_line_choice(&self.context, path_choices.line_choice, diagram_choices);
+      Self::set_line_choice(&self.context, path_choices, diagram_choices);
 
       self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
 
@@ -406,11 +875,11 @@ impl UnfixedCairoSpartanRender {
          LineClosureChoice::Closes => {
             self.context.close_path();
             self.transform_saver.restore_transform(&self.context);
-            self.stroke_and_fill(&self.context, diagram_choices, path_choices);
+            self.stroke_and_fill(&self.context, canvas_layout, diagram_choices, path_choices);
          }
          LineClosureChoice::OpenEnd => {
             self.transform_saver.restore_transform(&self.context);
-            self.stroke_and_fill(&self.context, diagram_choices, path_choices);
+            self.stroke_and_fill(&self.context, canvas_layout, diagram_choices, path_choices);
          }
          LineClosureChoice::Unfinished => {
             self.transform_saver.restore_transform(&self.context);
@@ -426,7 +895,7 @@ impl UnfixedCairoSpartanRender {
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
    ) {
-      Self::set_line_choice(&self.context, path_choices.line_choice, diagram_choices);
+      Self::set_line_choice(&self.context, path_choices, diagram_choices);
 
       self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
 
@@ -446,11 +915,11 @@ impl UnfixedCairoSpartanRender {
          LineClosureChoice::Closes => {
             self.context.close_path();
             self.transform_saver.restore_transform(&self.context);
-            self.stroke_and_fill(&self.context, diagram_choices, path_choices);
+            self.stroke_and_fill(&self.context, canvas_layout, diagram_choices, path_choices);
          }
          LineClosureChoice::OpenEnd => {
             self.transform_saver.restore_transform(&self.context);
-            self.stroke_and_fill(&self.context, diagram_choices, path_choices);
+            self.stroke_and_fill(&self.context, canvas_layout, diagram_choices, path_choices);
          }
          LineClosureChoice::Unfinished => {
             self.transform_saver.restore_transform(&self.context);
@@ -488,6 +957,41 @@ impl UnfixedCairoSpartanRender {
       );
    }
 
+   fn draw_rat_cubic(
+      &mut self,
+      path: &RatCubicHomogWeighted,
+      path_choices: &PathChoices,
+      segment_choices: &SegmentChoices,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) {
+      // Rational cubics are drawn exactly when they reduce to a polynomial cubic.
+      if let Some(cubic) = rc_weighted_as_polynomial(path, 1.0e-9) {
+         self.draw_cubic(&cubic, path_choices, segment_choices, canvas_layout, diagram_choices);
+         return;
+      }
+
+      // Otherwise, since rational cubics are not supported in SVG, we do a simple polyline
+      // approximation, with the same number of segments as for hyperbolic paths.
+      let t_int: Vec<i32> = (0..=self.num_segments_hyperbolic).collect();
+      let mut t = Vec::<f64>::with_capacity(t_int.len());
+      let scale = (path.r[1] - path.r[0]) / f64::from(self.num_segments_hyperbolic);
+      let offset = path.r[0];
+      for item in &t_int {
+         t.push(f64::from(*item).mul_add(scale, offset));
+      }
+
+      let pattern_vec = path.eval_with_bilinear(&t);
+
+      self.draw_polyline(
+         &pattern_vec,
+         path_choices,
+         segment_choices,
+         canvas_layout,
+         diagram_choices,
+      );
+   }
+
    fn draw_polyline(
       &mut self,
       locations: &PolylinePath,
@@ -496,7 +1000,7 @@ impl UnfixedCairoSpartanRender {
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
    ) {
-      Self::set_line_choice(&self.context, path_choices.line_choice, diagram_choices);
+      Self::set_line_choice(&self.context, path_choices, diagram_choices);
 
       self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
       assert!(!locations.is_empty());
@@ -510,11 +1014,11 @@ impl UnfixedCairoSpartanRender {
          LineClosureChoice::Closes => {
             self.context.close_path();
             self.transform_saver.restore_transform(&self.context);
-            self.stroke_and_fill(&self.context, diagram_choices, path_choices);
+            self.stroke_and_fill(&self.context, canvas_layout, diagram_choices, path_choices);
          }
          LineClosureChoice::OpenEnd => {
             self.transform_saver.restore_transform(&self.context);
-            self.stroke_and_fill(&self.context, diagram_choices, path_choices);
+            self.stroke_and_fill(&self.context, canvas_layout, diagram_choices, path_choices);
          }
          LineClosureChoice::Unfinished => {
             self.transform_saver.restore_transform(&self.context);
@@ -552,7 +1056,8 @@ impl UnfixedCairoSpartanRender {
 
       let text_layout: &mut (dyn ZvxTextLayout + 'a) = boxed_text_layout.as_mut();
 
-      text_layout.set_layout("sans", font_size, single_text);
+      let font_choices = drawable.font_choices.inherit_from(&diagram_choices.font_choices);
+      text_layout.set_layout(&font_choices, &drawable.paragraph_choices, font_size, single_text);
 
       let metrics = text_layout.get_metrics().as_ref().unwrap();
 
@@ -570,7 +1075,7 @@ impl UnfixedCairoSpartanRender {
 
       let mut height_adjust = metrics.font_ascent - metrics.strikethrough_center;
       let multiline_adjust = metrics.text_height - metrics.font_height;
-      let mut width_adjust = 0.0;
+      let mut width_adjust = metrics.text_start;
 
       match drawable.anchor_choice {
          TextAnchorChoice::Centered => {
@@ -663,12 +1168,90 @@ impl UnfixedCairoSpartanRender {
       let (tx, ty) = cairo_context.user_to_device(single_text.location[0], single_text.location[1]);
       transform_saver.restore_transform(cairo_context);
 
-      cairo_context.move_to(
-         tx - width_adjust / f64::from(pango::SCALE),
-         ty - height_adjust / f64::from(pango::SCALE),
-      );
+      if single_text.rotation == 0.0 {
+         cairo_context.move_to(
+            tx - width_adjust / f64::from(pango::SCALE),
+            ty - height_adjust / f64::from(pango::SCALE),
+         );
+         let _ = generic_text_layout.render_layout();
+      } else {
+         cairo_context.move_to(tx, ty);
+         let _ = generic_text_layout.render_layout_rotated(
+            [width_adjust, height_adjust],
+            single_text.rotation.to_radians(),
+         );
+      }
+   }
+
+   // Clusters of characters are laid out individually, and placed along the path at their
+   // positions in the layout of the whole text.  As with text sets, this is not a class method.
+   #[inline]
+   fn draw_text_path_with_lifetimes<'semi_global, 'child, 'parent>(
+      transform_saver: &'semi_global mut TransformSaver,
+      cairo_context: &'parent CairoContext,
+      pango_context: &'child PangoContext,
+      drawable: &TextPathDrawable,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) where
+      'parent: 'child,
+   {
+      let (horizontal, vertical) = match drawable.anchor_choice {
+         TextAnchorChoice::Centered => (TextAnchorHorizontal::Center, TextAnchorVertical::Middle),
+         TextAnchorChoice::ThreeByThree(horizontal, vertical) => (horizontal, vertical),
+      };
+      // Left-anchored proxy, so that the vertical adjustment applies to each cluster.
+      let proxy = TextDrawable {
+         size_choice: drawable.size_choice,
+         offset_choice: drawable.offset_choice,
+         anchor_choice: TextAnchorChoice::ThreeByThree(TextAnchorHorizontal::Left, vertical),
+         font_choices: drawable.font_choices.clone(),
+         ..Default::default()
+      };
+      let whole_text = TextSingle {
+         content: drawable.content.clone(),
+         markup: MarkupChoice::Plain,
+         ..Default::default()
+      };
+      let (mut whole_layout, left_adjust, height_adjust) =
+         Self::layout_text(cairo_context, pango_context, &whole_text, &proxy, diagram_choices);
+      let clusters = whole_layout.get_clusters();
+      let pango_scale = f64::from(pango::SCALE);
+      let text_width = whole_layout.get_metrics().as_ref().unwrap().text_width / pango_scale;
+
+      let sampling =
+         ArcLengthSampling::create(&drawable.segments, transform_saver.path_metric(canvas_layout));
+      let offset_x = -left_adjust / pango_scale;
+      let start = match horizontal {
+         TextAnchorHorizontal::Left => offset_x,
+         TextAnchorHorizontal::Center => 0.5 * (sampling.length() - text_width),
+         TextAnchorHorizontal::Right => sampling.length() - text_width - offset_x,
+      };
 
-      let _ = generic_text_layout.render_layout();
+      Self::set_color(cairo_context, diagram_choices, &drawable.color_choice);
+      for cluster in clusters {
+         let middle = start + 0.5_f64.mul_add(cluster.width, cluster.start) / pango_scale;
+         let Some(placement) = sampling.placement_at(middle) else {
+            return;
+         };
+         transform_saver.save_set_path_transform(cairo_context, canvas_layout);
+         let (x, y) = cairo_context.user_to_device(placement.point[0], placement.point[1]);
+         let (dx, dy) = cairo_context
+            .user_to_device_distance(placement.direction[0], placement.direction[1])
+            .unwrap();
+         transform_saver.restore_transform(cairo_context);
+
+         let cluster_text = TextSingle {
+            content: cluster.content,
+            markup: MarkupChoice::Plain,
+            ..Default::default()
+         };
+         let (mut cluster_layout, _, _) =
+            Self::layout_text(cairo_context, pango_context, &cluster_text, &proxy, diagram_choices);
+         cairo_context.move_to(x, y);
+         let _ = cluster_layout
+            .render_layout_rotated([0.5 * cluster.width, height_adjust], (-dy).atan2(dx));
+      }
    }
 
    #[allow(clippy::needless_lifetimes)]
@@ -692,13 +1275,31 @@ impl UnfixedCairoSpartanRender {
       }
    }
 
+   #[allow(clippy::needless_lifetimes)]
+   fn draw_text_path<'parent>(
+      &'parent mut self,
+      drawable: &TextPathDrawable,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) {
+      Self::draw_text_path_with_lifetimes(
+         &mut self.transform_saver,
+         &self.context,
+         &self.pango_context,
+         drawable,
+         canvas_layout,
+         diagram_choices,
+      );
+      self.context.new_path();
+   }
+
    fn draw_circles_set(
       &mut self,
       drawable: &Strokeable<CirclesSet>,
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
    ) {
-      Self::set_line_choice(&self.context, drawable.path_choices.line_choice, diagram_choices);
+      Self::set_line_choice(&self.context, &drawable.path_choices, diagram_choices);
 
       self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
       for center in &drawable.path.centers {
@@ -710,21 +1311,136 @@ impl UnfixedCairoSpartanRender {
          self.context.close_path();
       }
       self.transform_saver.restore_transform(&self.context);
-      self.stroke_and_fill(&self.context, diagram_choices, &drawable.path_choices);
+      self.stroke_and_fill(&self.context, canvas_layout, diagram_choices, &drawable.path_choices);
    }
 
-   fn draw_segment_sequence(
+   fn draw_segment(
       &mut self,
-      segment_sequence: &SegmentSequence,
+      segment: &OneOfSegment,
+      path_choices: &PathChoices,
+      segment_choices: &SegmentChoices,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) {
+      match segment {
+         OneOfSegment::Arc(path) => {
+            self.draw_arc(path, path_choices, segment_choices, canvas_layout, diagram_choices);
+         }
+         OneOfSegment::Cubic(path) => {
+            self.draw_cubic(path, path_choices, segment_choices, canvas_layout, diagram_choices);
+         }
+         OneOfSegment::Hyperbolic(path) => {
+            self.draw_hyperbolic(
+               path,
+               path_choices,
+               segment_choices,
+               canvas_layout,
+               diagram_choices,
+            );
+         }
+         OneOfSegment::Polyline(path) => {
+            self.draw_polyline(path, path_choices, segment_choices, canvas_layout, diagram_choices);
+         }
+         OneOfSegment::RatCubic(path) => {
+            self.draw_rat_cubic(
+               path,
+               path_choices,
+               segment_choices,
+               canvas_layout,
+               diagram_choices,
+            );
+         }
+         OneOfSegment::Neither => {}
+      }
+   }
+
+   // The clip path is built in diagram coordinates, and applies until the context is restored.
+   fn clip_to_region(
+      &mut self,
+      clip: &ClipRegion,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) {
+      match clip {
+         ClipRegion::Unclipped => return,
+         ClipRegion::Rectangle(range) => {
+            self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
+            self.context.rectangle(range[0], range[1], range[2] - range[0], range[3] - range[1]);
+            self.transform_saver.restore_transform(&self.context);
+         }
+         ClipRegion::Path(segments) => {
+            let mut segment_choices = SegmentChoices {
+               closure: LineClosureChoice::Unfinished,
+               continuation: ContinuationChoice::Starts,
+            };
+            for segment in segments {
+               self.draw_segment(
+                  segment,
+                  &PathChoices::default(),
+                  &segment_choices,
+                  canvas_layout,
+                  diagram_choices,
+               );
+               segment_choices.continuation = ContinuationChoice::Continues;
+            }
+            self.context.close_path();
+         }
+      }
+      self.context.clip();
+   }
+
+   // Images are embedded in SVG output as the original PNG data.
+   #[allow(clippy::missing_panics_doc)]
+   // Images that cannot be read or decoded are reported as errors, before anything is drawn.
+   fn draw_image(
+      &mut self,
+      drawable: &ImageDrawable,
+      canvas_layout: &CanvasLayout,
+   ) -> Result<(), Box<dyn Error>> {
+      let png_data = match &drawable.source {
+         ImageSource::Unset => return Ok(()),
+         ImageSource::PngData(data) => data.clone(),
+         ImageSource::PngFile(path) => std::fs::read(path)
+            .map_err(|error| format!("Unable to read PNG file \"{path}\": {error}"))?,
+      };
+      let image = ImageSurface::create_from_png(&mut Cursor::new(&png_data))
+         .map_err(|error| format!("Unable to decode PNG image: {error}"))?;
+      let (image_width, image_height) = (f64::from(image.width()), f64::from(image.height()));
+      image.set_mime_data(cairo::MIME_TYPE_PNG, png_data)?;
+
+      let [left, bottom, right, top] = drawable.rectangle;
+      self.context.save()?;
+      self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
+      // Image rows run downwards from the top of the rectangle.
+      self.context.translate(left, top);
+      self.context.scale((right - left) / image_width, (bottom - top) / image_height);
+      self.context.rectangle(0.0, 0.0, image_width, image_height);
+      self.context.clip();
+      self.context.set_source_surface(&image, 0.0, 0.0)?;
+      self.context.source().set_filter(match drawable.interpolation {
+         ImageInterpolation::Smooth => cairo::Filter::Good,
+         ImageInterpolation::Nearest => cairo::Filter::Nearest,
+      });
+      self.context.paint_with_alpha(drawable.opacity)?;
+      self.transform_saver.restore_transform(&self.context);
+      self.context.restore()?;
+      Ok(())
+   }
+
+   fn draw_segments(
+      &mut self,
+      segments: &[OneOfSegment],
+      completion: PathCompletion,
+      path_choices: &PathChoices,
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
    ) {
       let mut line_closure_choice = LineClosureChoice::Unfinished;
       let mut line_continuation_choice = ContinuationChoice::Starts;
-      for i in 0..segment_sequence.segments.len() {
-         let segment = &segment_sequence.segments[i];
-         if i == (segment_sequence.segments.len() - 1) {
-            if segment_sequence.completion == PathCompletion::Closed {
+      for i in 0..segments.len() {
+         let segment = &segments[i];
+         if i == (segments.len() - 1) {
+            if completion == PathCompletion::Closed {
                line_closure_choice = LineClosureChoice::Closes;
             } else {
                line_closure_choice = LineClosureChoice::OpenEnd;
@@ -732,123 +1448,419 @@ impl UnfixedCairoSpartanRender {
          }
          let segment_choices: SegmentChoices =
             SegmentChoices { closure: line_closure_choice, continuation: line_continuation_choice };
+         self.draw_segment(segment, path_choices, &segment_choices, canvas_layout, diagram_choices);
 
-         match &segment {
-            OneOfSegment::Arc(path) => {
-               self.draw_arc(
-                  path,
-                  &segment_sequence.path_choices,
-                  &segment_choices,
-                  canvas_layout,
-                  diagram_choices,
-               );
-            }
-            OneOfSegment::Cubic(path) => {
-               self.draw_cubic(
-                  path,
-                  &segment_sequence.path_choices,
-                  &segment_choices,
-                  canvas_layout,
-                  diagram_choices,
-               );
-            }
-            OneOfSegment::Hyperbolic(path) => {
-               self.draw_hyperbolic(
-                  path,
-                  &segment_sequence.path_choices,
-                  &segment_choices,
-                  canvas_layout,
-                  diagram_choices,
-               );
-            }
-            OneOfSegment::Polyline(path) => {
-               self.draw_polyline(
-                  path,
-                  &segment_sequence.path_choices,
-                  &segment_choices,
-                  canvas_layout,
-                  diagram_choices,
-               );
+         line_continuation_choice = ContinuationChoice::Continues;
+      }
+   }
+
+   // Marker drawn in canvas coordinates, with tip at the placement point, pointing in the
+   // placement direction.
+   fn draw_marker(
+      &mut self,
+      marker: MarkerChoice,
+      placement: &SegmentPlacement,
+      path_choices: &PathChoices,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) {
+      if marker == MarkerChoice::None {
+         return;
+      }
+      self.transform_saver.save_set_path_transform(&self.context, canvas_layout);
+      let tip = self.context.user_to_device(placement.point[0], placement.point[1]);
+      let direction =
+         self.context.user_to_device_distance(placement.direction[0], placement.direction[1]);
+      self.transform_saver.restore_transform(&self.context);
+      let Ok(direction) = direction else {
+         return;
+      };
+      let norm = direction.0.hypot(direction.1);
+      if norm == 0.0 {
+         return;
+      }
+      let u = (direction.0 / norm, direction.1 / norm);
+      let n = (-u.1, u.0);
+
+      let parameters = marker.to_marker_parameters(path_choices.markers.size, diagram_choices);
+      let length = parameters.length;
+      let half_width = parameters.half_width;
+      // Point displaced from another along a unit vector.
+      let displaced = |from: (f64, f64), distance: f64, v: (f64, f64)| {
+         (distance.mul_add(v.0, from.0), distance.mul_add(v.1, from.1))
+      };
+      let back = displaced(tip, -length, u);
+      let back_left = displaced(back, half_width, n);
+      let back_right = displaced(back, -half_width, n);
+
+      Self::set_line_choice(&self.context, path_choices, diagram_choices);
+      self.context.set_dash(&[], 0.0);
+      Self::set_color(&self.context, diagram_choices, &path_choices.color);
+      match marker {
+         MarkerChoice::None => {}
+         MarkerChoice::Open => {
+            self.context.move_to(back_left.0, back_left.1);
+            self.context.line_to(tip.0, tip.1);
+            self.context.line_to(back_right.0, back_right.1);
+            self.context.stroke().unwrap();
+         }
+         MarkerChoice::Filled | MarkerChoice::Barbed => {
+            self.context.move_to(tip.0, tip.1);
+            self.context.line_to(back_left.0, back_left.1);
+            if marker == MarkerChoice::Barbed {
+               let notch = displaced(back, MarkerChoice::BARB_NOTCH * length, u);
+               self.context.line_to(notch.0, notch.1);
             }
-            OneOfSegment::Neither => {}
+            self.context.line_to(back_right.0, back_right.1);
+            self.context.close_path();
+            self.context.fill().unwrap();
+         }
+         MarkerChoice::Circle => {
+            self.context.move_to(tip.0 + half_width, tip.1);
+            self.context.arc(tip.0, tip.1, half_width, 0.0, 2.0 * PI);
+            self.context.close_path();
+            self.context.fill().unwrap();
          }
+      }
+   }
 
-         line_continuation_choice = ContinuationChoice::Continues;
+   // Mid markers go at interior polyline vertices and joins between segments.  A single curve
+   // has its mid marker at the middle of its range.
+   fn mid_placements(segments: &[OneOfSegment], metric: [f64; 2]) -> Vec<SegmentPlacement> {
+      let mut placements = Vec::<SegmentPlacement>::new();
+      let mut previous_end: Option<SegmentPlacement> = None;
+      for segment in segments {
+         let Some([start, end]) = segment_ends(segment) else {
+            continue;
+         };
+         if let Some(previous) = previous_end {
+            placements.push(SegmentPlacement {
+               point: start.point,
+               direction: bisect_directions(previous.direction, start.direction, metric),
+            });
+         }
+         if matches!(segment, OneOfSegment::Polyline(_)) || (segments.len() == 1) {
+            placements.extend(segment_interior_placements(segment, metric));
+         }
+         previous_end = Some(end);
+      }
+      placements
+   }
+
+   fn draw_segment_sequence(
+      &mut self,
+      segment_sequence: &SegmentSequence,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) {
+      let segments = &segment_sequence.segments;
+      let path_choices = &segment_sequence.path_choices;
+      let markers = &path_choices.markers;
+      if (*markers == MarkerChoices::default()) || segments.is_empty() {
+         self.draw_segments(
+            segments,
+            segment_sequence.completion,
+            path_choices,
+            canvas_layout,
+            diagram_choices,
+         );
+         return;
+      }
+
+      // Metric for distances on canvas.
+      let metric = self.transform_saver.path_metric(canvas_layout);
+      let is_open = segment_sequence.completion == PathCompletion::Open;
+      let last = segments.len() - 1;
+      let start_placement = segment_ends(&segments[0]).map(|ends| SegmentPlacement {
+         point: ends[0].point,
+         direction: [-ends[0].direction[0], -ends[0].direction[1]],
+      });
+      let end_placement = segment_ends(&segments[last]).map(|ends| ends[1]);
+      let mid_placements = Self::mid_placements(segments, metric);
+
+      let mut trimmed_segments = segments.clone();
+      if is_open {
+         let start_setback =
+            markers.start.to_marker_parameters(markers.size, diagram_choices).setback;
+         let end_setback = markers.end.to_marker_parameters(markers.size, diagram_choices).setback;
+         trimmed_segments[0] = trim_segment(&trimmed_segments[0], [start_setback, 0.0], metric);
+         trimmed_segments[last] = trim_segment(&trimmed_segments[last], [0.0, end_setback], metric);
+      }
+      self.draw_segments(
+         &trimmed_segments,
+         segment_sequence.completion,
+         path_choices,
+         canvas_layout,
+         diagram_choices,
+      );
+
+      for placement in &mid_placements {
+         self.draw_marker(markers.mid, placement, path_choices, canvas_layout, diagram_choices);
+      }
+      if is_open {
+         if let Some(placement) = start_placement {
+            self.draw_marker(
+               markers.start,
+               &placement,
+               path_choices,
+               canvas_layout,
+               diagram_choices,
+            );
+         }
+         if let Some(placement) = end_placement {
+            self.draw_marker(markers.end, &placement, path_choices, canvas_layout, diagram_choices);
+         }
+      }
+   }
+
+   // Single-segment drawables with markers are drawn as one-segment sequences.
+   fn marked_single_segment(drawable: &OneOfDrawable) -> Option<SegmentSequence> {
+      let (segment, path_choices) = match drawable {
+         OneOfDrawable::Arc(d) => (OneOfSegment::Arc(d.path.clone()), &d.path_choices),
+         OneOfDrawable::Cubic(d) => (OneOfSegment::Cubic(d.path.clone()), &d.path_choices),
+         OneOfDrawable::Hyperbolic(d) => {
+            (OneOfSegment::Hyperbolic(d.path.clone()), &d.path_choices)
+         }
+         OneOfDrawable::Polyline(d) => (OneOfSegment::Polyline(d.path.clone()), &d.path_choices),
+         OneOfDrawable::RatCubic(d) => (OneOfSegment::RatCubic(d.path.clone()), &d.path_choices),
+         _ => return None,
+      };
+      if path_choices.markers == MarkerChoices::default() {
+         return None;
       }
+      Some(SegmentSequence {
+         completion: PathCompletion::Open,
+         path_choices: path_choices.clone(),
+         segments: vec![segment],
+      })
    }
 
+   // Draws a zero-length stroke that the output stream filter replaces with element tags.  The
+   // clip is reset so that the marker is neither culled nor wrapped in a clip group.
+   #[allow(clippy::cast_precision_loss)]
+   fn draw_annotation_marker(&self, code: usize) {
+      let context = &self.context;
+      context.save().unwrap();
+      context.reset_clip();
+      context.identity_matrix();
+      let (x1, y1, x2, y2) = context.clip_extents().unwrap();
+      let center = (0.5 * (x1 + x2), 0.5 * (y1 + y2));
+      context.set_line_cap(cairo::LineCap::Butt);
+      context.set_line_join(cairo::LineJoin::Miter);
+      context.set_miter_limit(MARKER_MITER_BASE + code as f64);
+      context.move_to(center.0, center.1);
+      context.line_to(center.0, center.1);
+      context.stroke().unwrap();
+      context.restore().unwrap();
+   }
+
+   // Registers the annotation, if not empty, and marks the start of its element.  The returned
+   // marker code is passed to the closing call.
+   fn open_annotation(&self, annotation: ElementAnnotation) -> Option<usize> {
+      if annotation.is_empty() {
+         return None;
+      }
+      let code = {
+         let mut annotations = self.annotations.borrow_mut();
+         annotations.push(annotation);
+         2 * (annotations.len() - 1)
+      };
+      self.draw_annotation_marker(code);
+      Some(code)
+   }
+
+   fn close_annotation(&self, marker_code: Option<usize>) {
+      if let Some(code) = marker_code {
+         self.draw_annotation_marker(code + 1);
+      }
+   }
+
+   fn draw_group(
+      &mut self,
+      drawable: &GroupDrawable,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) -> Result<(), Box<dyn Error>> {
+      let marker_code = self.open_annotation(ElementAnnotation {
+         id: drawable.id.clone(),
+         class: drawable.class.clone(),
+         link: drawable.link.clone(),
+         tooltip: drawable.tooltip.clone(),
+         alt_text: drawable.alt_text.clone(),
+      });
+      let previous = self.transform_saver.push_group_transform(drawable.transform, drawable.offset);
+      self.render_nested_drawables(&drawable.styled_drawables(), canvas_layout, diagram_choices)?;
+      self.transform_saver.group_matrix = previous;
+      self.close_annotation(marker_code);
+      Ok(())
+   }
+
+   #[allow(clippy::missing_errors_doc)]
    #[allow(clippy::missing_panics_doc)]
    pub fn render_drawables_impl(
       &mut self,
       drawables: &[QualifiedDrawable],
       canvas_layout: &CanvasLayout,
       diagram_choices: &DiagramChoices,
-   ) {
+   ) -> Result<(), Box<dyn Error>> {
+      let layer_choices = &diagram_choices.layer_choices;
+      let indices = layer_choices.draw_order(drawables);
+      for run in indices.chunk_by(|&a, &b| drawables[a].layer == drawables[b].layer) {
+         let layer = drawables[run[0]].layer;
+         let opacity = layer_choices.opacity(layer);
+         let is_translucent = !is_near_float(opacity, 1.0);
+         if is_translucent {
+            self.context.push_group();
+         }
+         for &i in run {
+            self.render_drawable(&drawables[i].drawable, canvas_layout, diagram_choices)?;
+         }
+         if is_translucent {
+            self.context.pop_group_to_source().unwrap();
+            self.context.paint_with_alpha(opacity).unwrap();
+         }
+      }
+      Ok(())
+   }
+
+   // Children of groups and clipped drawables are drawn with their parent, and so only sorted by
+   // layer.  Layer visibility and opacity apply at the top level.
+   fn render_nested_drawables(
+      &mut self,
+      drawables: &[QualifiedDrawable],
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) -> Result<(), Box<dyn Error>> {
+      for i in LayerChoices::sort_order(drawables) {
+         self.render_drawable(&drawables[i].drawable, canvas_layout, diagram_choices)?;
+      }
+      Ok(())
+   }
+
+   fn render_drawable(
+      &mut self,
+      drawable: &OneOfDrawable,
+      canvas_layout: &CanvasLayout,
+      diagram_choices: &DiagramChoices,
+   ) -> Result<(), Box<dyn Error>> {
       let segment_choices: SegmentChoices = SegmentChoices::default();
 
-      let mut indices = (0..drawables.len()).collect::<Vec<_>>();
-      indices.sort_by_key(|&i| &drawables[i].layer);
+      if let Some(segment_sequence) = Self::marked_single_segment(drawable) {
+         self.draw_segment_sequence(&segment_sequence, canvas_layout, diagram_choices);
+         return Ok(());
+      }
+      match drawable {
+         OneOfDrawable::Lines(drawable) => {
+            self.draw_lines_set(drawable, canvas_layout, diagram_choices);
+         }
+         OneOfDrawable::Arc(drawable) => {
+            self.draw_arc(
+               &drawable.path,
+               &drawable.path_choices,
+               &segment_choices,
+               canvas_layout,
+               diagram_choices,
+            );
+         }
+         OneOfDrawable::Hyperbolic(drawable) => {
+            self.draw_hyperbolic(
+               &drawable.path,
+               &drawable.path_choices,
+               &segment_choices,
+               canvas_layout,
+               diagram_choices,
+            );
+         }
+         OneOfDrawable::Cubic(drawable) => {
+            self.draw_cubic(
+               &drawable.path,
+               &drawable.path_choices,
+               &segment_choices,
+               canvas_layout,
+               diagram_choices,
+            );
+         }
+         OneOfDrawable::Points(drawable) => {
+            self.draw_points_set(drawable, canvas_layout, diagram_choices);
+         }
+         OneOfDrawable::Text(drawable) => {
+            self.draw_text_set(drawable, canvas_layout, diagram_choices);
+         }
+         OneOfDrawable::TextPath(drawable) => {
+            self.draw_text_path(drawable, canvas_layout, diagram_choices);
+         }
+         OneOfDrawable::Circles(drawable) => {
+            self.draw_circles_set(drawable, canvas_layout, diagram_choices);
+         }
+         OneOfDrawable::Polyline(drawable) => {
+            self.draw_polyline(
+               &drawable.path,
+               &drawable.path_choices,
+               &segment_choices,
+               canvas_layout,
+               diagram_choices,
+            );
+         }
+         OneOfDrawable::RatCubic(drawable) => {
+            self.draw_rat_cubic(
+               &drawable.path,
+               &drawable.path_choices,
+               &segment_choices,
+               canvas_layout,
+               diagram_choices,
+            );
+         }
+         OneOfDrawable::SegmentSequence(drawable) => {
+            self.draw_segment_sequence(drawable, canvas_layout, diagram_choices);
+         }
+         OneOfDrawable::Group(drawable) => {
+            self.draw_group(drawable, canvas_layout, diagram_choices)?;
+         }
+         OneOfDrawable::Clipped(drawable) => {
+            self.context.save().unwrap();
+            self.clip_to_region(&drawable.clip, canvas_layout, diagram_choices);
+            self.render_nested_drawables(&drawable.drawables, canvas_layout, diagram_choices)?;
+            self.context.restore().unwrap();
+         }
+         OneOfDrawable::Image(drawable) => {
+            let marker_code = self.open_annotation(ElementAnnotation {
+               alt_text: drawable.alt_text.clone(),
+               ..Default::default()
+            });
+            self.draw_image(drawable, canvas_layout)?;
+            self.close_annotation(marker_code);
+         }
+         OneOfDrawable::Neither => {}
+      }
+      Ok(())
+   }
+}
+
+impl<W> ZvxExtensible for CairoSpartanRender<W> {
+   fn capability_names(&self) -> Vec<&'static str> {
+      vec![SvgDocumentUnit::NAME]
+   }
 
-      for i in indices {
-         match &drawables[i].drawable {
-            OneOfDrawable::Lines(drawable) => {
-               self.draw_lines_set(drawable, canvas_layout, diagram_choices);
-            }
-            OneOfDrawable::Arc(drawable) => {
-               self.draw_arc(
-                  &drawable.path,
-                  &drawable.path_choices,
-                  &segment_choices,
-                  canvas_layout,
-                  diagram_choices,
-               );
-            }
-            OneOfDrawable::Hyperbolic(drawable) => {
-               self.draw_hyperbolic(
-                  &drawable.path,
-                  &drawable.path_choices,
-                  &segment_choices,
-                  canvas_layout,
-                  diagram_choices,
-               );
-            }
-            OneOfDrawable::Cubic(drawable) => {
-               self.draw_cubic(
-                  &drawable.path,
-                  &drawable.path_choices,
-                  &segment_choices,
-                  canvas_layout,
-                  diagram_choices,
-               );
-            }
-            OneOfDrawable::Points(drawable) => {
-               self.draw_points_set(drawable, canvas_layout, diagram_choices);
-            }
-            OneOfDrawable::Text(drawable) => {
-               self.draw_text_set(drawable, canvas_layout, diagram_choices);
-            }
-            OneOfDrawable::Circles(drawable) => {
-               self.draw_circles_set(drawable, canvas_layout, diagram_choices);
-            }
-            OneOfDrawable::Polyline(drawable) => {
-               self.draw_polyline(
-                  &drawable.path,
-                  &drawable.path_choices,
-                  &segment_choices,
-                  canvas_layout,
-                  diagram_choices,
-               );
-            }
-            OneOfDrawable::SegmentSequence(drawable) => {
-               self.draw_segment_sequence(drawable, canvas_layout, diagram_choices);
-            }
-            OneOfDrawable::Neither => {}
+   fn invoke_erased(
+      &mut self,
+      name: &str,
+      argument: Box<dyn Any>,
+   ) -> Result<Box<dyn Any>, Box<dyn Error>> {
+      match name {
+         SvgDocumentUnit::NAME => {
+            invoke_handler::<_, SvgDocumentUnit>(self, argument, |render, unit| {
+               render.unfixed.surface.set_document_unit(unit);
+               Ok(())
+            })
          }
+         _ => Err(format!("Capability `{name}` is not supported.").into()),
       }
    }
 }
 
-impl ZvxRenderEngine for CairoSpartanRender {
+impl<W: Write + 'static> ZvxRenderEngine for CairoSpartanRender<W> {
+   type Output = W;
+
    fn create_text_layout<'parent, 'a>(&'parent self) -> Box<dyn ZvxTextLayout + 'a>
    where
       'parent: 'a,
@@ -858,39 +1870,12 @@ impl ZvxRenderEngine for CairoSpartanRender {
 
    #[allow(clippy::missing_errors_doc)]
    #[allow(clippy::missing_panics_doc)]
-   fn render_drawables(
-      &mut self,
-      drawables: &[QualifiedDrawable],
-   ) -> Result<Box<dyn core::any::Any>, Box<dyn Error>> {
+   fn render_drawables(&mut self, drawables: &[QualifiedDrawable]) -> Result<W, Box<dyn Error>> {
       let canvas_layout: &CanvasLayout = &self.canvas_layout;
       let diagram_choices: &DiagramChoices = &self.diagram_choices;
 
-      self.unfixed.render_drawables_impl(drawables, canvas_layout, diagram_choices);
-
-      self.unfixed.surface.flush();
+      self.unfixed.render_drawables_impl(drawables, canvas_layout, diagram_choices)?;
 
-      match self.unfixed.surface.finish_output_stream() {
-         Ok(good) => Ok(good),
-         // SvgSurface keeps the stream, and returns when there is an error, but we just drop it
-         // and pass the error.
-         Err(stream_with_error) => Err(Box::new(stream_with_error.error)),
-      }
-   }
-
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_0(&mut self) -> Result<(), Box<dyn Error>> {
-      Err("Cairo-pango text layout does not implement `special_function_0`.".into())
-   }
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_1(&mut self) -> Result<(), Box<dyn Error>> {
-      Err("Cairo-pango text layout does not implement `special_function_1`.".into())
-   }
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_2(&mut self) -> Result<(), Box<dyn Error>> {
-      Err("Cairo-pango text layout does not implement `special_function_2`.".into())
-   }
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_3(&mut self) -> Result<(), Box<dyn Error>> {
-      Err("Cairo-pango text layout does not implement `special_function_3`.".into())
+      self.finish_stream()
    }
 }
@@ -0,0 +1,126 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+use zvx_cairo::annotation::{AnnotatingWriter, DocumentAnnotation, ElementAnnotation};
+
+// Filters the input, written in small chunks so that tags are split across writes.
+fn filter(
+   input: &str,
+   annotations: Vec<ElementAnnotation>,
+   document: DocumentAnnotation,
+) -> String {
+   let mut writer = AnnotatingWriter::new(Vec::new(), Rc::new(RefCell::new(annotations)), document);
+   for chunk in input.as_bytes().chunks(7) {
+      writer.write_all(chunk).unwrap();
+   }
+   String::from_utf8(writer.into_inner().unwrap()).unwrap()
+}
+
+fn row_annotation() -> ElementAnnotation {
+   ElementAnnotation {
+      id: Some("row".to_string()),
+      link: Some("https://example.com/?a=1&b=2".to_string()),
+      tooltip: Some("Row <1>".to_string()),
+      ..Default::default()
+   }
+}
+
+#[test]
+fn marker_replacement_test() {
+   let input = concat!(
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10pt\" height=\"10pt\">\n",
+      "<path fill=\"none\" stroke-miterlimit=\"900000\" d=\"M 5 5 L 5 5 \"/>\n",
+      "<path fill=\"none\" stroke-miterlimit=\"10\" d=\"M 0 0 L 10 10 \"/>\n",
+      "<path fill=\"none\" stroke-miterlimit=\"900001\" d=\"M 5 5 L 5 5 \"/>\n",
+      "</svg>\n",
+   );
+   let expected = concat!(
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10pt\" height=\"10pt\">\n",
+      "<a xlink:href=\"https://example.com/?a=1&amp;b=2\">\n",
+      "<g id=\"row\">\n",
+      "<title>Row &lt;1&gt;</title>\n",
+      "<path fill=\"none\" stroke-miterlimit=\"10\" d=\"M 0 0 L 10 10 \"/>\n",
+      "</g>\n",
+      "</a>\n",
+      "</svg>\n",
+   );
+   assert_eq!(filter(input, vec![row_annotation()], DocumentAnnotation::default()), expected);
+}
+
+#[test]
+fn marker_layout_test() {
+   // Markers are found by element, in style form, without line breaks.
+   let input = concat!(
+      "<svg width=\"10pt\"><path style=\"stroke-miterlimit:900002;\" d=\"M 5 5 L 5 5\"/>",
+      "<path style=\"stroke-miterlimit:4;\" d=\"M 0 0 L 1 1\"/>",
+      "<path style=\"stroke-miterlimit:900003;\" d=\"M 5 5 L 5 5\"/></svg>",
+   );
+   let annotations = vec![
+      row_annotation(),
+      ElementAnnotation { class: Some("cell".to_string()), ..Default::default() },
+   ];
+   let expected = concat!(
+      "<svg width=\"10pt\"><g class=\"cell\">",
+      "<path style=\"stroke-miterlimit:4;\" d=\"M 0 0 L 1 1\"/>",
+      "</g></svg>",
+   );
+   assert_eq!(filter(input, annotations, DocumentAnnotation::default()), expected);
+}
+
+#[test]
+fn unmatched_marker_test() {
+   // Large miter limits of other elements, and codes without annotations, are left alone.
+   let input = concat!(
+      "<svg width=\"10pt\">\n",
+      "<rect stroke-miterlimit=\"900000\" width=\"1\" height=\"1\"/>\n",
+      "<path stroke-miterlimit=\"900010\" d=\"M 5 5 L 5 5\"/>\n",
+      "<path stroke-miterlimit=\"100000\" d=\"M 0 0 L 1 1\" title='a>b'/>\n",
+      "</svg>\n",
+   );
+   assert_eq!(filter(input, vec![row_annotation()], DocumentAnnotation::default()), input);
+}
+
+#[test]
+fn document_annotation_test() {
+   let input = "<?xml version=\"1.0\"?>\n<svg width=\"10pt\">\n<path d=\"M 0 0\"/>\n</svg>\n";
+   let document = DocumentAnnotation {
+      title: Some("Fish & chips".to_string()),
+      description: Some("A \"plate\".".to_string()),
+      id_prefix: "menu".to_string(),
+   };
+   let expected = concat!(
+      "<?xml version=\"1.0\"?>\n",
+      "<svg width=\"10pt\" role=\"img\" aria-labelledby=\"menu-title menu-description\">\n",
+      "<title id=\"menu-title\">Fish &amp; chips</title>\n",
+      "<desc id=\"menu-description\">A &quot;plate&quot;.</desc>\n",
+      "<path d=\"M 0 0\"/>\n",
+      "</svg>\n",
+   );
+   assert_eq!(filter(input, Vec::new(), document.clone()), expected);
+
+   // Default ids, with only a title.
+   let document = DocumentAnnotation { title: document.title, ..Default::default() };
+   let expected = concat!(
+      "<?xml version=\"1.0\"?>\n",
+      "<svg width=\"10pt\" role=\"img\" aria-labelledby=\"diagram-title\">\n",
+      "<title id=\"diagram-title\">Fish &amp; chips</title>\n",
+      "<path d=\"M 0 0\"/>\n",
+      "</svg>\n",
+   );
+   assert_eq!(filter(input, Vec::new(), document), expected);
+   assert_eq!(filter(input, Vec::new(), DocumentAnnotation::default()), input);
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::error::Error;
 use std::f64::consts::PI;
 use std::io::Write;
 use zvx_cairo::CairoSpartanRender;
@@ -23,16 +24,16 @@ use zvx_golden::reduced::to_writer_pretty_reduced;
 // This may seem odd, but is Rust-inspired. The diagram and the renderer can be separately
 // borrowed with different mutability.
 // #[derive(Debug)]
-pub struct NotBoxedCairoSpartanCombo {
+pub struct NotBoxedCairoSpartanCombo<W> {
    pub drawable_diagram: DrawableDiagram,
-   pub render_controller: Box<CairoSpartanRender>,
+   pub render_controller: Box<CairoSpartanRender<W>>,
 }
 
 // Simplified test version of the standard combo structure that does not box-trait the renderer.
 // This provides direct access to rendering members.
-impl NotBoxedCairoSpartanCombo {
+impl<W: Write + 'static> NotBoxedCairoSpartanCombo<W> {
    #[allow(clippy::missing_panics_doc)]
-   pub fn create_for_stream<W: Write + 'static>(
+   pub fn create_for_stream(
       out_stream: W,
       preparation: &SpartanPreparation,
    ) -> Self {
@@ -51,8 +52,8 @@ impl NotBoxedCairoSpartanCombo {
 fn write_full_sample_to_write<W: Write + 'static>(
    out_stream: W,
    preparation: &SpartanPreparation,
-) -> Result<Box<dyn core::any::Any>, cairo::StreamWithError> {
-   let mut cairo_spartan: NotBoxedCairoSpartanCombo =
+) -> Result<W, Box<dyn Error>> {
+   let mut cairo_spartan: NotBoxedCairoSpartanCombo<W> =
       NotBoxedCairoSpartanCombo::create_for_stream(out_stream, preparation);
 
    cairo_spartan.render_controller.unfixed.context.set_line_width(1.0);
@@ -214,8 +215,7 @@ fn write_full_sample_to_write<W: Write + 'static>(
    transform_saver.restore_transform(context);
    pangocairo::functions::show_layout(context, &text_layout);
 
-   cairo_spartan.render_controller.unfixed.surface.flush();
-   cairo_spartan.render_controller.unfixed.surface.finish_output_stream()
+   cairo_spartan.render_controller.finish_stream()
 }
 
 #[test]
@@ -242,6 +242,6 @@ fn simple_full_spartan_test() {
    {
       let svg_golden = SvgGoldenTest::new("tests/goldenfiles/", "simple_spartan");
       let raw_result = write_full_sample_to_write(svg_golden.get_raw_writeable(), &preparation);
-      svg_golden.handover_result(raw_result.unwrap());
+      svg_golden.handover_content(&raw_result.unwrap());
    }
 }
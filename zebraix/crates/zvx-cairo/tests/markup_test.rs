@@ -0,0 +1,58 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use zvx_drawable::markup::is_well_formed_markup;
+
+// The well-formedness check used for automatic markup agrees with Pango's parser, so that
+// backends without Pango interpret text in the same way.
+#[test]
+fn pango_agreement_test() {
+   let samples = [
+      "<span foreground=\"red\">a <b>bold</b> word</span>",
+      "<span size='small' font_family=\"mono\">x</span>",
+      "a &lt; b &#x3B1; &#945;<sub >1</sub >",
+      "<span foreground=\"red\"/>a > b",
+      "<b>unclosed",
+      "stray</i>",
+      "<b><i>x</b></i>",
+      "x<sub",
+      "<bold>x</bold>",
+      "<span colour=\"red\">x</span>",
+      "<b weight=\"bold\">x</b>",
+      "<span size=small>x</span>",
+      "<span size=\"small>x</span>",
+      "<span size=\"small\"weight=\"bold\">x</span>",
+      "R&D",
+      "&nbsp;",
+      "&#0;",
+      "<span lang=\"R&D\">x</span>",
+      "<span lang=\"R&amp;D\">x</span>",
+      "<span size = \"small\"weight=\"bold\">x</span>",
+      "< b>x</b>",
+      "<B>x</B>",
+      "<span>x</span  >",
+      "<spanweight=\"bold\">x</span>",
+      "<span weight=\"bold\" =\"x\">x</span>",
+      "<i>a<sub>1</sub><sup/></i>",
+      "&#x110000;",
+      "&#X41;",
+   ];
+   for sample in samples {
+      assert_eq!(
+         is_well_formed_markup(sample),
+         pango::parse_markup(sample, '\0').is_ok(),
+         "Disagreement on {sample:?}."
+      );
+   }
+}
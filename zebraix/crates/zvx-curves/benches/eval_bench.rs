@@ -0,0 +1,93 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Compares batch evaluation into a buffer with evaluation that allocates per call.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use zvx_base::{CubicHomog, CubicPath, HyperbolicPath};
+use zvx_curves::base::TEval;
+use zvx_curves::test_helpers::{time_points, weighted_example_0};
+use zvx_curves::{CurveEval, CurveEvalInto};
+
+const NUM_POINTS: [i32; 2] = [64, 4096];
+
+fn cubic_example() -> CubicPath {
+   CubicPath {
+      r: [-4.5, 13.5],
+      h: CubicHomog([[4.0, 3.0 * 3.5, 3.0 * 4.5, 3.0], [-1.5, 3.0 * -2.0, 3.0 * 1.5, 2.0]]),
+      sigma: (3.6, 1.2),
+   }
+}
+
+fn hyperbolic_example() -> HyperbolicPath {
+   HyperbolicPath {
+      range: (-1.0, 1.5),
+      lambda: 2.0,
+      mu: 0.75,
+      offset: [0.5, -1.0],
+      minus_partial: [1.5, 0.25],
+      plus_partial: [-0.5, 2.0],
+      ..Default::default()
+   }
+}
+
+fn bench_curve<T: CurveEval + CurveEvalInto>(
+   c: &mut Criterion,
+   name: &str,
+   curve: &T,
+   r: [f64; 2],
+) {
+   let mut group = c.benchmark_group(name);
+   for num_points in NUM_POINTS {
+      let t = time_points(r, num_points - 1);
+      let mut out = vec![[0.0; 2]; t.len()];
+      group.bench_with_input(BenchmarkId::new("eval_with_bilinear", num_points), &t, |b, t| {
+         b.iter(|| black_box(curve.eval_with_bilinear(black_box(t))));
+      });
+      group.bench_with_input(BenchmarkId::new("eval_into", num_points), &t, |b, t| {
+         b.iter(|| curve.eval_into(black_box(t), black_box(&mut out)));
+      });
+   }
+   group.finish();
+}
+
+fn bench_cubic(c: &mut Criterion) {
+   let curve = cubic_example();
+   bench_curve(c, "cubic", &curve, curve.r);
+}
+
+fn bench_rat_quad(c: &mut Criterion) {
+   let curve = weighted_example_0();
+   bench_curve(c, "rat_quad", &curve, curve.r);
+}
+
+fn bench_hyperbolic(c: &mut Criterion) {
+   let path = hyperbolic_example();
+   let mut group = c.benchmark_group("hyperbolic");
+   for num_points in NUM_POINTS {
+      let t = time_points([path.range.0, path.range.1], num_points - 1);
+      let mut out = vec![[0.0; 2]; t.len()];
+      group.bench_with_input(BenchmarkId::new("eval_maybe_bilinear", num_points), &t, |b, t| {
+         b.iter(|| black_box(path.eval_maybe_bilinear(black_box(t))));
+      });
+      group.bench_with_input(BenchmarkId::new("eval_into", num_points), &t, |b, t| {
+         b.iter(|| path.eval_into(black_box(t), black_box(&mut out)));
+      });
+   }
+   group.finish();
+}
+
+criterion_group!(benches, bench_cubic, bench_rat_quad, bench_hyperbolic);
+criterion_main!(benches);
@@ -35,6 +35,12 @@ pub trait CurveEval {
    fn characterize_endpoints(&self) -> ([[f64; 2]; 2], [[f64; 2]; 2]);
 }
 
+// Batch evaluation into a caller-provided buffer, which must be the same length as `t`.  This
+// avoids allocation, and evaluates in a loop that the compiler can vectorize.
+pub trait CurveEvalInto {
+   fn eval_into(&self, t: &[f64], out: &mut [[f64; 2]]);
+}
+
 pub trait CurveTransform {
    fn displace(&mut self, d: [f64; 2]);
 
@@ -47,6 +53,23 @@ pub trait CurveTransform {
    fn select_range(&mut self, new_range: [f64; 2]);
 }
 
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PrecisionChoice {
+   #[default]
+   Ordinary,
+   // Error-free transformations and double-f64 intermediates, so that chained transformations
+   // stay accurate to near machine precision.
+   Compensated,
+}
+
+// Transformations with precision selected at call time.  Ordinary precision is the same as the
+// corresponding `CurveTransform` method.
+pub trait CurveTransformWithPrecision: CurveTransform {
+   fn bilinear_transform_with(&mut self, sigma_ratio: (f64, f64), precision: PrecisionChoice);
+
+   fn select_range_with(&mut self, new_range: [f64; 2], precision: PrecisionChoice);
+}
+
 // CurveMath: Bilinear transformation (timepoints).
 //
 // Weighted form, sort-of.
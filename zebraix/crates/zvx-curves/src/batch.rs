@@ -0,0 +1,168 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::base::CurveEvalInto;
+use zvx_base::{CubicPath, HyperbolicPath, RatCubicHomogWeighted, RatQuadHomogWeighted};
+
+// Rational curve in power form, for batch evaluation.
+//
+// The polynomials are in u = (t - center) * inv_half_span, which runs over [-1, 1] across the
+// range.  Building the power form is cheap, but it can be kept when the same curve is evaluated
+// many times.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatPowerForm<const N: usize> {
+   pub center: f64,
+   pub inv_half_span: f64,
+   pub x: [f64; N],
+   pub y: [f64; N],
+   pub w: [f64; N],
+}
+
+// Multiply polynomial (in ascending powers) by linear factor.
+#[inline]
+#[allow(clippy::suboptimal_flops)]
+fn poly_mul_linear<const N: usize>(p: &[f64; N], factor: [f64; 2]) -> [f64; N] {
+   let mut ret_val = [0.0; N];
+   for k in 0..N {
+      ret_val[k] = factor[0] * p[k] + if k > 0 { factor[1] * p[k - 1] } else { 0.0 };
+   }
+   ret_val
+}
+
+// Horner evaluation.
+#[inline]
+#[allow(clippy::suboptimal_flops)]
+fn poly_eval<const N: usize>(p: &[f64; N], u: f64) -> f64 {
+   let mut ret_val = p[N - 1];
+   for k in (0..N - 1).rev() {
+      ret_val = ret_val * u + p[k];
+   }
+   ret_val
+}
+
+// CurveMath: Bilinear to power form.
+//
+// With the range [c - h, c + h] and u = (t - c) / h, the bilinear parameters are
+// a = sigma.0 * h * (1 + u) and b = sigma.1 * h * (1 - u).  The factor h is common to all
+// terms in numerators and denominator, and so is dropped.  Each coefficient multiplies
+// b^(N-1-k) a^k, with any binomial factors already folded in.
+impl<const N: usize> RatPowerForm<N> {
+   #[must_use]
+   pub fn create_from_bilinear(rows: [&[f64; N]; 3], sigma: (f64, f64), r: [f64; 2]) -> Self {
+      let half_span = 0.5 * (r[1] - r[0]);
+      let inv_half_span = if half_span == 0.0 { 0.0 } else { 1.0 / half_span };
+      let a_factor = [sigma.0, sigma.0];
+      let b_factor = [sigma.1, -sigma.1];
+
+      let mut expanded = [[0.0; N]; 3];
+      let mut basis_a = [0.0; N];
+      basis_a[0] = 1.0;
+      for k in 0..N {
+         let mut basis = basis_a;
+         for _ in 0..(N - 1 - k) {
+            basis = poly_mul_linear(&basis, b_factor);
+         }
+         for (out_row, in_row) in expanded.iter_mut().zip(&rows) {
+            for (v, basis_v) in out_row.iter_mut().zip(&basis) {
+               *v += in_row[k] * basis_v;
+            }
+         }
+         basis_a = poly_mul_linear(&basis_a, a_factor);
+      }
+
+      Self {
+         center: 0.5 * (r[0] + r[1]),
+         inv_half_span,
+         x: expanded[0],
+         y: expanded[1],
+         w: expanded[2],
+      }
+   }
+
+   // Evaluates at each t, filling the corresponding output.
+   #[allow(clippy::missing_panics_doc)]
+   pub fn eval_into(&self, t: &[f64], out: &mut [[f64; 2]]) {
+      assert_eq!(t.len(), out.len(), "Output buffer must match length of t.");
+      for (item, out_v) in t.iter().zip(out.iter_mut()) {
+         let u = (*item - self.center) * self.inv_half_span;
+         let recip_w = 1.0 / poly_eval(&self.w, u);
+         *out_v = [poly_eval(&self.x, u) * recip_w, poly_eval(&self.y, u) * recip_w];
+      }
+   }
+}
+
+impl From<&RatQuadHomogWeighted> for RatPowerForm<3> {
+   fn from(weighted: &RatQuadHomogWeighted) -> Self {
+      let h = &weighted.h.0;
+      Self::create_from_bilinear([&h[0], &h[1], &h[2]], weighted.sigma, weighted.r)
+   }
+}
+
+impl From<&RatCubicHomogWeighted> for RatPowerForm<4> {
+   fn from(weighted: &RatCubicHomogWeighted) -> Self {
+      let h = &weighted.h.0;
+      Self::create_from_bilinear([&h[0], &h[1], &h[2]], weighted.sigma, weighted.r)
+   }
+}
+
+// The denominator of the cubic path is (a + b)^3.
+impl From<&CubicPath> for RatPowerForm<4> {
+   fn from(path: &CubicPath) -> Self {
+      let h = &path.h.0;
+      Self::create_from_bilinear([&h[0], &h[1], &[1.0, 3.0, 3.0, 1.0]], path.sigma, path.r)
+   }
+}
+
+impl CurveEvalInto for RatQuadHomogWeighted {
+   fn eval_into(&self, t: &[f64], out: &mut [[f64; 2]]) {
+      RatPowerForm::<3>::from(self).eval_into(t, out);
+   }
+}
+
+impl CurveEvalInto for RatCubicHomogWeighted {
+   fn eval_into(&self, t: &[f64], out: &mut [[f64; 2]]) {
+      RatPowerForm::<4>::from(self).eval_into(t, out);
+   }
+}
+
+impl CurveEvalInto for CubicPath {
+   fn eval_into(&self, t: &[f64], out: &mut [[f64; 2]]) {
+      RatPowerForm::<4>::from(self).eval_into(t, out);
+   }
+}
+
+// Hyperbolic paths are already in partial-fraction form.
+impl CurveEvalInto for HyperbolicPath {
+   #[allow(clippy::suboptimal_flops)]
+   fn eval_into(&self, t: &[f64], out: &mut [[f64; 2]]) {
+      assert_eq!(t.len(), out.len(), "Output buffer must match length of t.");
+      let lambda = self.lambda;
+      let mu = self.mu;
+      for (item, out_v) in t.iter().zip(out.iter_mut()) {
+         let recip_minus = 1.0 / (lambda - mu * *item);
+         let recip_plus = 1.0 / (lambda + mu * *item);
+         *out_v = [
+            self.offset[0]
+               + self.minus_partial[0] * recip_minus
+               + self.plus_partial[0] * recip_plus,
+            self.offset[1]
+               + self.minus_partial[1] * recip_minus
+               + self.plus_partial[1] * recip_plus,
+         ];
+      }
+   }
+}
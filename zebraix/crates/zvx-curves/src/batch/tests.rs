@@ -0,0 +1,94 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::base::TEval;
+use crate::test_helpers::{time_points, weighted_example_0};
+use crate::{CurveEval, CurveTransform, FourPointRatCubic, ManagedRatCubic};
+use approx::assert_abs_diff_eq;
+use zvx_base::utils::CoordSliceWrapped;
+use zvx_base::CubicHomog;
+
+fn assert_batch_matches<T: CurveEval + CurveEvalInto>(curve: &T, t: &[f64]) {
+   let mut out = vec![[0.0; 2]; t.len()];
+   curve.eval_into(t, &mut out);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&out[..]),
+      &CoordSliceWrapped::from(&curve.eval_with_bilinear(t)[..]),
+      epsilon = 1.0e-12
+   );
+}
+
+#[test]
+fn rat_quad_batch_test() {
+   let mut curve = weighted_example_0();
+   // Includes points outside the range.
+   assert_batch_matches(&curve, &time_points([curve.r[0] - 2.0, curve.r[1] + 3.0], 50));
+   curve.select_range([-2.5, 0.5]);
+   assert_batch_matches(&curve, &time_points(curve.r, 50));
+
+   // Power form can be kept for repeated use.
+   let power_form = RatPowerForm::<3>::from(&curve);
+   let t = time_points(curve.r, 7);
+   let mut out = vec![[0.0; 2]; t.len()];
+   power_form.eval_into(&t, &mut out);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&out[..]),
+      &CoordSliceWrapped::from(&curve.eval_with_bilinear(&t)[..]),
+      epsilon = 1.0e-12
+   );
+}
+
+#[test]
+fn cubic_batch_test() {
+   let curve = CubicPath {
+      r: [-4.5, 13.5],
+      h: CubicHomog([[4.0, 3.0 * 3.5, 3.0 * 4.5, 3.0], [-1.5, 3.0 * -2.0, 3.0 * 1.5, 2.0]]),
+      sigma: (3.6, 1.2),
+   };
+   assert_batch_matches(&curve, &time_points(curve.r, 50));
+
+   let rat_cubic = ManagedRatCubic::create_from_four_points(
+      &FourPointRatCubic {
+         r: [-4.5, 13.5],
+         p: [[4.0, -1.5], [3.5, -2.0], [4.5, 1.5], [3.0, 2.0]],
+         w: [1.0, 2.5, 0.75, 1.25],
+         sigma: (3.6, 1.2),
+      },
+      [0.0; 4],
+   )
+   .rc_curve;
+   assert_batch_matches(&rat_cubic, &time_points(rat_cubic.r, 50));
+}
+
+#[test]
+fn hyperbolic_batch_test() {
+   let path = HyperbolicPath {
+      range: (-1.0, 1.5),
+      lambda: 2.0,
+      mu: 0.75,
+      offset: [0.5, -1.0],
+      minus_partial: [1.5, 0.25],
+      plus_partial: [-0.5, 2.0],
+      ..Default::default()
+   };
+   let t = time_points([path.range.0, path.range.1], 30);
+   let mut out = vec![[0.0; 2]; t.len()];
+   path.eval_into(&t, &mut out);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&out[..]),
+      &CoordSliceWrapped::from(&path.eval_maybe_bilinear(&t)[..]),
+      epsilon = 1.0e-12
+   );
+}
@@ -0,0 +1,381 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests;
+
+use zvx_base::{
+   homog_cross, q_mat_adjugate, q_mat_determinant, q_mat_product, q_mat_transpose,
+   q_mat_vec_product, QMat, RatQuadHomog, RatQuadHomogWeighted,
+};
+
+// Relative tolerance for detecting degenerate (singular) configurations.
+const DEGENERACY_TOLERANCE: f64 = 1.0e-12;
+// Relative magnitude below which a polynomial value at a turning point is taken as a double
+// root, that is a tangential intersection.
+const TANGENCY_TOLERANCE: f64 = 1.0e-10;
+const NUM_BISECTION_STEPS: i32 = 64;
+
+// Implicit form of a conic, that is A x^2 + B x y + C y^2 + D x + E y + F = 0.
+//
+// Coefficients are held in the order [A, B, C, D, E, F], and are normalized to unit norm.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct ImplicitConic {
+   pub coeffs: [f64; 6],
+}
+
+#[inline]
+#[must_use]
+fn dot_3(u: &[f64; 3], v: &[f64; 3]) -> f64 {
+   u[0].mul_add(v[0], u[1].mul_add(v[1], u[2] * v[2]))
+}
+
+#[inline]
+#[must_use]
+const fn homog_from_point(p: [f64; 2]) -> [f64; 3] {
+   [p[0], p[1], 1.0]
+}
+
+// Determinant of 5x5 matrix, by Gaussian elimination with partial pivoting.
+#[must_use]
+fn determinant_5(mut m: [[f64; 5]; 5]) -> f64 {
+   let mut det = 1.0;
+   for col in 0..5 {
+      let mut pivot_row = col;
+      for row in (col + 1)..5 {
+         if m[row][col].abs() > m[pivot_row][col].abs() {
+            pivot_row = row;
+         }
+      }
+      if m[pivot_row][col] == 0.0 {
+         return 0.0;
+      }
+      if pivot_row != col {
+         m.swap(pivot_row, col);
+         det = -det;
+      }
+      let pivot_values = m[col];
+      det *= pivot_values[col];
+      for row in &mut m[(col + 1)..] {
+         let f = row[col] / pivot_values[col];
+         for (v, pivot_v) in row.iter_mut().zip(&pivot_values).skip(col) {
+            *v -= f * pivot_v;
+         }
+      }
+   }
+   det
+}
+
+// Evaluate polynomial in Bernstein form over [0, 1] by de Casteljau's algorithm.
+#[must_use]
+fn bernstein_eval(coeffs: &[f64], s: f64) -> f64 {
+   let mut values = coeffs.to_vec();
+   for degree in (1..values.len()).rev() {
+      for k in 0..degree {
+         values[k] = (1.0 - s).mul_add(values[k], s * values[k + 1]);
+      }
+   }
+   values.first().copied().unwrap_or(0.0)
+}
+
+// Roots in [0, 1] of polynomial in Bernstein form, in increasing order.
+//
+// The roots of the derivative split [0, 1] into intervals over which the polynomial is
+// monotonic.  Each interval holds at most one root, bracketed by a sign change and refined by
+// bisection.  Values within tolerance of zero at interval ends are taken as roots, which finds
+// double roots at turning points.
+fn bernstein_roots(coeffs: &[f64], tolerance: f64) -> Vec<f64> {
+   if coeffs.len() < 2 {
+      return vec![];
+   }
+   let derivative: Vec<f64> = coeffs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+   let derivative_tolerance =
+      TANGENCY_TOLERANCE * derivative.iter().fold(0.0_f64, |acc, v| acc.max(v.abs()));
+
+   let mut knots = vec![0.0];
+   knots.extend(bernstein_roots(&derivative, derivative_tolerance));
+   knots.push(1.0);
+   let values: Vec<f64> = knots
+      .iter()
+      .map(|s| {
+         let value = bernstein_eval(coeffs, *s);
+         if value.abs() <= tolerance {
+            0.0
+         } else {
+            value
+         }
+      })
+      .collect();
+
+   let mut roots = Vec::<f64>::new();
+   for (i, s) in knots.iter().enumerate() {
+      if i > 0 && values[i - 1] * values[i] < 0.0 {
+         let (mut s_low, mut s_high, mut v_low) = (knots[i - 1], *s, values[i - 1]);
+         for _ in 0..NUM_BISECTION_STEPS {
+            let s_mid = 0.5 * (s_low + s_high);
+            let v_mid = bernstein_eval(coeffs, s_mid);
+            if v_mid * v_low > 0.0 {
+               s_low = s_mid;
+               v_low = v_mid;
+            } else {
+               s_high = s_mid;
+            }
+         }
+         roots.push(0.5 * (s_low + s_high));
+      }
+      if values[i] == 0.0 && roots.last() != Some(s) {
+         roots.push(*s);
+      }
+   }
+   roots
+}
+
+// Solve for [alpha, beta, gamma] such that v = alpha * p0 + beta * p1 + gamma * p2.
+fn basis_coords(
+   p0: &[f64; 3],
+   p1: &[f64; 3],
+   p2: &[f64; 3],
+   v: &[f64; 3],
+) -> Result<[f64; 3], &'static str> {
+   let columns: QMat = [[p0[0], p1[0], p2[0]], [p0[1], p1[1], p2[1]], [p0[2], p1[2], p2[2]]];
+   let det = q_mat_determinant(&columns);
+   let scale = dot_3(p0, p0).sqrt() * dot_3(p1, p1).sqrt() * dot_3(p2, p2).sqrt();
+   if det.abs() <= DEGENERACY_TOLERANCE * scale {
+      return Err("Control points are collinear.");
+   }
+   let c = q_mat_vec_product(&q_mat_adjugate(&columns), v);
+   Ok([c[0] / det, c[1] / det, c[2] / det])
+}
+
+// CurveMath: RQC from homogeneous control points.
+//
+// Creates the weighted RQC b^2 p0 + 2 w a b p1 + a^2 p2, with the end points p0 and p2 given
+// with unit weight, and the homogeneous middle control point p1 possibly at infinity.  The
+// weight w is chosen so that the curve passes through the point `via` for positive a and b.
+fn rat_quad_from_homog_control(
+   p0: &[f64; 3],
+   p1: &[f64; 3],
+   p2: &[f64; 3],
+   via: [f64; 2],
+   r: [f64; 2],
+) -> Result<RatQuadHomogWeighted, &'static str> {
+   let [alpha, beta, gamma] = basis_coords(p0, p1, p2, &homog_from_point(via))?;
+   if alpha * gamma <= 0.0 {
+      return Err("Intermediate point does not lie between end points.");
+   }
+   let w = beta / (2.0 * alpha.signum() * (alpha * gamma).sqrt());
+
+   Ok(RatQuadHomogWeighted {
+      r,
+      h: RatQuadHomog([
+         [p0[0], 2.0 * w * p1[0], p2[0]],
+         [p0[1], 2.0 * w * p1[1], p2[1]],
+         [p0[2], 2.0 * w * p1[2], p2[2]],
+      ]),
+      sigma: (1.0, 1.0),
+   })
+}
+
+// CurveMath: RQC from three points and end tangents.
+//
+// The middle control point lies at the intersection of the tangents at the end points.  The
+// tangents are directions, and their sign does not matter.
+#[allow(clippy::missing_errors_doc)]
+pub fn rat_quad_from_tangents(
+   p: &[[f64; 2]; 3],
+   tangents: &[[f64; 2]; 2],
+   r: [f64; 2],
+) -> Result<RatQuadHomogWeighted, &'static str> {
+   let p0 = homog_from_point(p[0]);
+   let p2 = homog_from_point(p[2]);
+   let line_0 = homog_cross(&p0, &[tangents[0][0], tangents[0][1], 0.0]);
+   let line_2 = homog_cross(&p2, &[tangents[1][0], tangents[1][1], 0.0]);
+   let p1 = homog_cross(&line_0, &line_2);
+   rat_quad_from_homog_control(&p0, &p1, &p2, p[1], r)
+}
+
+impl ImplicitConic {
+   #[must_use]
+   pub fn create_from_conic_matrix(m: &QMat) -> Self {
+      let mut retval = Self {
+         coeffs: [
+            m[0][0],
+            m[0][1] + m[1][0],
+            m[1][1],
+            m[0][2] + m[2][0],
+            m[1][2] + m[2][1],
+            m[2][2],
+         ],
+      };
+      retval.normalize();
+      retval
+   }
+
+   // Symmetric matrix M such that the conic is [x, y, 1] M [x, y, 1]^T = 0.
+   #[must_use]
+   #[allow(clippy::many_single_char_names)]
+   pub fn conic_matrix(&self) -> QMat {
+      let [a, b, c, d, e, f] = self.coeffs;
+      [[a, 0.5 * b, 0.5 * d], [0.5 * b, c, 0.5 * e], [0.5 * d, 0.5 * e, f]]
+   }
+
+   pub fn normalize(&mut self) {
+      let norm = self.coeffs.iter().map(|v| v * v).sum::<f64>().sqrt();
+      if norm > 0.0 {
+         for v in &mut self.coeffs {
+            *v /= norm;
+         }
+      }
+   }
+
+   // CurveMath: Implicit form of RQC.
+   //
+   // The powers u = [b^2, a b, a^2] satisfy u_1^2 - u_0 u_2 = 0, and the homogeneous curve
+   // points are X = H u.  Therefore the curve satisfies X^T adj(H)^T K adj(H) X = 0.  The form
+   // is independent of the range and sigma, and covers the whole conic, not just the arc.
+   #[allow(clippy::missing_errors_doc)]
+   pub fn create_from_rat_quad(curve: &RatQuadHomogWeighted) -> Result<Self, &'static str> {
+      let h = &curve.h.0;
+      let scale = dot_3(&h[0], &h[0]).max(dot_3(&h[1], &h[1])).max(dot_3(&h[2], &h[2]));
+      if q_mat_determinant(h).abs() <= DEGENERACY_TOLERANCE * scale * scale.sqrt() {
+         return Err("Curve is degenerate conic.");
+      }
+      let k: QMat = [[0.0, 0.0, -0.5], [0.0, 1.0, 0.0], [-0.5, 0.0, 0.0]];
+      let adj = q_mat_adjugate(h);
+      Ok(Self::create_from_conic_matrix(&q_mat_product(
+         &q_mat_transpose(&adj),
+         &q_mat_product(&k, &adj),
+      )))
+   }
+
+   // CurveMath: Conic through five points.
+   //
+   // Each point gives a linear constraint on the coefficients.  The coefficients are the
+   // null space of the 5x6 constraint matrix, which is found as the signed 5x5 minors.
+   #[allow(clippy::missing_errors_doc)]
+   pub fn create_from_five_points(p: &[[f64; 2]; 5]) -> Result<Self, &'static str> {
+      let rows: [[f64; 6]; 5] = std::array::from_fn(|i| {
+         [p[i][0] * p[i][0], p[i][0] * p[i][1], p[i][1] * p[i][1], p[i][0], p[i][1], 1.0]
+      });
+      let mut retval = Self::default();
+      for j in 0..6 {
+         let minor: [[f64; 5]; 5] = std::array::from_fn(|i| {
+            std::array::from_fn(|k| rows[i][if k < j { k } else { k + 1 }])
+         });
+         let det = determinant_5(minor);
+         retval.coeffs[j] = if j % 2 == 0 { det } else { -det };
+      }
+      let norm = retval.coeffs.iter().map(|v| v * v).sum::<f64>().sqrt();
+      let scale =
+         rows.iter().map(|row| row.iter().map(|v| v * v).sum::<f64>().sqrt()).product::<f64>();
+      if norm <= DEGENERACY_TOLERANCE * scale {
+         return Err("Points do not determine a unique conic.");
+      }
+      retval.normalize();
+      Ok(retval)
+   }
+
+   // Algebraic value at point.  Zero on the conic.
+   #[must_use]
+   pub fn value(&self, p: [f64; 2]) -> f64 {
+      let x = homog_from_point(p);
+      dot_3(&x, &q_mat_vec_product(&self.conic_matrix(), &x))
+   }
+
+   // Test that the point lies on the conic, within tolerance of the value normalized by the
+   // homogeneous magnitude of the point.
+   #[must_use]
+   pub fn contains(&self, p: [f64; 2], tolerance: f64) -> bool {
+      let x = homog_from_point(p);
+      self.value(p).abs() <= tolerance * dot_3(&x, &x)
+   }
+
+   // Arc of the conic from start to end, passing through `via`.  All three points should lie on
+   // the conic.  The middle control point is the pole of the chord, that is the intersection
+   // of the tangents at the end points.
+   #[allow(clippy::missing_errors_doc)]
+   pub fn arc_through(
+      &self,
+      start: [f64; 2],
+      via: [f64; 2],
+      end: [f64; 2],
+      r: [f64; 2],
+   ) -> Result<RatQuadHomogWeighted, &'static str> {
+      let m = self.conic_matrix();
+      let p0 = homog_from_point(start);
+      let p2 = homog_from_point(end);
+      let p1 = homog_cross(&q_mat_vec_product(&m, &p0), &q_mat_vec_product(&m, &p2));
+      rat_quad_from_homog_control(&p0, &p1, &p2, via, r)
+   }
+
+   // Intersections with the line p + s d, returned as the values of s in increasing order.
+   #[must_use]
+   #[allow(clippy::suboptimal_flops)]
+   pub fn intersect_line(&self, p: [f64; 2], d: [f64; 2]) -> Vec<f64> {
+      let m = self.conic_matrix();
+      let p_h = homog_from_point(p);
+      let d_h = [d[0], d[1], 0.0];
+      let m_d = q_mat_vec_product(&m, &d_h);
+      let c_2 = dot_3(&d_h, &m_d);
+      let c_1 = 2.0 * dot_3(&p_h, &m_d);
+      let c_0 = dot_3(&p_h, &q_mat_vec_product(&m, &p_h));
+
+      if c_2.abs() <= DEGENERACY_TOLERANCE * (c_1.abs() + c_0.abs()) {
+         if c_1 == 0.0 {
+            return vec![];
+         }
+         return vec![-c_0 / c_1];
+      }
+      let discriminant = c_1 * c_1 - 4.0 * c_2 * c_0;
+      if discriminant < 0.0 {
+         return vec![];
+      }
+      // Numerically stable form of quadratic roots.
+      let q = -0.5 * (c_1 + c_1.signum() * discriminant.sqrt());
+      let mut roots = if q == 0.0 { vec![0.0, 0.0] } else { vec![q / c_2, c_0 / q] };
+      roots.sort_by(f64::total_cmp);
+      roots
+   }
+
+   // Intersections with RQC within its range, returned as values of t in increasing order.
+   //
+   // The curve is X = H u, where u = [l^2, l m, m^2] for the weighted parameters l = b (w - t)
+   // and m = a (t - v).  Substituting into the implicit form gives u^T (H^T M H) u, which is a
+   // homogeneous quartic in (l, m).  With l = 1 - s and m = s this is a quartic in Bernstein
+   // form over s in [0, 1], which covers exactly the range.  Tangential intersections are
+   // double roots, and are returned once.  If the curve lies on the conic, no intersections
+   // are returned.
+   #[must_use]
+   pub fn intersect_rat_quad(&self, curve: &RatQuadHomogWeighted) -> Vec<f64> {
+      let h = &curve.h.0;
+      let g = q_mat_product(&q_mat_transpose(h), &q_mat_product(&self.conic_matrix(), h));
+      let bernstein =
+         [g[0][0], 0.5 * g[0][1], g[0][2].mul_add(2.0, g[1][1]) / 6.0, 0.5 * g[1][2], g[2][2]];
+      let scale = bernstein.iter().fold(0.0_f64, |acc, v| acc.max(v.abs()));
+      if scale == 0.0 {
+         return vec![];
+      }
+
+      let [t_start, t_end] = curve.r;
+      let (sigma_a, sigma_b) = curve.sigma;
+      bernstein_roots(&bernstein, TANGENCY_TOLERANCE * scale)
+         .into_iter()
+         .map(|s| {
+            let weight_start = sigma_a * (1.0 - s);
+            let weight_end = sigma_b * s;
+            t_start.mul_add(weight_start, t_end * weight_end) / (weight_start + weight_end)
+         })
+         .collect()
+   }
+}
@@ -0,0 +1,243 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::test_helpers::{time_points, weighted_example_0};
+use crate::{CurveEval, FivePointRatQuad, ManagedRatQuad, SpecifiedRatQuad, ThreePointTangentRepr};
+use approx::assert_abs_diff_eq;
+use zvx_base::utils::{CoordSliceWrapped, F64ArrayWrapped};
+
+// Compare coefficients, allowing for the sign ambiguity of the implicit form.
+fn assert_same_conic(a: &ImplicitConic, b: &ImplicitConic) {
+   let sign = if a.coeffs.iter().zip(&b.coeffs).map(|(u, v)| u * v).sum::<f64>() < 0.0 {
+      -1.0
+   } else {
+      1.0
+   };
+   let b_signed: [f64; 6] = std::array::from_fn(|k| sign * b.coeffs[k]);
+   assert_abs_diff_eq!(
+      &F64ArrayWrapped::<6>::from(&a.coeffs),
+      &F64ArrayWrapped::<6>::from(&b_signed),
+      epsilon = 1.0e-9
+   );
+}
+
+#[test]
+fn implicit_from_rat_quad_test() {
+   let curve = weighted_example_0();
+   let conic = ImplicitConic::create_from_rat_quad(&curve).unwrap();
+
+   // Points beyond the range are also on the conic.
+   let points = curve.eval_with_bilinear(&time_points([curve.r[0] - 3.0, curve.r[1] + 5.0], 20));
+   for p in &points {
+      assert!(conic.contains(*p, 1.0e-12), "Point {p:?} off conic.");
+   }
+   assert!(!conic.contains([0.0, 0.0], 1.0e-6));
+
+   // Independent of bilinear transformation.
+   let mut transformed = curve.clone();
+   transformed.sigma = (0.5, 3.0);
+   assert_same_conic(&conic, &ImplicitConic::create_from_rat_quad(&transformed).unwrap());
+
+   // Degenerate curves (here with collinear control points) have no proper implicit form.
+   let degenerate = RatQuadHomogWeighted {
+      r: [0.0, 1.0],
+      h: RatQuadHomog([[0.0, 2.0, 2.0], [0.0, 2.0, 2.0], [1.0, 2.0, 1.0]]),
+      sigma: (1.0, 1.0),
+   };
+   assert!(ImplicitConic::create_from_rat_quad(&degenerate).is_err());
+}
+
+#[test]
+fn implicit_round_trip_test() {
+   let curve = weighted_example_0();
+   let conic = ImplicitConic::create_from_rat_quad(&curve).unwrap();
+
+   let t = [curve.r[0], 0.5 * (curve.r[0] + curve.r[1]), curve.r[1]];
+   let p = curve.eval_with_bilinear(&t);
+   let arc = conic.arc_through(p[0], p[1], p[2], curve.r).unwrap();
+
+   // Same end points, and same conic.
+   let arc_ends = arc.eval_with_bilinear(&arc.r);
+   let reference_ends = [p[0], p[2]];
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&arc_ends[..]),
+      &CoordSliceWrapped::from(&reference_ends[..]),
+      epsilon = 1.0e-9
+   );
+   assert_same_conic(&conic, &ImplicitConic::create_from_rat_quad(&arc).unwrap());
+
+   // The arc is the same, not the complementary arc, so passes through the intermediate point.
+   let arc_points = arc.eval_with_bilinear(&time_points(arc.r, 2000));
+   assert!(arc_points.iter().any(|q| (q[0] - p[1][0]).hypot(q[1] - p[1][1]) < 0.01));
+}
+
+#[test]
+fn five_point_test() {
+   // Ellipse x^2 / 4 + y^2 = 1.
+   let angles = [-0.5, -0.25, 0.0, 0.25, 0.5].map(|f: f64| f * std::f64::consts::PI);
+   let p = angles.map(|theta| [2.0 * theta.cos(), theta.sin()]);
+   let conic = ImplicitConic::create_from_five_points(&p).unwrap();
+   let mut reference = ImplicitConic { coeffs: [0.25, 0.0, 1.0, 0.0, 0.0, -1.0] };
+   reference.normalize();
+   assert_same_conic(&reference, &conic);
+
+   let managed = ManagedRatQuad::create_from_five_points(
+      &FivePointRatQuad { r: [-1.0, 1.0], p, ..Default::default() },
+      [0.0; 4],
+   )
+   .unwrap();
+   assert_eq!(managed.specified, SpecifiedRatQuad::FivePoint);
+   // By symmetry, the five points are matched at evenly spaced parameters.
+   let points = managed.rq_curve.eval_with_bilinear(&[-1.0, 0.0, 1.0]);
+   let reference_points = [p[0], p[2], p[4]];
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&reference_points[..]),
+      epsilon = 1.0e-9
+   );
+   for q in managed.rq_curve.eval_with_bilinear(&time_points([-1.0, 1.0], 20)) {
+      assert!(conic.contains(q, 1.0e-12));
+   }
+
+   // Four collinear points do not determine a conic.
+   let degenerate = [[0.0, 0.0], [1.0, 1.0], [2.0, 2.0], [3.0, 3.0], [1.0, 0.0]];
+   assert!(ImplicitConic::create_from_five_points(&degenerate).is_err());
+}
+
+#[test]
+fn tangents_test() {
+   // Quarter circle.
+   let half_sqrt_2 = std::f64::consts::FRAC_1_SQRT_2;
+   let managed = ManagedRatQuad::create_from_tangents(
+      &ThreePointTangentRepr {
+         r: [0.0, 1.0],
+         p: [[1.0, 0.0], [half_sqrt_2, half_sqrt_2], [0.0, 1.0]],
+         tangents: [[0.0, 1.0], [-1.0, 0.0]],
+         ..Default::default()
+      },
+      [0.0; 4],
+   )
+   .unwrap();
+   assert_eq!(managed.specified, SpecifiedRatQuad::ThreePointTangent);
+   for q in managed.rq_curve.eval_with_bilinear(&time_points([0.0, 1.0], 20)) {
+      assert_abs_diff_eq!(q[0].hypot(q[1]), 1.0, epsilon = 1.0e-12);
+   }
+   // Middle weight is cos(45), with the binomial factor folded in.
+   let h = &managed.rq_curve.h.0;
+   assert_abs_diff_eq!(h[2][1] / h[2][0], 2.0 * half_sqrt_2, epsilon = 1.0e-12);
+
+   // Semicircle, with parallel tangents, so that the middle control point is at infinity.
+   let semicircle = rat_quad_from_tangents(
+      &[[1.0, -1.0], [0.0, 0.0], [1.0, 1.0]],
+      &[[-1.0, 0.0], [1.0, 0.0]],
+      [0.0, 1.0],
+   )
+   .unwrap();
+   for q in semicircle.eval_with_bilinear(&time_points([0.0, 1.0], 20)) {
+      assert_abs_diff_eq!((q[0] - 1.0).hypot(q[1]), 1.0, epsilon = 1.0e-12);
+      assert!(q[0] <= 1.0 + 1.0e-12);
+   }
+}
+
+#[test]
+fn intersection_test() {
+   let mut unit_circle = ImplicitConic { coeffs: [1.0, 0.0, 1.0, 0.0, 0.0, -1.0] };
+   unit_circle.normalize();
+
+   let s = unit_circle.intersect_line([0.0, 0.5], [1.0, 0.0]);
+   assert_abs_diff_eq!(
+      &F64ArrayWrapped::<2>::from(&[s[0], s[1]]),
+      &F64ArrayWrapped::<2>::from(&[-(0.75_f64.sqrt()), 0.75_f64.sqrt()]),
+      epsilon = 1.0e-12
+   );
+   assert!(unit_circle.intersect_line([0.0, 1.5], [1.0, 0.0]).is_empty());
+
+   // Semicircle centred at (1, 0) crosses the unit circle at (0.5, +/- sqrt(0.75)).
+   let semicircle = rat_quad_from_tangents(
+      &[[1.0, -1.0], [0.0, 0.0], [1.0, 1.0]],
+      &[[-1.0, 0.0], [1.0, 0.0]],
+      [-2.0, 3.0],
+   )
+   .unwrap();
+   let t = unit_circle.intersect_rat_quad(&semicircle);
+   assert_eq!(t.len(), 2);
+   let points = semicircle.eval_with_bilinear(&t);
+   let reference_points = [[0.5, -(0.75_f64.sqrt())], [0.5, 0.75_f64.sqrt()]];
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&reference_points[..]),
+      epsilon = 1.0e-9
+   );
+}
+
+// Straight RQC along y = height, from x = -1 to x = 1.5, with x = -1 + 2.5 t.
+fn horizontal_line(height: f64) -> RatQuadHomogWeighted {
+   RatQuadHomogWeighted {
+      r: [0.0, 1.0],
+      h: RatQuadHomog([[-1.0, 0.5, 1.5], [height, 2.0 * height, height], [1.0, 2.0, 1.0]]),
+      sigma: (1.0, 1.0),
+   }
+}
+
+#[test]
+fn tangential_intersection_test() {
+   let mut unit_circle = ImplicitConic { coeffs: [1.0, 0.0, 1.0, 0.0, 0.0, -1.0] };
+   unit_circle.normalize();
+
+   // Tangent line touches at (0, 1), which is a double root.
+   let t = unit_circle.intersect_rat_quad(&horizontal_line(1.0));
+   assert_eq!(t.len(), 1);
+   assert_abs_diff_eq!(t[0], 0.4, epsilon = 1.0e-9);
+
+   // Circle centred at (0, 2) touches the unit circle externally at (0, 1).
+   let touching = rat_quad_from_tangents(
+      &[[-1.0, 2.0], [0.0, 1.0], [1.0, 2.0]],
+      &[[0.0, -1.0], [0.0, 1.0]],
+      [0.0, 1.3],
+   )
+   .unwrap();
+   let t = unit_circle.intersect_rat_quad(&touching);
+   assert_eq!(t.len(), 1);
+   let points = touching.eval_with_bilinear(&t);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&[[0.0, 1.0]][..]),
+      epsilon = 1.0e-6
+   );
+
+   // Missing the circle entirely.
+   assert!(unit_circle.intersect_rat_quad(&horizontal_line(1.001)).is_empty());
+}
+
+#[test]
+fn close_intersections_test() {
+   let mut unit_circle = ImplicitConic { coeffs: [1.0, 0.0, 1.0, 0.0, 0.0, -1.0] };
+   unit_circle.normalize();
+
+   // Line just inside the top of the circle crosses twice, very close together.
+   let height: f64 = 1.0 - 1.0e-6;
+   let half_chord = height.mul_add(-height, 1.0).sqrt();
+   let t = unit_circle.intersect_rat_quad(&horizontal_line(height));
+   assert_eq!(t.len(), 2);
+   assert_abs_diff_eq!(
+      &F64ArrayWrapped::<2>::from(&[t[0], t[1]]),
+      &F64ArrayWrapped::<2>::from(&[(1.0 - half_chord) / 2.5, (1.0 + half_chord) / 2.5]),
+      epsilon = 1.0e-9
+   );
+   let points = horizontal_line(height).eval_with_bilinear(&t);
+   for p in &points {
+      assert!(unit_circle.contains(*p, 1.0e-9), "Point {p:?} off conic.");
+   }
+}
@@ -51,6 +51,61 @@ fn cubic_eval_part_quad(b: f64, a: f64, coeffs: &[f64; 3], multiplier: f64) -> f
    multiplier * (b * b * coeffs[0] + b * a * coeffs[1] + a * a * coeffs[2])
 }
 
+// CurveMath: Range selection (cubic).
+//
+// Transform that sub- or super-selects the range of a cubic in weighted form, along with the
+// sigma ratio of the result.  This is shared by polynomial and rational cubics, since the
+// denominator row transforms in the same way as the coordinate rows.
+//
+// Does not check that range is valid (no asymptote in bilinear within range).
+#[allow(clippy::similar_names)]
+#[allow(clippy::suboptimal_flops)]
+#[must_use]
+pub(crate) fn select_range_c_mat(
+   sigma: (f64, f64),
+   r: [f64; 2],
+   new_range: [f64; 2],
+) -> (CMat, (f64, f64)) {
+   let a_k = sigma.0 * (new_range[0] - r[0]);
+   let b_k = sigma.1 * (r[1] - new_range[0]);
+   let a_l = sigma.0 * (new_range[1] - r[0]);
+   let b_l = sigma.1 * (r[1] - new_range[1]);
+
+   let alpha = b_k / (a_k + b_k);
+   let beta = 1.0 - alpha;
+   let gamma = b_l / (a_l + b_l);
+   let delta = 1.0 - gamma;
+
+   let selection_transform: CMat = [
+      [
+         alpha * alpha * alpha,
+         3.0 * alpha * alpha * gamma,
+         3.0 * alpha * gamma * gamma,
+         gamma * gamma * gamma,
+      ],
+      [
+         alpha * alpha * beta,
+         2.0 * alpha * beta * gamma + alpha * alpha * delta,
+         2.0 * alpha * gamma * delta + beta * gamma * gamma,
+         gamma * gamma * delta,
+      ],
+      [
+         alpha * beta * beta,
+         2.0 * alpha * beta * delta + beta * beta * gamma,
+         2.0 * beta * gamma * delta + alpha * delta * delta,
+         gamma * delta * delta,
+      ],
+      [
+         beta * beta * beta,
+         3.0 * beta * beta * delta,
+         3.0 * beta * delta * delta,
+         delta * delta * delta,
+      ],
+   ];
+
+   (selection_transform, (a_l + b_l, a_k + b_k))
+}
+
 impl CurveEval for CubicPath {
    #[allow(clippy::many_single_char_names)]
    fn eval_with_bilinear(&self, t: &[f64]) -> Vec<[f64; 2]> {
@@ -129,51 +184,13 @@ impl CurveTransform for CubicPath {
       self.r = new_range;
    }
 
-   #[allow(clippy::similar_names)]
-   #[allow(clippy::suboptimal_flops)]
    fn select_range(&mut self, new_range: [f64; 2]) {
-      // Does not check that range is valid (no asymptote in bilinear within range).
-      let a_k = self.sigma.0 * (new_range[0] - self.r[0]);
-      let b_k = self.sigma.1 * (self.r[1] - new_range[0]);
-      let a_l = self.sigma.0 * (new_range[1] - self.r[0]);
-      let b_l = self.sigma.1 * (self.r[1] - new_range[1]);
-
-      let alpha = b_k / (a_k + b_k);
-      let beta = 1.0 - alpha;
-      let gamma = b_l / (a_l + b_l);
-      let delta = 1.0 - gamma;
-
-      let selection_transform: CMat = [
-         [
-            alpha * alpha * alpha,
-            3.0 * alpha * alpha * gamma,
-            3.0 * alpha * gamma * gamma,
-            gamma * gamma * gamma,
-         ],
-         [
-            alpha * alpha * beta,
-            2.0 * alpha * beta * gamma + alpha * alpha * delta,
-            2.0 * alpha * gamma * delta + beta * gamma * gamma,
-            gamma * gamma * delta,
-         ],
-         [
-            alpha * beta * beta,
-            2.0 * alpha * beta * delta + beta * beta * gamma,
-            2.0 * beta * gamma * delta + alpha * delta * delta,
-            gamma * delta * delta,
-         ],
-         [
-            beta * beta * beta,
-            3.0 * beta * beta * delta,
-            3.0 * beta * delta * delta,
-            delta * delta * delta,
-         ],
-      ];
+      let (selection_transform, new_sigma) = select_range_c_mat(self.sigma, self.r, new_range);
 
       let selected_cubic: CubicHomog = self.h.apply_c_mat(&selection_transform);
 
       // It would be good to power-2 normalize.
-      self.sigma = (a_l + b_l, a_k + b_k);
+      self.sigma = new_sigma;
       self.h = selected_cubic;
       self.r = new_range;
    }
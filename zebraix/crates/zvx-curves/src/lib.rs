@@ -13,16 +13,38 @@
 // limitations under the License.
 
 pub mod base;
+pub mod batch;
+pub mod conic;
 pub mod cubic;
+pub mod rat_cubic;
 pub mod rat_quad;
+pub mod segment;
 pub mod subclasses;
+pub mod svg_arc;
+// Shared by unit tests and benchmarks, and not part of the supported interface.
+#[doc(hidden)]
+pub mod test_helpers;
 
-pub use crate::base::{bilinear_transform_timepoints, CurveEval, CurveTransform, ZebraixAngle};
+pub use crate::base::{
+   bilinear_transform_timepoints, CurveEval, CurveEvalInto, CurveTransform,
+   CurveTransformWithPrecision, PrecisionChoice, ZebraixAngle,
+};
+pub use crate::batch::RatPowerForm;
+pub use crate::conic::ImplicitConic;
 pub use crate::cubic::managed::ManagedCubic;
+pub use crate::rat_cubic::managed::ManagedRatCubic;
+pub use crate::rat_cubic::FourPointRatCubic;
 pub use crate::rat_quad::managed::ManagedRatQuad;
-pub use crate::rat_quad::{FourPointRatQuad, SpecifiedRatQuad, ThreePointAngleRepr};
+pub use crate::rat_quad::{
+   FivePointRatQuad, FourPointRatQuad, SpecifiedRatQuad, ThreePointAngleRepr, ThreePointTangentRepr,
+};
+pub use crate::segment::{
+   bisect_directions, segment_ends, segment_interior_placements, segment_samples, trim_segment,
+   ArcLengthSampling, SegmentPlacement,
+};
 pub use crate::subclasses::convert::RegularizedRatQuadPath;
 pub use crate::subclasses::threes::RatQuadOoeSubclassed;
+pub use crate::svg_arc::SvgArcRepr;
 
 // #[cfg(test)]
 // #[macro_use]
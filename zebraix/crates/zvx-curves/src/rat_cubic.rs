@@ -0,0 +1,209 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod managed;
+#[cfg(test)]
+mod tests;
+
+use crate::cubic::select_range_c_mat;
+use crate::{CurveEval, CurveTransform};
+use serde::Serialize;
+use serde_default::DefaultFromSerde;
+use zvx_base::{
+   default_unit_ratio, is_default_unit_ratio, CubicHomog, CubicPath, CurveCubicMatrix,
+   RatCubicHomog, RatCubicHomogWeighted,
+};
+
+#[inline]
+#[allow(clippy::many_single_char_names)]
+#[allow(clippy::suboptimal_flops)]
+#[must_use]
+fn eval_part_cubic(b: f64, a: f64, coeffs: &[f64; 4]) -> f64 {
+   b * b * b * coeffs[0] + b * b * a * coeffs[1] + b * a * a * coeffs[2] + a * a * a * coeffs[3]
+}
+
+// Partial derivative of cubic part with respect to a.
+#[inline]
+#[allow(clippy::many_single_char_names)]
+#[allow(clippy::suboptimal_flops)]
+#[must_use]
+fn eval_part_cubic_da(b: f64, a: f64, coeffs: &[f64; 4]) -> f64 {
+   b * b * coeffs[1] + 2.0 * b * a * coeffs[2] + 3.0 * a * a * coeffs[3]
+}
+
+// Partial derivative of cubic part with respect to b.
+#[inline]
+#[allow(clippy::many_single_char_names)]
+#[allow(clippy::suboptimal_flops)]
+#[must_use]
+fn eval_part_cubic_db(b: f64, a: f64, coeffs: &[f64; 4]) -> f64 {
+   3.0 * b * b * coeffs[0] + 2.0 * b * a * coeffs[1] + a * a * coeffs[2]
+}
+
+// Four control points with weights.  The curve passes through the end points, and the control
+// points pull towards themselves in proportion to their weights.  Equal weights give the
+// polynomial cubic with the same control points.
+#[derive(Debug, Serialize, DefaultFromSerde, PartialEq, Clone)]
+pub struct FourPointRatCubic {
+   pub r: [f64; 2], // Range.
+   pub p: [[f64; 2]; 4],
+   pub w: [f64; 4],
+   #[serde(skip_serializing_if = "is_default_unit_ratio", default = "default_unit_ratio")]
+   pub sigma: (f64, f64),
+}
+
+// Internal bilinear transform.  Really only for testing.
+#[must_use]
+#[allow(clippy::many_single_char_names)]
+pub fn rc_weighted_collapse_bilinear(weighted: &RatCubicHomogWeighted) -> RatCubicHomogWeighted {
+   let (p, q) = weighted.sigma;
+   let f = [q * q * q, p * q * q, p * p * q, p * p * p];
+
+   let wh = &weighted.h.0;
+   let scale_row =
+      |row: &[f64; 4]| -> [f64; 4] { [f[0] * row[0], f[1] * row[1], f[2] * row[2], f[3] * row[3]] };
+   let h = RatCubicHomog([scale_row(&wh[0]), scale_row(&wh[1]), scale_row(&wh[2])]);
+
+   RatCubicHomogWeighted { r: weighted.r, h, sigma: (1.0, 1.0) }
+}
+
+// CurveMath: Rational cubic as polynomial.
+//
+// A rational cubic is a polynomial cubic, with a bilinear transformation, when the denominator
+// is a perfect cube, that is proportional to (lambda * b + mu * a)^3.  With binomial factors
+// folded in, the denominator coefficients are then [lambda^3, 3 lambda^2 mu, 3 lambda mu^2,
+// mu^3].  The scaling lambda and mu are absorbed into the sigma ratio.
+//
+// Returns None if the denominator is not, within tolerance relative to its magnitude, such a
+// cube.  Renderers can then draw exactly with cubic Beziers, and otherwise approximate.
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+pub fn rc_weighted_as_polynomial(
+   weighted: &RatCubicHomogWeighted,
+   tolerance: f64,
+) -> Option<CubicPath> {
+   let d = &weighted.h.0[2];
+   let lambda = d[0].cbrt();
+   let mu = d[3].cbrt();
+   if (lambda == 0.0) || (mu == 0.0) {
+      return None;
+   }
+   let magnitude = d[0].abs().max(d[1].abs()).max(d[2].abs()).max(d[3].abs());
+   if ((d[1] - 3.0 * lambda * lambda * mu).abs() > tolerance * magnitude)
+      || ((d[2] - 3.0 * lambda * mu * mu).abs() > tolerance * magnitude)
+   {
+      return None;
+   }
+
+   let f = [
+      1.0 / (lambda * lambda * lambda),
+      1.0 / (lambda * lambda * mu),
+      1.0 / (lambda * mu * mu),
+      1.0 / (mu * mu * mu),
+   ];
+   let scale_row =
+      |row: &[f64; 4]| -> [f64; 4] { [f[0] * row[0], f[1] * row[1], f[2] * row[2], f[3] * row[3]] };
+   Some(CubicPath {
+      r: weighted.r,
+      h: CubicHomog([scale_row(&weighted.h.0[0]), scale_row(&weighted.h.0[1])]),
+      sigma: (weighted.sigma.0 * mu, weighted.sigma.1 * lambda),
+   })
+}
+
+impl CurveEval for RatCubicHomogWeighted {
+   #[allow(clippy::many_single_char_names)]
+   fn eval_with_bilinear(&self, t: &[f64]) -> Vec<[f64; 2]> {
+      let mut ret_val = Vec::<[f64; 2]>::with_capacity(t.len());
+      for item in t {
+         let a = self.sigma.0 * (*item - self.r[0]);
+         let b = self.sigma.1 * (self.r[1] - *item);
+         let recip_denom = 1.0 / eval_part_cubic(b, a, &self.h.0[2]);
+         let x = eval_part_cubic(b, a, &self.h.0[0]) * recip_denom;
+         let y = eval_part_cubic(b, a, &self.h.0[1]) * recip_denom;
+         ret_val.push([x, y]);
+      }
+      ret_val
+   }
+
+   // Quotient rule, with d/dt = sigma.0 * d/da - sigma.1 * d/db.
+   #[allow(clippy::suboptimal_flops)]
+   #[allow(clippy::many_single_char_names)]
+   fn eval_derivative_scaled(&self, t: &[f64], scale: f64) -> Vec<[f64; 2]> {
+      let mut ret_val = Vec::<[f64; 2]>::with_capacity(t.len());
+      for item in t {
+         let a = self.sigma.0 * (*item - self.r[0]);
+         let b = self.sigma.1 * (self.r[1] - *item);
+         let diff = |coeffs: &[f64; 4]| -> f64 {
+            self.sigma.0 * eval_part_cubic_da(b, a, coeffs)
+               - self.sigma.1 * eval_part_cubic_db(b, a, coeffs)
+         };
+         let denom = eval_part_cubic(b, a, &self.h.0[2]);
+         let denom_diff = diff(&self.h.0[2]);
+         let factor = scale / denom / denom;
+         let x = factor
+            * (diff(&self.h.0[0]) * denom - eval_part_cubic(b, a, &self.h.0[0]) * denom_diff);
+         let y = factor
+            * (diff(&self.h.0[1]) * denom - eval_part_cubic(b, a, &self.h.0[1]) * denom_diff);
+         ret_val.push([x, y]);
+      }
+      ret_val
+   }
+
+   #[allow(clippy::suboptimal_flops)]
+   fn characterize_endpoints(&self) -> ([[f64; 2]; 2], [[f64; 2]; 2]) {
+      let b = &self.h.0[0];
+      let c = &self.h.0[1];
+      let a = &self.h.0[2];
+      let (sigma_a, sigma_b) = self.sigma;
+      let factor_up = sigma_a / sigma_b / a[0] / a[0];
+      let factor_down = sigma_b / sigma_a / a[3] / a[3];
+      (
+         [[b[0] / a[0], c[0] / a[0]], [b[3] / a[3], c[3] / a[3]]],
+         [
+            [factor_up * (a[0] * b[1] - a[1] * b[0]), factor_up * (a[0] * c[1] - a[1] * c[0])],
+            [factor_down * (a[2] * b[3] - a[3] * b[2]), factor_down * (a[2] * c[3] - a[3] * c[2])],
+         ],
+      )
+   }
+}
+
+impl CurveTransform for RatCubicHomogWeighted {
+   #[allow(clippy::suboptimal_flops)]
+   fn displace(&mut self, d: [f64; 2]) {
+      for k in 0..4 {
+         self.h.0[0][k] += d[0] * self.h.0[2][k];
+         self.h.0[1][k] += d[1] * self.h.0[2][k];
+      }
+   }
+
+   fn bilinear_transform(&mut self, sigma_ratio: (f64, f64)) {
+      self.sigma.0 *= sigma_ratio.0;
+      self.sigma.1 *= sigma_ratio.1;
+   }
+
+   fn raw_change_range(&mut self, new_range: [f64; 2]) {
+      self.r = new_range;
+   }
+
+   fn select_range(&mut self, new_range: [f64; 2]) {
+      let (selection_transform, new_sigma) = select_range_c_mat(self.sigma, self.r, new_range);
+
+      let selected_cubic: RatCubicHomog = self.h.apply_c_mat(&selection_transform);
+
+      // It would be good to power-2 normalize.
+      self.sigma = new_sigma;
+      self.h = selected_cubic;
+      self.r = new_range;
+   }
+}
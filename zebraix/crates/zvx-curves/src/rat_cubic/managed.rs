@@ -0,0 +1,50 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::FourPointRatCubic;
+use serde::Serialize;
+use serde_default::DefaultFromSerde;
+use zvx_base::{RatCubicHomog, RatCubicHomogWeighted};
+
+#[derive(Debug, Serialize, DefaultFromSerde, PartialEq, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ManagedRatCubic {
+   pub rc_curve: RatCubicHomogWeighted,
+   // Used as desired, by renders, for clipping and curve approximation.
+   pub canvas_range: [f64; 4],
+}
+
+impl ManagedRatCubic {
+   #[must_use]
+   pub fn create_from_weighted(rc_curve: &RatCubicHomogWeighted, canvas_range: [f64; 4]) -> Self {
+      Self { rc_curve: rc_curve.clone(), canvas_range }
+   }
+
+   #[must_use]
+   pub fn create_from_four_points(four_points: &FourPointRatCubic, canvas_range: [f64; 4]) -> Self {
+      let points = &four_points.p;
+      let binomial = [1.0, 3.0, 3.0, 1.0];
+
+      let denom: [f64; 4] = std::array::from_fn(|k| binomial[k] * four_points.w[k]);
+      let numer_x: [f64; 4] = std::array::from_fn(|k| denom[k] * points[k][0]);
+      let numer_y: [f64; 4] = std::array::from_fn(|k| denom[k] * points[k][1]);
+      let rat_cubic = RatCubicHomogWeighted {
+         r: four_points.r,
+         h: RatCubicHomog([numer_x, numer_y, denom]),
+         sigma: four_points.sigma,
+      };
+
+      Self { rc_curve: rat_cubic, canvas_range }
+   }
+}
@@ -0,0 +1,259 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::bilinear_transform_timepoints;
+use crate::ManagedRatCubic;
+use approx::assert_abs_diff_eq;
+use zvx_base::utils::CoordSliceWrapped;
+use zvx_base::{CubicFourPoint, RatQuadHomog, RatQuadHomogWeighted};
+
+#[allow(clippy::unreadable_literal)]
+fn four_point_example_0() -> FourPointRatCubic {
+   FourPointRatCubic {
+      r: [-4.5, 13.5],
+      p: [[4.0, -1.5], [3.5, -2.0], [4.5, 1.5], [3.0, 2.0]],
+      w: [1.0, 2.5, 0.75, 1.25],
+      sigma: (3.6, 1.2),
+   }
+}
+
+fn weighted_example_0() -> RatCubicHomogWeighted {
+   ManagedRatCubic::create_from_four_points(&four_point_example_0(), [0.0; 4]).rc_curve
+}
+
+fn time_points(r: [f64; 2], num_segments: i32, nudge: f64) -> (Vec<f64>, f64) {
+   let t_int: Vec<i32> = (0..=num_segments).collect();
+   let mut t = Vec::<f64>::with_capacity(t_int.len());
+   let scale = nudge * (r[1] - r[0]) / f64::from(num_segments);
+   for item in &t_int {
+      t.push(f64::from(*item).mul_add(scale, r[0]));
+   }
+   (t, scale)
+}
+
+// Reference evaluation directly from the four points and weights, as a weighted average of the
+// control points with Bernstein weights.
+#[allow(clippy::many_single_char_names)]
+#[allow(clippy::suboptimal_flops)]
+fn reference_eval_four_point(four_point: &FourPointRatCubic, t: &[f64]) -> Vec<[f64; 2]> {
+   let t_bilineared = bilinear_transform_timepoints(t, four_point.sigma, four_point.r);
+   let mut ret_val = Vec::<[f64; 2]>::with_capacity(t.len());
+   for item in &t_bilineared {
+      let a = (*item - four_point.r[0]) / (four_point.r[1] - four_point.r[0]);
+      let b = 1.0 - a;
+      let bernstein = [b * b * b, 3.0 * b * b * a, 3.0 * b * a * a, a * a * a];
+      let mut sum = [0.0, 0.0, 0.0];
+      for ((basis, w), p) in bernstein.iter().zip(&four_point.w).zip(&four_point.p) {
+         let f = basis * w;
+         sum[0] += f * p[0];
+         sum[1] += f * p[1];
+         sum[2] += f;
+      }
+      ret_val.push([sum[0] / sum[2], sum[1] / sum[2]]);
+   }
+   ret_val
+}
+
+#[test]
+fn eval_test() {
+   let four_point = four_point_example_0();
+   let curve = weighted_example_0();
+   let (t, _) = time_points(curve.r, 20, 1.0);
+
+   let points = curve.eval_with_bilinear(&t);
+   let reference_points = reference_eval_four_point(&four_point, &t);
+
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&reference_points[..]),
+      epsilon = 1.0e-5
+   );
+
+   // Collapsing the bilinear transform into the coefficients does not change the curve.
+   let collapsed = rc_weighted_collapse_bilinear(&curve);
+   let collapsed_points = collapsed.eval_with_bilinear(&t);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&collapsed_points[..]),
+      &CoordSliceWrapped::from(&points[..]),
+      epsilon = 1.0e-5
+   );
+}
+
+#[must_use]
+fn euler_reference_derivative_scaled(
+   curve: &RatCubicHomogWeighted,
+   t: &[f64],
+   scale: f64,
+   delta: f64,
+) -> Vec<[f64; 2]> {
+   let mut t_plus = Vec::<f64>::with_capacity(t.len());
+   for item in t {
+      t_plus.push(*item + delta);
+   }
+
+   let points = curve.eval_with_bilinear(t);
+   let points_plus = curve.eval_with_bilinear(&t_plus);
+
+   let factor = scale / delta;
+   let mut ret_val = Vec::<[f64; 2]>::with_capacity(t.len());
+   for (a, b) in points.iter().zip(&points_plus[..]) {
+      ret_val.push([factor * (b[0] - a[0]), factor * (b[1] - a[1])]);
+   }
+   ret_val
+}
+
+#[test]
+fn derivative_scaled_test() {
+   const EULER_DELTA: f64 = 0.00001;
+   let curve = weighted_example_0();
+   // Stop short of the end so that the forward difference stays within range.
+   let (t, scale) = time_points([curve.r[0], curve.r[1] - 0.01], 12, 1.0);
+
+   let derivatives = curve.eval_derivative_scaled(&t, scale);
+   let reference_derivatives = euler_reference_derivative_scaled(&curve, &t, scale, EULER_DELTA);
+
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&derivatives[..]),
+      &CoordSliceWrapped::from(&reference_derivatives[..]),
+      epsilon = 1.0e-3
+   );
+}
+
+// Compare end-point characterization method against separate eval and derivative eval.
+#[test]
+fn endpoints_test() {
+   let curve = weighted_example_0();
+   let scale = curve.r[1] - curve.r[0];
+   let t = curve.r;
+
+   let reference_points = curve.eval_with_bilinear(&t[..]);
+   let reference_derivatives = curve.eval_derivative_scaled(&t, scale);
+
+   let endpoints = curve.characterize_endpoints();
+
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&endpoints.0[..]),
+      &CoordSliceWrapped::from(&reference_points[..]),
+      epsilon = 1.0e-4
+   );
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&endpoints.1[..]),
+      &CoordSliceWrapped::from(&reference_derivatives[..]),
+      epsilon = 1.0e-4
+   );
+}
+
+// Selecting a range, and then evaluating over the new range, should give the same points as the
+// original curve.
+#[test]
+fn select_range_test() {
+   let curve = weighted_example_0();
+   let new_range = [-1.5, 7.0];
+   let mut selected = curve.clone();
+   selected.select_range(new_range);
+   assert_eq!(selected.r, new_range);
+
+   let (t, _) = time_points(new_range, 10, 1.0);
+   let points = selected.eval_with_bilinear(&t);
+   let reference_points = curve.eval_with_bilinear(&t);
+
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&reference_points[..]),
+      epsilon = 1.0e-5
+   );
+}
+
+#[test]
+fn displace_test() {
+   let curve = weighted_example_0();
+   let d = [1.25, -3.5];
+   let mut displaced = curve.clone();
+   displaced.displace(d);
+
+   let (t, _) = time_points(curve.r, 10, 1.0);
+   let points = displaced.eval_with_bilinear(&t);
+   let mut reference_points = curve.eval_with_bilinear(&t);
+   for p in &mut reference_points {
+      p[0] += d[0];
+      p[1] += d[1];
+   }
+
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&reference_points[..]),
+      epsilon = 1.0e-5
+   );
+}
+
+// Equal weights reduce to the polynomial cubic, and so the exact conversion should succeed.
+// Unequal weights in the example should not convert.
+#[test]
+fn as_polynomial_test() {
+   let four_point = FourPointRatCubic { w: [0.5, 0.5, 0.5, 0.5], ..four_point_example_0() };
+   let curve = ManagedRatCubic::create_from_four_points(&four_point, [0.0; 4]).rc_curve;
+   let polynomial = rc_weighted_as_polynomial(&curve, 1.0e-9).unwrap();
+
+   let cubic = crate::ManagedCubic::create_from_control_points(
+      &CubicFourPoint {
+         r: four_point.r,
+         h: CubicHomog([
+            [four_point.p[0][0], four_point.p[1][0], four_point.p[2][0], four_point.p[3][0]],
+            [four_point.p[0][1], four_point.p[1][1], four_point.p[2][1], four_point.p[3][1]],
+         ]),
+         sigma: four_point.sigma,
+      },
+      [0.0; 4],
+   )
+   .four_point;
+
+   let (t, _) = time_points(curve.r, 10, 1.0);
+   let points = curve.eval_with_bilinear(&t);
+   let polynomial_points = polynomial.eval_with_bilinear(&t);
+   let cubic_points = cubic.eval_with_bilinear(&t);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&polynomial_points[..]),
+      epsilon = 1.0e-5
+   );
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&cubic_points[..]),
+      epsilon = 1.0e-5
+   );
+
+   assert!(rc_weighted_as_polynomial(&weighted_example_0(), 1.0e-9).is_none());
+}
+
+// Degree elevation of a rational quadratic is exact.
+#[test]
+fn elevate_rat_quad_test() {
+   let quad = RatQuadHomogWeighted {
+      r: [-3.0, 5.0],
+      h: RatQuadHomog([[-2.0, 3.0, 4.0], [1.0, 2.5, -1.5], [1.0, 1.5, 0.75]]),
+      sigma: (0.8, 1.7),
+   };
+   let curve = RatCubicHomogWeighted::from(&quad);
+
+   let (t, _) = time_points(curve.r, 10, 1.0);
+   let points = curve.eval_with_bilinear(&t);
+   let reference_points = quad.eval_with_bilinear(&t);
+
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&reference_points[..]),
+      epsilon = 1.0e-5
+   );
+}
@@ -16,12 +16,16 @@ pub mod managed;
 #[cfg(test)]
 mod tests;
 
-use crate::{CurveEval, CurveTransform, ZebraixAngle};
+use crate::{
+   CurveEval, CurveTransform, CurveTransformWithPrecision, PrecisionChoice, ZebraixAngle,
+};
 use serde::{Deserialize, Serialize};
 use serde_default::DefaultFromSerde;
+use zvx_base::compensated::power_2_normalizer;
 use zvx_base::{
    default_unit_ratio, is_default, is_default_unit_ratio, q_reduce, rat_quad_expand_weighted,
-   rat_quad_rq_eval, CurveMatrix, QMat, RatQuadHomog, RatQuadHomogPower, RatQuadHomogWeighted,
+   rat_quad_rq_eval, CurveMatrix, DoubleF64, QMat, RatQuadHomog, RatQuadHomogPower,
+   RatQuadHomogWeighted,
 };
 
 // Update by adding 3x1 vector multiplied by scalar.
@@ -57,12 +61,34 @@ pub struct ThreePointAngleRepr {
    pub sigma: (f64, f64),
 }
 
+// Conic arc through five points, from p[0] to p[4].
+#[derive(Debug, Serialize, DefaultFromSerde, PartialEq, Clone)]
+pub struct FivePointRatQuad {
+   pub r: [f64; 2], // Range.
+   pub p: [[f64; 2]; 5],
+   #[serde(skip_serializing_if = "is_default_unit_ratio", default = "default_unit_ratio")]
+   pub sigma: (f64, f64),
+}
+
+// Conic arc from p[0] to p[2], through p[1], with tangent directions at the end points.
+#[derive(Debug, Serialize, Deserialize, DefaultFromSerde, PartialEq, Clone)]
+pub struct ThreePointTangentRepr {
+   pub r: [f64; 2], // Range.
+   pub p: [[f64; 2]; 3],
+   pub tangents: [[f64; 2]; 2],
+   #[serde(skip_serializing_if = "is_default_unit_ratio", default = "default_unit_ratio")]
+   pub sigma: (f64, f64),
+}
+
 #[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
 pub enum SpecifiedRatQuad {
    #[default]
    None, // For, say, polynomial directly specified.
    FourPoint,
    ThreePointAngle,
+   FivePoint,
+   ThreePointTangent,
+   SvgArc,
 }
 
 // New trait, applied to RatQuadHomogWeighted and Cubilinear:
@@ -189,7 +215,7 @@ impl CurveTransform for RatQuadHomogWeighted {
 
       // This appears slightly less accurate than the reference, which converts to power form
       // and doing a simple cut there.  Differences may be more substantial when recutting
-      // elliptical arcs to wider ranges.
+      // elliptical arcs to wider ranges.  See `select_range_with` for a compensated version.
       let selection_transform: QMat = [
          [alpha * alpha, 2.0 * alpha * gamma, gamma * gamma],
          [alpha * beta, alpha * delta + beta * gamma, gamma * delta],
@@ -205,6 +231,82 @@ impl CurveTransform for RatQuadHomogWeighted {
    }
 }
 
+// Power-2 normalization, which is exact, so that repeated transformations neither overflow nor
+// underflow.
+fn rq_power_2_normalize(curve: &mut RatQuadHomogWeighted) {
+   let a = &curve.h.0[2];
+   let h_factor = power_2_normalizer(a[0].abs().max(a[1].abs()).max(a[2].abs()));
+   for row in &mut curve.h.0 {
+      for v in row {
+         *v *= h_factor;
+      }
+   }
+   let sigma_factor = power_2_normalizer(curve.sigma.1);
+   curve.sigma = (curve.sigma.0 * sigma_factor, curve.sigma.1 * sigma_factor);
+}
+
+impl CurveTransformWithPrecision for RatQuadHomogWeighted {
+   // The curve depends only on the ratio of the sigma components.  The compensated version
+   // rounds the ratio once, rather than rounding each component.
+   fn bilinear_transform_with(&mut self, sigma_ratio: (f64, f64), precision: PrecisionChoice) {
+      match precision {
+         PrecisionChoice::Ordinary => self.bilinear_transform(sigma_ratio),
+         PrecisionChoice::Compensated => {
+            let numerator = DoubleF64::product(self.sigma.0, sigma_ratio.0);
+            let denominator = DoubleF64::product(self.sigma.1, sigma_ratio.1);
+            let ratio = numerator / denominator;
+            self.sigma = (ratio.to_f64(), 1.0);
+            rq_power_2_normalize(self);
+         }
+      }
+   }
+
+   // The compensated version is as the ordinary, but computes the selection transform in
+   // double-f64, avoiding cancellation in complementary factors, and applies it with
+   // compensated dot products.
+   fn select_range_with(&mut self, new_range: [f64; 2], precision: PrecisionChoice) {
+      match precision {
+         PrecisionChoice::Ordinary => self.select_range(new_range),
+         PrecisionChoice::Compensated => {
+            let a_k = DoubleF64::sum(new_range[0], -self.r[0]) * self.sigma.0;
+            let b_k = DoubleF64::sum(self.r[1], -new_range[0]) * self.sigma.1;
+            let a_l = DoubleF64::sum(new_range[1], -self.r[0]) * self.sigma.0;
+            let b_l = DoubleF64::sum(self.r[1], -new_range[1]) * self.sigma.1;
+
+            let sum_k = a_k + b_k;
+            let sum_l = a_l + b_l;
+            let alpha = b_k / sum_k;
+            let beta = a_k / sum_k;
+            let gamma = b_l / sum_l;
+            let delta = a_l / sum_l;
+
+            let two = DoubleF64::from(2.0);
+            let selection_transform: [[DoubleF64; 3]; 3] = [
+               [alpha * alpha, two * alpha * gamma, gamma * gamma],
+               [alpha * beta, alpha * delta + beta * gamma, gamma * delta],
+               [beta * beta, two * beta * delta, delta * delta],
+            ];
+
+            let mut selected_rq = RatQuadHomog::default();
+            for (out_row, in_row) in selected_rq.0.iter_mut().zip(&self.h.0) {
+               for (j, out_v) in out_row.iter_mut().enumerate() {
+                  let mut accumulated = DoubleF64::default();
+                  for (in_v, transform_row) in in_row.iter().zip(&selection_transform) {
+                     accumulated = accumulated + transform_row[j] * *in_v;
+                  }
+                  *out_v = accumulated.to_f64();
+               }
+            }
+
+            self.sigma = (sum_l.to_f64(), sum_k.to_f64());
+            self.h = selected_rq;
+            self.r = new_range;
+            rq_power_2_normalize(self);
+         }
+      }
+   }
+}
+
 // Internal bilinear transform.
 #[must_use]
 #[allow(clippy::suboptimal_flops)]
@@ -12,7 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{FourPointRatQuad, SpecifiedRatQuad, ThreePointAngleRepr};
+use crate::conic::{rat_quad_from_tangents, ImplicitConic};
+use crate::svg_arc::{rat_quads_from_svg_arc, SvgArcRepr};
+use crate::{
+   CurveTransform, FivePointRatQuad, FourPointRatQuad, SpecifiedRatQuad, ThreePointAngleRepr,
+   ThreePointTangentRepr,
+};
 use serde::Serialize;
 use serde_default::DefaultFromSerde;
 use zvx_base::{RatQuadHomog, RatQuadHomogWeighted};
@@ -112,4 +117,40 @@ impl ManagedRatQuad {
       };
       Ok(Self { rq_curve: rat_quad, specified: SpecifiedRatQuad::ThreePointAngle, canvas_range })
    }
+
+   #[allow(clippy::missing_errors_doc)]
+   pub fn create_from_five_points(
+      five_points: &FivePointRatQuad,
+      canvas_range: [f64; 4],
+   ) -> Result<Self, &'static str> {
+      let p = &five_points.p;
+      let conic = ImplicitConic::create_from_five_points(p)?;
+      let mut rat_quad = conic.arc_through(p[0], p[2], p[4], five_points.r)?;
+      rat_quad.bilinear_transform(five_points.sigma);
+      Ok(Self { rq_curve: rat_quad, specified: SpecifiedRatQuad::FivePoint, canvas_range })
+   }
+
+   #[allow(clippy::missing_errors_doc)]
+   pub fn create_from_tangents(
+      tangent_repr: &ThreePointTangentRepr,
+      canvas_range: [f64; 4],
+   ) -> Result<Self, &'static str> {
+      let mut rat_quad =
+         rat_quad_from_tangents(&tangent_repr.p, &tangent_repr.tangents, tangent_repr.r)?;
+      rat_quad.bilinear_transform(tangent_repr.sigma);
+      Ok(Self { rq_curve: rat_quad, specified: SpecifiedRatQuad::ThreePointTangent, canvas_range })
+   }
+
+   // An SVG arc may need more than one segment, and the range is divided evenly between them.
+   #[allow(clippy::missing_errors_doc)]
+   pub fn create_from_svg_arc(
+      arc: &SvgArcRepr,
+      r: [f64; 2],
+      canvas_range: [f64; 4],
+   ) -> Result<Vec<Self>, &'static str> {
+      Ok(rat_quads_from_svg_arc(arc, r)?
+         .into_iter()
+         .map(|rq_curve| Self { rq_curve, specified: SpecifiedRatQuad::SvgArc, canvas_range })
+         .collect())
+   }
 }
@@ -14,12 +14,15 @@
 
 use super::*;
 use crate::bilinear_transform_timepoints;
-use crate::CurveEval;
+use crate::test_helpers::weighted_example_0;
+use crate::{CurveEval, CurveTransformWithPrecision, PrecisionChoice};
 use approx::assert_abs_diff_eq;
 use zvx_base::rat_quad_expand_power;
 use zvx_base::utils::CoordSliceWrapped;
 use zvx_base::utils::PathWrapped;
-use zvx_base::{q_reduce, rat_quad_rq_eval, RatQuadHomog, RatQuadHomogPower, RatQuadHomogWeighted};
+use zvx_base::{
+   q_reduce, rat_quad_rq_eval, DoubleF64, RatQuadHomog, RatQuadHomogPower, RatQuadHomogWeighted,
+};
 
 // Done: Checklist, Eval end points.
 // Done: Checklist, Eval end point derivatives.
@@ -90,19 +93,6 @@ fn reference_create_power_from_weighted(weighted: &RatQuadHomogWeighted) -> RatQ
    RatQuadHomogPower { r: weighted.r, h: RatQuadHomog([b, c, a]), sigma: weighted.sigma }
 }
 
-#[allow(clippy::unreadable_literal)]
-fn weighted_example_0() -> RatQuadHomogWeighted {
-   RatQuadHomogWeighted {
-      r: [-6.0, 14.0],
-      h: RatQuadHomog([
-         [-2.946278254943949, 0.0, -3.9283710065919317],
-         [-2.946278254943949, 2.0 * 0.6944444444444453, 3.9283710065919317],
-         [1.9641855032959659, 2.0 * 1.388888888888889, 1.9641855032959659],
-      ]),
-      sigma: (2.0, 1.5),
-   }
-}
-
 #[test]
 #[allow(clippy::unreadable_literal)]
 fn weighted_power_conversion_test() {
@@ -458,3 +448,78 @@ fn select_range_test() {
       // );
    }
 }
+
+// Evaluation in double-f64 throughout, used as high-precision reference.
+fn compensated_reference_eval(weighted: &RatQuadHomogWeighted, t: &[f64]) -> Vec<[f64; 2]> {
+   t.iter()
+      .map(|t| {
+         let a = DoubleF64::sum(*t, -weighted.r[0]) * weighted.sigma.0;
+         let b = DoubleF64::sum(weighted.r[1], -*t) * weighted.sigma.1;
+         let coords: Vec<DoubleF64> = weighted
+            .h
+            .0
+            .iter()
+            .map(|row| b * b * row[0] + a * b * row[1] + a * a * row[2])
+            .collect();
+         [(coords[0] / coords[2]).to_f64(), (coords[1] / coords[2]).to_f64()]
+      })
+      .collect()
+}
+
+// Largest distance between evaluations of chain-selected curve and the original.
+fn chained_selection_gap(precision: PrecisionChoice) -> f64 {
+   let original = weighted_example_0();
+   let ranges = [[-5.3, 9.7], [-4.1, 2.9], [-5.9, 13.3], [0.3, 0.7], [-6.0, 14.0], [1.1, 1.3]];
+   let mut selected = original.clone();
+   for _ in 0..20 {
+      for range in ranges {
+         selected.select_range_with(range, precision);
+      }
+   }
+   let t: Vec<f64> = (0..=20).map(|i| f64::from(i).mul_add(0.01, 1.1)).collect();
+   let points = selected.eval_with_bilinear(&t);
+   let reference_points = compensated_reference_eval(&original, &t);
+   points
+      .iter()
+      .zip(&reference_points)
+      .map(|(p, q)| (p[0] - q[0]).hypot(p[1] - q[1]))
+      .fold(0.0, f64::max)
+}
+
+#[test]
+fn compensated_select_range_test() {
+   let ordinary_gap = chained_selection_gap(PrecisionChoice::Ordinary);
+   let compensated_gap = chained_selection_gap(PrecisionChoice::Compensated);
+   // Chained ordinary selection drifts by about 1e-12, and compensated by about 1e-15.
+   assert!(compensated_gap < 1.0e-14);
+   assert!(100.0 * compensated_gap < ordinary_gap);
+
+   // Ordinary precision is the same as plain selection.
+   let mut plain = weighted_example_0();
+   plain.select_range([-4.1, 2.9]);
+   let mut ordinary = weighted_example_0();
+   ordinary.select_range_with([-4.1, 2.9], PrecisionChoice::Ordinary);
+   assert_eq!(plain, ordinary);
+}
+
+#[test]
+fn compensated_bilinear_transform_test() {
+   let original = weighted_example_0();
+   let mut transformed = original.clone();
+   for _ in 0..50 {
+      transformed.bilinear_transform_with((0.7, 1.3), PrecisionChoice::Compensated);
+      transformed.bilinear_transform_with((1.3, 0.7), PrecisionChoice::Compensated);
+   }
+   transformed.bilinear_transform_with((3.0, 1.0), PrecisionChoice::Compensated);
+   let mut reference = original;
+   reference.bilinear_transform((3.0, 1.0));
+
+   let t: Vec<f64> = (0..=20).map(|i| f64::from(i).mul_add(1.0, -6.0)).collect();
+   let points = transformed.eval_with_bilinear(&t);
+   let reference_points = compensated_reference_eval(&reference, &t);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&reference_points[..]),
+      epsilon = 1.0e-13
+   );
+}
@@ -0,0 +1,295 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{CurveEval, CurveTransform};
+use zvx_base::{OneOfSegment, PolylinePath};
+
+// Location and forward direction along a segment.  The direction is not normalized.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SegmentPlacement {
+   pub point: [f64; 2],
+   pub direction: [f64; 2],
+}
+
+// Distance measured after scaling, so that it can be measured on the canvas.
+fn scaled_distance(a: [f64; 2], b: [f64; 2], metric: [f64; 2]) -> f64 {
+   (metric[0] * (b[0] - a[0])).hypot(metric[1] * (b[1] - a[1]))
+}
+
+const fn curve_range(segment: &OneOfSegment) -> Option<[f64; 2]> {
+   match segment {
+      OneOfSegment::Arc(path) => Some(path.angle_range),
+      OneOfSegment::Cubic(path) => Some(path.r),
+      OneOfSegment::Hyperbolic(path) => Some([path.range.0, path.range.1]),
+      OneOfSegment::RatCubic(path) => Some(path.r),
+      OneOfSegment::Polyline(_) | OneOfSegment::Neither => None,
+   }
+}
+
+fn curve_eval(segment: &OneOfSegment, t: &[f64]) -> Vec<[f64; 2]> {
+   match segment {
+      OneOfSegment::Arc(path) => path.eval_with_bilinear(t),
+      OneOfSegment::Cubic(path) => path.eval_with_bilinear(t),
+      OneOfSegment::Hyperbolic(path) => path.eval_with_bilinear(t),
+      OneOfSegment::RatCubic(path) => path.eval_with_bilinear(t),
+      OneOfSegment::Polyline(_) | OneOfSegment::Neither => Vec::new(),
+   }
+}
+
+fn curve_derivative(segment: &OneOfSegment, t: &[f64]) -> Vec<[f64; 2]> {
+   match segment {
+      OneOfSegment::Arc(path) => path.eval_derivative_scaled(t, 1.0),
+      OneOfSegment::Cubic(path) => path.eval_derivative_scaled(t, 1.0),
+      OneOfSegment::Hyperbolic(path) => path.eval_derivative_scaled(t, 1.0),
+      OneOfSegment::RatCubic(path) => path.eval_derivative_scaled(t, 1.0),
+      OneOfSegment::Polyline(_) | OneOfSegment::Neither => Vec::new(),
+   }
+}
+
+// Forward direction at t.  Where the derivative vanishes, such as at a cubic end with a
+// coincident control point, the direction is that of a short chord.
+fn curve_placement(segment: &OneOfSegment, range: [f64; 2], t: f64) -> SegmentPlacement {
+   let point = curve_eval(segment, &[t])[0];
+   let mut direction = curve_derivative(segment, &[t])[0];
+   let span = range[1] - range[0];
+   if direction[0].hypot(direction[1]) * span.abs() < 1.0e-9 {
+      let nudge = 1.0e-4 * span;
+      let t_near = if t + nudge > range[1] { t - nudge } else { t + nudge };
+      let near = curve_eval(segment, &[t_near])[0];
+      let sign = (t_near - t).signum();
+      direction = [sign * (near[0] - point[0]), sign * (near[1] - point[1])];
+   }
+   SegmentPlacement { point, direction }
+}
+
+// Placements at start and end of segment, or None if the segment is empty.
+#[must_use]
+pub fn segment_ends(segment: &OneOfSegment) -> Option<[SegmentPlacement; 2]> {
+   if let OneOfSegment::Polyline(locations) = segment {
+      let n = locations.len();
+      if n < 2 {
+         return None;
+      }
+      let start_direction = [locations[1][0] - locations[0][0], locations[1][1] - locations[0][1]];
+      let end_direction =
+         [locations[n - 1][0] - locations[n - 2][0], locations[n - 1][1] - locations[n - 2][1]];
+      return Some([
+         SegmentPlacement { point: locations[0], direction: start_direction },
+         SegmentPlacement { point: locations[n - 1], direction: end_direction },
+      ]);
+   }
+   let range = curve_range(segment)?;
+   Some([curve_placement(segment, range, range[0]), curve_placement(segment, range, range[1])])
+}
+
+// Direction bisecting incoming and outgoing directions, as for markers at vertices.
+#[must_use]
+pub fn bisect_directions(incoming: [f64; 2], outgoing: [f64; 2], metric: [f64; 2]) -> [f64; 2] {
+   let in_norm = scaled_distance([0.0, 0.0], incoming, metric);
+   let out_norm = scaled_distance([0.0, 0.0], outgoing, metric);
+   if in_norm == 0.0 || out_norm == 0.0 {
+      return if in_norm == 0.0 { outgoing } else { incoming };
+   }
+   [incoming[0] / in_norm + outgoing[0] / out_norm, incoming[1] / in_norm + outgoing[1] / out_norm]
+}
+
+// Placements within a segment, that is at interior vertices of polylines, or at the middle
+// of the range for curves.
+#[must_use]
+pub fn segment_interior_placements(
+   segment: &OneOfSegment,
+   metric: [f64; 2],
+) -> Vec<SegmentPlacement> {
+   if let OneOfSegment::Polyline(locations) = segment {
+      return locations
+         .windows(3)
+         .map(|w| SegmentPlacement {
+            point: w[1],
+            direction: bisect_directions(
+               [w[1][0] - w[0][0], w[1][1] - w[0][1]],
+               [w[2][0] - w[1][0], w[2][1] - w[1][1]],
+               metric,
+            ),
+         })
+         .collect();
+   }
+   curve_range(segment)
+      .map(|range| vec![curve_placement(segment, range, 0.5 * (range[0] + range[1]))])
+      .unwrap_or_default()
+}
+
+// Find parameter at which (scaled) distance from the end at range[1] is the target.  The
+// search is by bisection, and so assumes that distance increases away from the end.
+fn find_trim_parameter(
+   segment: &OneOfSegment,
+   range: [f64; 2],
+   distance: f64,
+   metric: [f64; 2],
+) -> f64 {
+   let end_point = curve_eval(segment, &[range[1]])[0];
+   let mut inner = range[0];
+   let mut outer = range[1];
+   for _ in 0..60 {
+      let mid = 0.5 * (inner + outer);
+      let p = curve_eval(segment, &[mid])[0];
+      if scaled_distance(p, end_point, metric) > distance {
+         inner = mid;
+      } else {
+         outer = mid;
+      }
+   }
+   0.5 * (inner + outer)
+}
+
+fn trim_polyline_start(locations: &mut PolylinePath, distance: f64, metric: [f64; 2]) {
+   let mut remaining = distance;
+   while locations.len() >= 2 {
+      let edge = scaled_distance(locations[0], locations[1], metric);
+      if edge > remaining {
+         let f = remaining / edge;
+         locations[0] = [
+            f.mul_add(locations[1][0] - locations[0][0], locations[0][0]),
+            f.mul_add(locations[1][1] - locations[0][1], locations[0][1]),
+         ];
+         return;
+      }
+      remaining -= edge;
+      if locations.len() == 2 {
+         // Trimmed to nothing, leave as single point.
+         locations[0] = locations[1];
+         return;
+      }
+      locations.remove(0);
+   }
+}
+
+// Shortens segment by distances (after scaling by metric) from start and end.  Curves are
+// trimmed by changing their range.  Trimming is capped at the middle of the segment.
+#[must_use]
+pub fn trim_segment(segment: &OneOfSegment, trim: [f64; 2], metric: [f64; 2]) -> OneOfSegment {
+   if let OneOfSegment::Polyline(locations) = segment {
+      let mut trimmed = locations.clone();
+      if trim[0] > 0.0 {
+         trim_polyline_start(&mut trimmed, trim[0], metric);
+      }
+      if trim[1] > 0.0 {
+         trimmed.reverse();
+         trim_polyline_start(&mut trimmed, trim[1], metric);
+         trimmed.reverse();
+      }
+      return OneOfSegment::Polyline(trimmed);
+   }
+   let Some(range) = curve_range(segment) else {
+      return segment.clone();
+   };
+   let middle = 0.5 * (range[0] + range[1]);
+   let new_start = if trim[0] > 0.0 {
+      find_trim_parameter(segment, [middle, range[0]], trim[0], metric)
+   } else {
+      range[0]
+   };
+   let new_end = if trim[1] > 0.0 {
+      find_trim_parameter(segment, [middle, range[1]], trim[1], metric)
+   } else {
+      range[1]
+   };
+   let new_range = [new_start, new_end];
+
+   let mut trimmed = segment.clone();
+   match &mut trimmed {
+      OneOfSegment::Arc(path) => path.angle_range = new_range,
+      OneOfSegment::Cubic(path) => path.select_range(new_range),
+      OneOfSegment::Hyperbolic(path) => path.range = (new_range[0], new_range[1]),
+      OneOfSegment::RatCubic(path) => path.select_range(new_range),
+      OneOfSegment::Polyline(_) | OneOfSegment::Neither => {}
+   }
+   trimmed
+}
+
+// Points along a segment.  Polylines give their locations, and curves are evaluated at the
+// given number of equal parameter intervals.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn segment_samples(segment: &OneOfSegment, intervals: usize) -> Vec<[f64; 2]> {
+   if let OneOfSegment::Polyline(locations) = segment {
+      return locations.clone();
+   }
+   let Some(range) = curve_range(segment) else {
+      return Vec::new();
+   };
+   let t: Vec<f64> = (0..=intervals)
+      .map(|i| (range[1] - range[0]).mul_add(i as f64 / intervals as f64, range[0]))
+      .collect();
+   curve_eval(segment, &t)
+}
+
+// Sequence of segments sampled for placement by (scaled) arc length, such as for text along a
+// path.  Curves are sampled evenly in their parameter, which is adequate for labels.
+#[derive(Debug, Default, Clone)]
+pub struct ArcLengthSampling {
+   points: Vec<[f64; 2]>,
+   distances: Vec<f64>,
+}
+
+impl ArcLengthSampling {
+   const CURVE_SAMPLES: usize = 64;
+
+   #[must_use]
+   pub fn create(segments: &[OneOfSegment], metric: [f64; 2]) -> Self {
+      let mut points: Vec<[f64; 2]> = Vec::new();
+      for segment in segments {
+         for point in segment_samples(segment, Self::CURVE_SAMPLES) {
+            if points.last() != Some(&point) {
+               points.push(point);
+            }
+         }
+      }
+
+      let mut distances = Vec::with_capacity(points.len());
+      let mut total = 0.0;
+      for i in 0..points.len() {
+         if i > 0 {
+            total += scaled_distance(points[i - 1], points[i], metric);
+         }
+         distances.push(total);
+      }
+      Self { points, distances }
+   }
+
+   #[must_use]
+   pub fn length(&self) -> f64 {
+      self.distances.last().copied().unwrap_or(0.0)
+   }
+
+   // Placement at distance from start.  Distances beyond either end extrapolate along the end
+   // direction.  None if the path has fewer than two distinct points.
+   #[must_use]
+   pub fn placement_at(&self, distance: f64) -> Option<SegmentPlacement> {
+      let n = self.points.len();
+      if n < 2 {
+         return None;
+      }
+      let i = self.distances.partition_point(|d| *d <= distance).clamp(1, n - 1);
+      let (a, b) = (self.points[i - 1], self.points[i]);
+      let span = self.distances[i] - self.distances[i - 1];
+      let fraction = if span > 0.0 { (distance - self.distances[i - 1]) / span } else { 0.0 };
+      Some(SegmentPlacement {
+         point: [fraction.mul_add(b[0] - a[0], a[0]), fraction.mul_add(b[1] - a[1], a[1])],
+         direction: [b[0] - a[0], b[1] - a[1]],
+      })
+   }
+}
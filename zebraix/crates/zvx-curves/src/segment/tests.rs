@@ -0,0 +1,161 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use approx::assert_abs_diff_eq;
+use std::f64::consts::{FRAC_PI_2, PI};
+use zvx_base::utils::CoordSliceWrapped;
+use zvx_base::{ArcPath, CubicHomog, CubicPath};
+
+fn cubic_example() -> CubicPath {
+   CubicPath {
+      r: [-4.5, 13.5],
+      h: CubicHomog([[4.0, 3.0 * 3.5, 3.0 * 4.5, 3.0], [-1.5, 3.0 * -2.0, 3.0 * 1.5, 2.0]]),
+      sigma: (3.6, 1.2),
+   }
+}
+
+#[test]
+fn ends_test() {
+   // Semicircle, radius 2, anticlockwise from (2, 0).
+   let arc = OneOfSegment::Arc(ArcPath {
+      angle_range: [0.0, PI],
+      center: [0.0, 0.0],
+      transform: [2.0, 0.0, 0.0, 2.0],
+   });
+   let ends = segment_ends(&arc).unwrap();
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&[ends[0].point, ends[1].point][..]),
+      &CoordSliceWrapped::from(&[[2.0, 0.0], [-2.0, 0.0]][..]),
+      epsilon = 1.0e-12
+   );
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&[ends[0].direction, ends[1].direction][..]),
+      &CoordSliceWrapped::from(&[[0.0, 2.0], [0.0, -2.0]][..]),
+      epsilon = 1.0e-12
+   );
+   let middle = segment_interior_placements(&arc, [1.0, 1.0]);
+   assert_eq!(middle.len(), 1);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&[middle[0].point][..]),
+      &CoordSliceWrapped::from(&[[0.0, 2.0]][..]),
+      epsilon = 1.0e-12
+   );
+
+   // Cubic directions are along control polygon at ends.
+   let cubic = cubic_example();
+   let ends = segment_ends(&OneOfSegment::Cubic(cubic)).unwrap();
+   let cross = |d: [f64; 2], e: [f64; 2]| d[0] * e[1] - d[1] * e[0];
+   assert_abs_diff_eq!(cross(ends[0].direction, [-0.5, -0.5]), 0.0, epsilon = 1.0e-12);
+   assert!(ends[0].direction[0] < 0.0);
+   assert_abs_diff_eq!(cross(ends[1].direction, [-1.5, 0.5]), 0.0, epsilon = 1.0e-12);
+   assert!(ends[1].direction[0] < 0.0);
+
+   // Interior polyline vertices bisect the edges.
+   let polyline = OneOfSegment::Polyline(vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]);
+   let corners = segment_interior_placements(&polyline, [1.0, 1.0]);
+   assert_eq!(corners.len(), 1);
+   assert_abs_diff_eq!(corners[0].direction[0], corners[0].direction[1], epsilon = 1.0e-12);
+   assert!(segment_ends(&OneOfSegment::Polyline(vec![[0.0, 0.0]])).is_none());
+}
+
+#[test]
+fn trim_test() {
+   let metric = [2.0, 0.5];
+   let arc = OneOfSegment::Arc(ArcPath {
+      angle_range: [0.0, FRAC_PI_2],
+      center: [1.0, 0.0],
+      transform: [1.0, 0.0, 0.0, 3.0],
+   });
+   let cubic = OneOfSegment::Cubic(cubic_example());
+   let polyline = OneOfSegment::Polyline(vec![[0.0, 0.0], [0.2, 0.0], [0.2, 3.0], [1.0, 3.0]]);
+
+   for segment in [arc, cubic, polyline] {
+      let original = segment_ends(&segment).unwrap();
+      let trimmed = trim_segment(&segment, [0.25, 0.75], metric);
+      let trimmed_ends = segment_ends(&trimmed).unwrap();
+      assert_abs_diff_eq!(
+         scaled_distance(original[0].point, trimmed_ends[0].point, metric),
+         0.25,
+         epsilon = 1.0e-9
+      );
+      assert_abs_diff_eq!(
+         scaled_distance(original[1].point, trimmed_ends[1].point, metric),
+         0.75,
+         epsilon = 1.0e-9
+      );
+      // Direction is preserved.
+      let d = original[1].direction;
+      let e = trimmed_ends[1].direction;
+      assert!(d[0] * e[0] + d[1] * e[1] > 0.0);
+   }
+}
+
+#[test]
+fn arc_length_test() {
+   // Semicircle, radius 2, followed by a straight line back to the start.
+   let segments = [
+      OneOfSegment::Arc(ArcPath {
+         angle_range: [0.0, PI],
+         center: [0.0, 0.0],
+         transform: [2.0, 0.0, 0.0, 2.0],
+      }),
+      OneOfSegment::Polyline(vec![[-2.0, 0.0], [2.0, 0.0]]),
+   ];
+   let sampling = ArcLengthSampling::create(&segments, [1.0, 1.0]);
+   assert_abs_diff_eq!(sampling.length(), 2.0 * PI + 4.0, epsilon = 2.0e-3);
+
+   let top = sampling.placement_at(0.5 * sampling.length() - 2.0).unwrap();
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&[top.point][..]),
+      &CoordSliceWrapped::from(&[[0.0, 2.0]][..]),
+      epsilon = 1.0e-3
+   );
+   // Direction is that of the chord between samples, so is nearly horizontal.
+   assert!(top.direction[0] < 0.0);
+   assert!(top.direction[1].abs() < 0.05 * top.direction[0].abs());
+
+   // Extrapolation beyond the end, along the line.
+   let beyond = sampling.placement_at(sampling.length() + 1.0).unwrap();
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&[beyond.point][..]),
+      &CoordSliceWrapped::from(&[[3.0, 0.0]][..]),
+      epsilon = 1.0e-12
+   );
+
+   // Scaling by metric doubles the line length.
+   let line = ArcLengthSampling::create(&segments[1..], [2.0, 1.0]);
+   assert_abs_diff_eq!(line.length(), 8.0, epsilon = 1.0e-12);
+   assert!(ArcLengthSampling::create(&[], [1.0, 1.0]).placement_at(0.0).is_none());
+}
+
+#[test]
+fn samples_test() {
+   // Quarter circle, radius 2, sampled at half-way.
+   let arc = OneOfSegment::Arc(ArcPath {
+      angle_range: [0.0, FRAC_PI_2],
+      center: [1.0, 0.0],
+      transform: [2.0, 0.0, 0.0, 2.0],
+   });
+   let half = 2.0 * (0.5 * FRAC_PI_2).cos();
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&segment_samples(&arc, 2)[..]),
+      &CoordSliceWrapped::from(&[[3.0, 0.0], [1.0 + half, half], [1.0, 2.0]][..]),
+      epsilon = 1.0e-9
+   );
+
+   let polyline = OneOfSegment::Polyline(vec![[0.0, 0.0], [1.0, 1.0]]);
+   assert_eq!(segment_samples(&polyline, 8), vec![[0.0, 0.0], [1.0, 1.0]]);
+   assert!(segment_samples(&OneOfSegment::Neither, 8).is_empty());
+}
@@ -17,7 +17,7 @@
 // for hyperbolic, and (implied) linear-quadratic for parabolic.
 
 use crate::base::TEval;
-use crate::RegularizedRatQuadPath;
+use crate::{CurveEval, RegularizedRatQuadPath};
 use serde::Serialize;
 use zvx_base::{ArcPath, CubicPath, HyperbolicPath, OneOfSegment, RatQuadHomogWeighted};
 
@@ -94,3 +94,70 @@ impl TEval for HyperbolicPath {
       ret_val
    }
 }
+
+// The parameter of arc paths is the angle.
+#[allow(clippy::suboptimal_flops)]
+impl CurveEval for ArcPath {
+   fn eval_with_bilinear(&self, t: &[f64]) -> Vec<[f64; 2]> {
+      let m = &self.transform;
+      t.iter()
+         .map(|theta| {
+            let (s, c) = theta.sin_cos();
+            [m[0] * c + m[2] * s + self.center[0], m[1] * c + m[3] * s + self.center[1]]
+         })
+         .collect()
+   }
+
+   fn eval_derivative_scaled(&self, t: &[f64], scale: f64) -> Vec<[f64; 2]> {
+      let m = &self.transform;
+      t.iter()
+         .map(|theta| {
+            let (s, c) = theta.sin_cos();
+            [scale * (m[2] * c - m[0] * s), scale * (m[3] * c - m[1] * s)]
+         })
+         .collect()
+   }
+
+   fn characterize_endpoints(&self) -> ([[f64; 2]; 2], [[f64; 2]; 2]) {
+      let span = self.angle_range[1] - self.angle_range[0];
+      let points = self.eval_with_bilinear(&self.angle_range);
+      let velocities = self.eval_derivative_scaled(&self.angle_range, span);
+      ([points[0], points[1]], [velocities[0], velocities[1]])
+   }
+}
+
+// As with `TEval`, the bilinear transformation is already applied to the partial fractions.
+#[allow(clippy::suboptimal_flops)]
+impl CurveEval for HyperbolicPath {
+   fn eval_with_bilinear(&self, t: &[f64]) -> Vec<[f64; 2]> {
+      self.eval_maybe_bilinear(t)
+   }
+
+   // Derivative of offset + minus_partial / (lambda - mu t) + plus_partial / (lambda + mu t),
+   // that is mu minus_partial / (lambda - mu t)^2 - mu plus_partial / (lambda + mu t)^2.  The
+   // pairing of minus and plus terms is intended.
+   #[allow(clippy::suspicious_operation_groupings)]
+   fn eval_derivative_scaled(&self, t: &[f64], scale: f64) -> Vec<[f64; 2]> {
+      let lambda = self.lambda;
+      let mu = self.mu;
+      t.iter()
+         .map(|item| {
+            let minus_denom = lambda - mu * *item;
+            let plus_denom = lambda + mu * *item;
+            let minus_factor = scale * mu / (minus_denom * minus_denom);
+            let plus_factor = scale * mu / (plus_denom * plus_denom);
+            [
+               minus_factor * self.minus_partial[0] - plus_factor * self.plus_partial[0],
+               minus_factor * self.minus_partial[1] - plus_factor * self.plus_partial[1],
+            ]
+         })
+         .collect()
+   }
+
+   fn characterize_endpoints(&self) -> ([[f64; 2]; 2], [[f64; 2]; 2]) {
+      let range = [self.range.0, self.range.1];
+      let points = self.eval_with_bilinear(&range);
+      let velocities = self.eval_derivative_scaled(&range, range[1] - range[0]);
+      ([points[0], points[1]], [velocities[0], velocities[1]])
+   }
+}
@@ -0,0 +1,221 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{CurveEval, ImplicitConic};
+use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+use zvx_base::{is_default, RatQuadHomog, RatQuadHomogWeighted};
+
+// Elliptical arc in the endpoint parameterization of SVG paths, as in the SVG "A" command.
+//
+// The x-axis rotation is in degrees.  The sweep flag is true when the arc is traversed in the
+// direction of increasing angle.
+#[derive(Debug, Serialize, Deserialize, DefaultFromSerde, PartialEq, Clone)]
+pub struct SvgArcRepr {
+   pub start: [f64; 2],
+   pub radii: [f64; 2],
+   #[serde(skip_serializing_if = "is_default")]
+   pub x_axis_rotation: f64,
+   #[serde(skip_serializing_if = "is_default")]
+   pub large_arc: bool,
+   #[serde(skip_serializing_if = "is_default")]
+   pub sweep: bool,
+   pub end: [f64; 2],
+}
+
+// Center parameterization of the ellipse, with the arc angles in the frame of the ellipse axes.
+struct CenterArc {
+   center: [f64; 2],
+   radii: [f64; 2],
+   cos_phi: f64,
+   sin_phi: f64,
+   start_angle: f64,
+   delta_angle: f64,
+}
+
+impl CenterArc {
+   // Maps a point on the unit circle, given in homogeneous form, onto the ellipse.
+   #[allow(clippy::suboptimal_flops)]
+   fn map_homog(&self, u: [f64; 3]) -> [f64; 3] {
+      let x = self.radii[0] * u[0];
+      let y = self.radii[1] * u[1];
+      [
+         self.cos_phi * x - self.sin_phi * y + self.center[0] * u[2],
+         self.sin_phi * x + self.cos_phi * y + self.center[1] * u[2],
+         u[2],
+      ]
+   }
+}
+
+// CurveMath: SVG endpoint to center parameterization.
+//
+// Follows the SVG implementation notes, including the scaling-up of radii that are too small
+// for the end points.
+#[allow(clippy::suboptimal_flops)]
+#[allow(clippy::many_single_char_names)]
+fn endpoint_to_center(arc: &SvgArcRepr) -> Result<CenterArc, &'static str> {
+   let [x_1, y_1] = arc.start;
+   let [x_2, y_2] = arc.end;
+   let mut r_x = arc.radii[0].abs();
+   let mut r_y = arc.radii[1].abs();
+   let dx = 0.5 * (x_1 - x_2);
+   let dy = 0.5 * (y_1 - y_2);
+   if (dx == 0.0) && (dy == 0.0) {
+      return Err("Arc end points coincide.");
+   }
+   if (r_x == 0.0) || (r_y == 0.0) {
+      return Err("Arc has zero radius.");
+   }
+   let (sin_phi, cos_phi) = arc.x_axis_rotation.to_radians().sin_cos();
+
+   let x_p = cos_phi * dx + sin_phi * dy;
+   let y_p = -sin_phi * dx + cos_phi * dy;
+
+   let lambda = (x_p * x_p) / (r_x * r_x) + (y_p * y_p) / (r_y * r_y);
+   if lambda > 1.0 {
+      let f = lambda.sqrt();
+      r_x *= f;
+      r_y *= f;
+   }
+
+   let numerator = r_x * r_x * r_y * r_y - r_x * r_x * y_p * y_p - r_y * r_y * x_p * x_p;
+   let denominator = r_x * r_x * y_p * y_p + r_y * r_y * x_p * x_p;
+   let mut f = (numerator / denominator).max(0.0).sqrt();
+   if arc.large_arc == arc.sweep {
+      f = -f;
+   }
+   // Center in the rotated frame.
+   let [c_x, c_y] = [f * r_x * y_p / r_y, -f * r_y * x_p / r_x];
+
+   let center = [
+      cos_phi * c_x - sin_phi * c_y + 0.5 * (x_1 + x_2),
+      sin_phi * c_x + cos_phi * c_y + 0.5 * (y_1 + y_2),
+   ];
+
+   let start_angle = ((y_p - c_y) / r_y).atan2((x_p - c_x) / r_x);
+   let end_angle = ((-y_p - c_y) / r_y).atan2((-x_p - c_x) / r_x);
+   let mut delta_angle = (end_angle - start_angle).rem_euclid(TAU);
+   if !arc.sweep && (delta_angle > 0.0) {
+      delta_angle -= TAU;
+   }
+
+   Ok(CenterArc { center, radii: [r_x, r_y], cos_phi, sin_phi, start_angle, delta_angle })
+}
+
+// Converts an SVG arc into rational quadratic segments, each spanning no more than a quarter
+// of the ellipse.  The range is divided evenly between the segments.
+#[allow(clippy::missing_errors_doc)]
+#[allow(clippy::suboptimal_flops)]
+pub fn rat_quads_from_svg_arc(
+   arc: &SvgArcRepr,
+   r: [f64; 2],
+) -> Result<Vec<RatQuadHomogWeighted>, &'static str> {
+   let center_arc = endpoint_to_center(arc)?;
+   // Tiny excess to avoid splitting exact quarter arcs.
+   let num_segments = (center_arc.delta_angle.abs() / FRAC_PI_2 - 1.0e-9).ceil().max(1.0);
+   #[allow(clippy::cast_possible_truncation)]
+   let num_segments_int = (num_segments as i32).max(1);
+   let segment_angle = center_arc.delta_angle / num_segments;
+   let half_angle = 0.5 * segment_angle;
+   let w = half_angle.cos();
+   let t_scale = (r[1] - r[0]) / num_segments;
+
+   let mut ret_val =
+      Vec::<RatQuadHomogWeighted>::with_capacity(usize::try_from(num_segments_int).unwrap_or(1));
+   for i in 0..num_segments_int {
+      let theta_0 = f64::from(i).mul_add(segment_angle, center_arc.start_angle);
+      let theta_m = theta_0 + half_angle;
+      let theta_2 = theta_0 + segment_angle;
+      let p_0 = center_arc.map_homog([theta_0.cos(), theta_0.sin(), 1.0]);
+      // Middle control point is at (cos, sin) / w, with weight w.
+      let p_1 = center_arc.map_homog([theta_m.cos(), theta_m.sin(), w]);
+      let p_2 = center_arc.map_homog([theta_2.cos(), theta_2.sin(), 1.0]);
+      ret_val.push(RatQuadHomogWeighted {
+         r: [f64::from(i).mul_add(t_scale, r[0]), f64::from(i + 1).mul_add(t_scale, r[0])],
+         h: RatQuadHomog([
+            [p_0[0], 2.0 * p_1[0], p_2[0]],
+            [p_0[1], 2.0 * p_1[1], p_2[1]],
+            [p_0[2], 2.0 * p_1[2], p_2[2]],
+         ]),
+         sigma: (1.0, 1.0),
+      });
+   }
+   // Set end points exactly, as they would be in SVG.
+   ret_val[0].h.0[0][0] = arc.start[0];
+   ret_val[0].h.0[1][0] = arc.start[1];
+   let last = ret_val.len() - 1;
+   ret_val[last].h.0[0][2] = arc.end[0];
+   ret_val[last].h.0[1][2] = arc.end[1];
+
+   Ok(ret_val)
+}
+
+// CurveMath: RQC to SVG endpoint parameterization.
+//
+// Finds the ellipse from the implicit form, its center from the stationary point, and its axes
+// from the eigenvectors of the quadratic part.  The flags are set from the angles of the start,
+// middle and end of the arc about the center.
+#[allow(clippy::missing_errors_doc)]
+#[allow(clippy::suboptimal_flops)]
+#[allow(clippy::many_single_char_names)]
+pub fn svg_arc_from_rat_quad(curve: &RatQuadHomogWeighted) -> Result<SvgArcRepr, &'static str> {
+   let conic = ImplicitConic::create_from_rat_quad(curve)?;
+   let [a, b, c, d, e, _] = conic.coeffs;
+   let discriminant = 4.0 * a * c - b * b;
+   if discriminant <= 0.0 {
+      return Err("Curve is not elliptical.");
+   }
+   let center = [(b * e - 2.0 * c * d) / discriminant, (b * d - 2.0 * a * e) / discriminant];
+   let f_center = conic.value(center);
+
+   let phi = 0.5 * b.atan2(a - c);
+   let (sin_phi, cos_phi) = phi.sin_cos();
+   let lambda_1 = a * cos_phi * cos_phi + b * cos_phi * sin_phi + c * sin_phi * sin_phi;
+   let lambda_2 = a * sin_phi * sin_phi - b * cos_phi * sin_phi + c * cos_phi * cos_phi;
+   let radii_sq = [-f_center / lambda_1, -f_center / lambda_2];
+   if (radii_sq[0] <= 0.0) || (radii_sq[1] <= 0.0) {
+      return Err("Curve is imaginary ellipse.");
+   }
+   let radii = radii_sq.map(f64::sqrt);
+
+   let points =
+      curve.eval_with_bilinear(&[curve.r[0], 0.5 * (curve.r[0] + curve.r[1]), curve.r[1]]);
+   let angles: Vec<f64> = points
+      .iter()
+      .map(|p| {
+         let dx = p[0] - center[0];
+         let dy = p[1] - center[1];
+         let x_p = cos_phi * dx + sin_phi * dy;
+         let y_p = -sin_phi * dx + cos_phi * dy;
+         (y_p / radii[1]).atan2(x_p / radii[0])
+      })
+      .collect();
+   let to_end = (angles[2] - angles[0]).rem_euclid(TAU);
+   let to_middle = (angles[1] - angles[0]).rem_euclid(TAU);
+   let sweep = to_middle < to_end;
+   let extent = if sweep { to_end } else { TAU - to_end };
+
+   Ok(SvgArcRepr {
+      start: points[0],
+      radii,
+      x_axis_rotation: phi.to_degrees(),
+      large_arc: extent > PI,
+      sweep,
+      end: points[2],
+   })
+}
@@ -0,0 +1,163 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::test_helpers::time_points;
+use crate::{ManagedRatQuad, RatQuadOoeSubclassed, SpecifiedRatQuad};
+use approx::assert_abs_diff_eq;
+use zvx_base::utils::CoordSliceWrapped;
+use zvx_base::OneOfSegment;
+
+// Value of the implicit ellipse equation, which is zero on the ellipse.
+#[allow(clippy::suboptimal_flops)]
+fn ellipse_residual(p: [f64; 2], center: [f64; 2], radii: [f64; 2], rotation: f64) -> f64 {
+   let (sin_phi, cos_phi) = rotation.to_radians().sin_cos();
+   let dx = p[0] - center[0];
+   let dy = p[1] - center[1];
+   let x_p = cos_phi * dx + sin_phi * dy;
+   let y_p = -sin_phi * dx + cos_phi * dy;
+   (x_p / radii[0]).powi(2) + (y_p / radii[1]).powi(2) - 1.0
+}
+
+#[test]
+fn quarter_circle_test() {
+   let arc = SvgArcRepr {
+      start: [3.0, 1.0],
+      radii: [2.0, 2.0],
+      sweep: true,
+      end: [1.0, 3.0],
+      ..Default::default()
+   };
+   let segments = rat_quads_from_svg_arc(&arc, [0.0, 1.0]).unwrap();
+   assert_eq!(segments.len(), 1);
+
+   let points = segments[0].eval_with_bilinear(&[0.0, 0.5, 1.0]);
+   let h = std::f64::consts::SQRT_2;
+   let reference_points = [[3.0, 1.0], [1.0 + h, 1.0 + h], [1.0, 3.0]];
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&points[..]),
+      &CoordSliceWrapped::from(&reference_points[..]),
+      epsilon = 1.0e-12
+   );
+
+   // The other three flag combinations select the other center, or the complementary arc.
+   let large =
+      rat_quads_from_svg_arc(&SvgArcRepr { large_arc: true, ..arc.clone() }, [0.0, 1.0]).unwrap();
+   assert_eq!(large.len(), 3);
+   let large_mid = large[1].eval_with_bilinear(&[0.5]);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&large_mid[..]),
+      &CoordSliceWrapped::from(&[[3.0 + h, 3.0 + h]][..]),
+      epsilon = 1.0e-12
+   );
+   let reverse =
+      rat_quads_from_svg_arc(&SvgArcRepr { sweep: false, ..arc.clone() }, [0.0, 1.0]).unwrap();
+   assert_eq!(reverse.len(), 1);
+   let reverse_mid = reverse[0].eval_with_bilinear(&[0.5]);
+   assert_abs_diff_eq!(
+      &CoordSliceWrapped::from(&reverse_mid[..]),
+      &CoordSliceWrapped::from(&[[3.0 - h, 3.0 - h]][..]),
+      epsilon = 1.0e-12
+   );
+}
+
+#[test]
+fn rotated_ellipse_round_trip_test() {
+   let center = [0.5, -1.0];
+   let radii = [2.0, 1.0];
+   let rotation = 30.0;
+   let (sin_phi, cos_phi) = f64::to_radians(rotation).sin_cos();
+   let on_ellipse = |theta: f64| -> [f64; 2] {
+      let (s, c) = theta.sin_cos();
+      [
+         center[0] + cos_phi * radii[0] * c - sin_phi * radii[1] * s,
+         center[1] + sin_phi * radii[0] * c + cos_phi * radii[1] * s,
+      ]
+   };
+   let arc = SvgArcRepr {
+      start: on_ellipse(0.3),
+      radii,
+      x_axis_rotation: rotation,
+      large_arc: true,
+      sweep: false,
+      end: on_ellipse(1.2),
+   };
+
+   let managed = ManagedRatQuad::create_from_svg_arc(&arc, [0.0, 4.0], [0.0; 4]).unwrap();
+   assert_eq!(managed.len(), 4);
+   assert_eq!(managed[0].specified, SpecifiedRatQuad::SvgArc);
+   assert_abs_diff_eq!(managed[0].rq_curve.r[0], 0.0);
+   assert_abs_diff_eq!(managed[3].rq_curve.r[1], 4.0);
+
+   for (i, segment) in managed.iter().enumerate() {
+      let curve = &segment.rq_curve;
+      for p in curve.eval_with_bilinear(&time_points(curve.r, 10)) {
+         assert_abs_diff_eq!(ellipse_residual(p, center, radii, rotation), 0.0, epsilon = 1.0e-12);
+      }
+      // Segments join.
+      if i > 0 {
+         let previous_end = managed[i - 1].rq_curve.eval_with_bilinear(&[curve.r[0]]);
+         let this_start = curve.eval_with_bilinear(&[curve.r[0]]);
+         assert_abs_diff_eq!(
+            &CoordSliceWrapped::from(&previous_end[..]),
+            &CoordSliceWrapped::from(&this_start[..]),
+            epsilon = 1.0e-12
+         );
+      }
+
+      // Reverse conversion recovers the ellipse, and the (small) arc in the same direction.
+      let segment_arc = svg_arc_from_rat_quad(curve).unwrap();
+      assert!(!segment_arc.sweep);
+      assert!(!segment_arc.large_arc);
+      let round_trip = rat_quads_from_svg_arc(&segment_arc, curve.r).unwrap();
+      assert_eq!(round_trip.len(), 1);
+      let t = time_points(curve.r, 10);
+      let points = round_trip[0].eval_with_bilinear(&t);
+      let reference_points = curve.eval_with_bilinear(&t);
+      assert_abs_diff_eq!(
+         &CoordSliceWrapped::from(&points[..]),
+         &CoordSliceWrapped::from(&reference_points[..]),
+         epsilon = 1.0e-9
+      );
+   }
+
+   // Extracting elliptical subclass.
+   for segment in &managed {
+      let subclassed = RatQuadOoeSubclassed::segment_from_ordinary(&segment.rq_curve, 0.01);
+      assert!(matches!(subclassed, Ok(OneOfSegment::Arc(_))));
+   }
+}
+
+#[test]
+fn out_of_range_test() {
+   // Radii too small are scaled up, here to a semicircle.
+   let arc =
+      SvgArcRepr { start: [0.0, 0.0], radii: [1.0, 1.0], end: [4.0, 0.0], ..Default::default() };
+   let segments = rat_quads_from_svg_arc(&arc, [0.0, 1.0]).unwrap();
+   assert_eq!(segments.len(), 2);
+   for segment in &segments {
+      for p in segment.eval_with_bilinear(&time_points(segment.r, 10)) {
+         assert_abs_diff_eq!(
+            ellipse_residual(p, [2.0, 0.0], [2.0, 2.0], 0.0),
+            0.0,
+            epsilon = 1.0e-12
+         );
+      }
+   }
+
+   assert!(
+      rat_quads_from_svg_arc(&SvgArcRepr { end: [0.0, 0.0], ..arc.clone() }, [0.0, 1.0]).is_err()
+   );
+   assert!(rat_quads_from_svg_arc(&SvgArcRepr { radii: [0.0, 1.0], ..arc }, [0.0, 1.0]).is_err());
+}
@@ -0,0 +1,38 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Curves and sample points shared by the unit tests of several modules, and by benchmarks.
+
+use zvx_base::{RatQuadHomog, RatQuadHomogWeighted};
+
+#[must_use]
+#[allow(clippy::unreadable_literal)]
+pub fn weighted_example_0() -> RatQuadHomogWeighted {
+   RatQuadHomogWeighted {
+      r: [-6.0, 14.0],
+      h: RatQuadHomog([
+         [-2.946278254943949, 0.0, -3.9283710065919317],
+         [-2.946278254943949, 2.0 * 0.6944444444444453, 3.9283710065919317],
+         [1.9641855032959659, 2.0 * 1.388888888888889, 1.9641855032959659],
+      ]),
+      sigma: (2.0, 1.5),
+   }
+}
+
+// Evenly spaced time points over the range, including both ends.
+#[must_use]
+pub fn time_points(r: [f64; 2], num_segments: i32) -> Vec<f64> {
+   let scale = (r[1] - r[0]) / f64::from(num_segments);
+   (0..=num_segments).map(|i| f64::from(i).mul_add(scale, r[0])).collect()
+}
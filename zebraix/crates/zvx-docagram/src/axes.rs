@@ -19,9 +19,9 @@ use serde_default::DefaultFromSerde;
 use zvx_base::is_default;
 use zvx_base::OneOfSegment;
 use zvx_drawable::{
-   LineChoice, LinesSetSet, OneOfDrawable, PathChoices, PathCompletion, QualifiedDrawable,
-   SegmentSequence, Strokeable, TextAnchorChoice, TextAnchorHorizontal, TextAnchorVertical,
-   TextDrawable, TextOffsetChoice, TextSingle, TextSizeChoice,
+   LayerChoices, LineChoice, LinesSetSet, OneOfDrawable, PathChoices, PathCompletion,
+   QualifiedDrawable, SegmentSequence, Strokeable, TextAnchorChoice, TextAnchorHorizontal,
+   TextAnchorVertical, TextDrawable, TextOffsetChoice, TextSingle, TextSizeChoice,
 };
 
 #[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
@@ -158,7 +158,7 @@ impl AxesSpec {
       // around the edge.  This is intentional, so that the line is never clipped.
       if let Some(box_choices) = &diagram.prep.background_box {
          // Create background box.
-         let background_layer = -1000;
+         let background_layer = LayerChoices::BACKGROUND;
          let left = diagram.prep.axes_range[0]
             - (diagram.prep.axes_range[2] - diagram.prep.axes_range[0]) * diagram.prep.padding[0]
             + diagram.prep.diagram_choices.line_width / diagram.prep.canvas_layout.scale[0];
@@ -208,7 +208,7 @@ impl AxesSpec {
       let has_vert_zero = (-range[0] > x_tolerance) && (range[2] > x_tolerance);
       let has_horiz_zero = (-range[1] > y_tolerance) && (range[3] > y_tolerance);
 
-      let axes_layer = 0;
+      let axes_layer = LayerChoices::GRID;
       let mut lines_ordinary = Strokeable::<LinesSetSet> {
          path: LinesSetSet { offsets: Some(vec![[0.0, 0.0]]), ..Default::default() },
          path_choices: PathChoices {
@@ -251,7 +251,10 @@ impl AxesSpec {
                      [range[2], range[3]],
                      [range[2], range[1]],
                   ])],
-                  path_choices: PathChoices::default(),
+                  path_choices: PathChoices {
+                     color: diagram.prep.main_color_choice.clone(),
+                     ..Default::default()
+                  },
                }),
                ..Default::default()
             });
@@ -404,7 +407,7 @@ impl AxesSpec {
          }
 
          // Change layer to depth.
-         let axes_layer = 0;
+         let axes_layer = LayerChoices::GRID;
          if !horizontal_numbering.texts.is_empty() {
             let qualified_drawable = QualifiedDrawable {
                layer: axes_layer,
@@ -413,7 +416,7 @@ impl AxesSpec {
             };
             diagram.drawables.push(qualified_drawable);
          }
-         let axes_layer = 0;
+         let axes_layer = LayerChoices::GRID;
          if !vertical_numbering.texts.is_empty() {
             let qualified_drawable = QualifiedDrawable {
                layer: axes_layer,
@@ -14,9 +14,13 @@
 
 use serde::{Deserialize, Serialize};
 use serde_default::DefaultFromSerde;
+use std::fmt::Write as _;
 use zvx_base::is_default;
-use zvx_drawable::choices::{CanvasLayout, DiagramChoices};
-use zvx_drawable::{ColorChoice, PathChoices, QualifiedDrawable};
+use zvx_drawable::choices::{CanvasLayout, DiagramChoices, LayerChoices};
+use zvx_drawable::estimator::displayed_text;
+use zvx_drawable::{
+   ColorChoice, ColorRole, FontChoices, OneOfDrawable, PathChoices, QualifiedDrawable, ThemeChoice,
+};
 
 #[derive(Serialize, Deserialize, Debug, Default, Copy, Clone)]
 pub enum SizingScheme {
@@ -72,6 +76,10 @@ pub struct SpartanDiagram {
    )]
    pub base_line_width: f64,
 
+   // Base font, which text drawables modify.
+   #[serde(skip_serializing_if = "is_default")]
+   pub base_font_choices: FontChoices,
+
    #[serde(skip_serializing_if = "is_default")]
    pub base_color_choice: ColorChoice,
 
@@ -81,6 +89,10 @@ pub struct SpartanDiagram {
    #[serde(skip_serializing_if = "is_default")]
    pub text_color_choice: ColorChoice,
 
+   // Palette through which colour roles, and unset colours, are resolved.
+   #[serde(skip_serializing_if = "is_default")]
+   pub theme: ThemeChoice,
+
    // Scaling of 1-D annotations, such as grid line width vs normal.
    #[serde(
       skip_serializing_if = "SpartanDiagram::is_default_annotation_linear_scale",
@@ -123,6 +135,21 @@ pub struct SpartanDiagram {
 
    #[serde(skip_serializing_if = "is_default")]
    pub background_box: Option<PathChoices>,
+
+   // Named layers, with visibility and opacity.  Generated axes are on the standard background
+   // and grid layers.
+   #[serde(skip_serializing_if = "is_default")]
+   pub layer_choices: LayerChoices,
+
+   // Accessible title and description, written by backends that support them.
+   #[serde(skip_serializing_if = "is_default")]
+   pub title: Option<String>,
+   #[serde(skip_serializing_if = "is_default")]
+   pub description: Option<String>,
+   // Prefix for the ids of document elements, such as the title and description, so that they
+   // are unique when several diagrams are inlined in one page.  The default prefix is "diagram".
+   #[serde(skip_serializing_if = "is_default")]
+   pub document_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, DefaultFromSerde)]
@@ -135,6 +162,82 @@ pub struct DrawableDiagram {
    pub drawables: Vec<QualifiedDrawable>,
 }
 
+impl DrawableDiagram {
+   // Readable text fallback for the diagram: the title and description, the axes range, and
+   // labels in drawing order.  A group with alternative text is listed by that text alone, and
+   // an image is listed by its alternative text, if any.
+   // Top-level drawables on hidden layers are omitted.
+   #[must_use]
+   pub fn text_summary(&self) -> String {
+      let diagram_choices = &self.prep.diagram_choices;
+      let mut summary = String::new();
+      for text in [&diagram_choices.title, &diagram_choices.description].into_iter().flatten() {
+         let _ = writeln!(summary, "{text}");
+      }
+      let [x_min, y_min, x_max, y_max] = self.prep.axes_range;
+      let _ =
+         writeln!(summary, "Axes range: x from {x_min} to {x_max}, y from {y_min} to {y_max}.");
+
+      let mut labels = Vec::new();
+      let order = diagram_choices.layer_choices.draw_order(&self.drawables);
+      Self::collect_labels(&self.drawables, &order, &mut labels);
+      if !labels.is_empty() {
+         summary.push_str("Labels:\n");
+         for label in labels {
+            let _ = writeln!(summary, "* {label}");
+         }
+      }
+      summary
+   }
+
+   // Labels are collapsed onto a single line.
+   fn push_label(label: &str, labels: &mut Vec<String>) {
+      let label = label.split_whitespace().collect::<Vec<_>>().join(" ");
+      if !label.is_empty() {
+         labels.push(label);
+      }
+   }
+
+   // Labels of the drawables, taken in the given order.  Nested drawables are taken in layer
+   // order, since layer visibility only applies at the top level.
+   fn collect_labels(drawables: &[QualifiedDrawable], order: &[usize], labels: &mut Vec<String>) {
+      for &i in order {
+         match &drawables[i].drawable {
+            OneOfDrawable::Text(drawable) => {
+               for single_text in &drawable.texts {
+                  Self::push_label(&displayed_text(single_text), labels);
+               }
+            }
+            OneOfDrawable::TextPath(drawable) => Self::push_label(&drawable.content, labels),
+            OneOfDrawable::Group(drawable) => {
+               if let Some(alt_text) = &drawable.alt_text {
+                  Self::push_label(alt_text, labels);
+               } else {
+                  Self::collect_labels(
+                     &drawable.drawables,
+                     &LayerChoices::sort_order(&drawable.drawables),
+                     labels,
+                  );
+               }
+            }
+            OneOfDrawable::Clipped(drawable) => {
+               Self::collect_labels(
+                  &drawable.drawables,
+                  &LayerChoices::sort_order(&drawable.drawables),
+                  labels,
+               );
+            }
+            OneOfDrawable::Image(drawable) => {
+               if let Some(alt_text) = &drawable.alt_text {
+                  Self::push_label(alt_text, labels);
+               }
+            }
+            _ => {}
+         }
+      }
+   }
+}
+
 impl SpartanDiagram {
    #[must_use]
    pub fn new() -> Self {
@@ -362,23 +465,34 @@ impl SpartanDiagram {
          self.base_font_size * self.annotation_offset[0];
       preparation.diagram_choices.annotation_offset_absolute[1] =
          self.base_font_size * self.annotation_offset[1];
+      preparation.diagram_choices.font_choices.clone_from(&self.base_font_choices);
       preparation.diagram_choices.annotation_linear_scale = self.annotation_linear_scale;
       preparation.diagram_choices.annotation_area_scale = self.annotation_area_scale;
 
-      preparation.main_color_choice = self.base_color_choice.clone();
-      if self.light_color_choice == ColorChoice::default() {
-         preparation.light_color_choice = self.base_color_choice.clone();
-      } else {
-         preparation.light_color_choice = self.light_color_choice.clone();
-      }
-      if self.text_color_choice == ColorChoice::default() {
-         preparation.text_color_choice = self.base_color_choice.clone();
-      } else {
-         preparation.text_color_choice = self.text_color_choice.clone();
-      }
+      preparation.diagram_choices.palette = self.theme.to_palette();
+
+      // Unset colours fall back to the base colour, and then to palette roles.
+      let unset = ColorChoice::default();
+      let with_fallback = |choice: &ColorChoice, role: ColorRole| -> ColorChoice {
+         if *choice != unset {
+            choice.clone()
+         } else if self.base_color_choice != unset {
+            self.base_color_choice.clone()
+         } else {
+            ColorChoice::Role(role)
+         }
+      };
+      preparation.main_color_choice = with_fallback(&self.base_color_choice, ColorRole::Axis);
+      preparation.light_color_choice = with_fallback(&self.light_color_choice, ColorRole::Grid);
+      preparation.text_color_choice = with_fallback(&self.text_color_choice, ColorRole::Text);
 
       preparation.background_box.clone_from(&self.background_box);
 
+      preparation.diagram_choices.layer_choices.clone_from(&self.layer_choices);
+      preparation.diagram_choices.title.clone_from(&self.title);
+      preparation.diagram_choices.description.clone_from(&self.description);
+      preparation.diagram_choices.document_id.clone_from(&self.document_id);
+
       preparation
    }
 }
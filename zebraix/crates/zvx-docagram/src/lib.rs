@@ -14,6 +14,8 @@
 
 pub mod axes;
 pub mod diagram;
+pub mod validation;
 
 pub use crate::axes::{AxesSpec, AxesStyle, AxisNumbering};
 pub use crate::diagram::SizingScheme;
+pub use crate::validation::{Diagnostic, DiagnosticKind, DiagnosticSeverity};
@@ -0,0 +1,426 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Validation of drawables, so that problems can be reported before rendering is attempted.
+//
+// Errors are problems that renderers cannot handle, such as non-finite coordinates, empty
+// polylines, invalid dash arrays, hatching without positive spacing, excessive miter limits,
+// and image files that do not exist or image data that is not PNG.
+// Warnings are for content that renders, but probably not as intended, such as empty sets,
+// missing text, and geometry entirely outside the canvas.
+
+#[cfg(test)]
+mod tests;
+
+use crate::diagram::DrawableDiagram;
+use std::fmt;
+use std::path::Path;
+use zvx_base::OneOfSegment;
+use zvx_curves::segment_samples;
+use zvx_drawable::kinds::MAX_MITER_LIMIT;
+use zvx_drawable::{
+   ClipRegion, FillPattern, ImageSource, LineChoice, OneOfDrawable, PathChoices, PointChoice,
+   QualifiedDrawable,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+   Warning,
+   Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+   NonFiniteCoordinate,
+   // Zero-length or zero-size geometry, or a polyline with too few points.
+   DegenerateGeometry,
+   EmptySet,
+   MissingText,
+   MissingImage,
+   OutsideCanvas,
+   // Line or fill style that renderers cannot draw as given, such as negative dash lengths or
+   // zero hatch spacing.
+   InvalidStyle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+   // Index of the drawable, followed by indices within any enclosing groups and clipped
+   // drawables.
+   pub location: Vec<usize>,
+   pub severity: DiagnosticSeverity,
+   pub kind: DiagnosticKind,
+   pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      let location: Vec<String> = self.location.iter().map(ToString::to_string).collect();
+      let severity = match self.severity {
+         DiagnosticSeverity::Warning => "warning",
+         DiagnosticSeverity::Error => "error",
+      };
+      write!(f, "{severity} at drawable {}: {}", location.join("."), self.message)
+   }
+}
+
+// Affine transform from local to diagram coordinates, as [xx, yx, xy, yy, x0, y0].
+type Affine = [f64; 6];
+
+const IDENTITY: Affine = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+const CURVE_SAMPLES: usize = 16;
+
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+
+const fn apply(affine: &Affine, p: [f64; 2]) -> [f64; 2] {
+   [
+      affine[0].mul_add(p[0], affine[2].mul_add(p[1], affine[4])),
+      affine[1].mul_add(p[0], affine[3].mul_add(p[1], affine[5])),
+   ]
+}
+
+// Local transform, applied before the outer.
+fn compose(local: &Affine, outer: &Affine) -> Affine {
+   let [x0, y0] = apply(outer, [local[4], local[5]]);
+   [
+      outer[0].mul_add(local[0], outer[2] * local[1]),
+      outer[1].mul_add(local[0], outer[3] * local[1]),
+      outer[0].mul_add(local[2], outer[2] * local[3]),
+      outer[1].mul_add(local[2], outer[3] * local[3]),
+      x0,
+      y0,
+   ]
+}
+
+fn all_finite(points: &[[f64; 2]]) -> bool {
+   points.iter().all(|p| p[0].is_finite() && p[1].is_finite())
+}
+
+// Exact comparison, since only exactly coincident points give zero-length geometry.
+#[allow(clippy::float_cmp)]
+fn coincident(p: [f64; 2], q: [f64; 2]) -> bool {
+   p == q
+}
+
+fn all_coincident(points: &[[f64; 2]]) -> bool {
+   points.windows(2).all(|w| coincident(w[0], w[1]))
+}
+
+struct Validator {
+   // Canvas extent in diagram coordinates, as [left, bottom, right, top].
+   canvas_extent: [f64; 4],
+   location: Vec<usize>,
+   diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator {
+   fn report(&mut self, severity: DiagnosticSeverity, kind: DiagnosticKind, message: &str) {
+      self.diagnostics.push(Diagnostic {
+         location: self.location.clone(),
+         severity,
+         kind,
+         message: message.to_string(),
+      });
+   }
+
+   fn warn(&mut self, kind: DiagnosticKind, message: &str) {
+      self.report(DiagnosticSeverity::Warning, kind, message);
+   }
+
+   fn error(&mut self, kind: DiagnosticKind, message: &str) {
+      self.report(DiagnosticSeverity::Error, kind, message);
+   }
+
+   // Checks that the points are finite, and that they are not all outside the canvas.  Returns
+   // false if not finite.
+   fn check_points(&mut self, points: &[[f64; 2]], affine: &Affine) -> bool {
+      if !all_finite(points) {
+         self.error(DiagnosticKind::NonFiniteCoordinate, "Coordinates are NaN or infinite.");
+         return false;
+      }
+      if points.is_empty() {
+         return true;
+      }
+      let mut bounds = [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY];
+      for point in points {
+         let [x, y] = apply(affine, *point);
+         bounds = [bounds[0].min(x), bounds[1].min(y), bounds[2].max(x), bounds[3].max(y)];
+      }
+      let extent = &self.canvas_extent;
+      if bounds[2] < extent[0]
+         || bounds[0] > extent[2]
+         || bounds[3] < extent[1]
+         || bounds[1] > extent[3]
+      {
+         self.warn(DiagnosticKind::OutsideCanvas, "Geometry is entirely outside the canvas.");
+      }
+      true
+   }
+
+   // Checks a sequence of segments, together as a path.
+   fn check_segments(&mut self, segments: &[OneOfSegment], affine: &Affine) {
+      if segments.is_empty() {
+         self.warn(DiagnosticKind::EmptySet, "Path has no segments.");
+         return;
+      }
+      let mut points = Vec::new();
+      for segment in segments {
+         let samples = segment_samples(segment, CURVE_SAMPLES);
+         match segment {
+            OneOfSegment::Polyline(locations) if locations.is_empty() => {
+               self.error(DiagnosticKind::DegenerateGeometry, "Polyline has no points.");
+            }
+            OneOfSegment::Polyline(locations) if locations.len() == 1 => {
+               self.warn(DiagnosticKind::DegenerateGeometry, "Polyline has only one point.");
+            }
+            OneOfSegment::Neither => {
+               self.warn(DiagnosticKind::DegenerateGeometry, "Segment is unset.");
+            }
+            _ => {
+               if all_finite(&samples) && all_coincident(&samples) {
+                  self.warn(DiagnosticKind::DegenerateGeometry, "Segment has zero length.");
+               }
+            }
+         }
+         points.extend(samples);
+      }
+      self.check_points(&points, affine);
+   }
+
+   fn check_path_choices(&mut self, path_choices: &PathChoices) {
+      if let LineChoice::Custom(style) = &path_choices.line_choice {
+         if !style.has_valid_dashes() {
+            self
+               .error(DiagnosticKind::InvalidStyle, "Dashes are negative, non-finite or all zero.");
+         }
+      }
+      if path_choices.miter_limit.is_nan() || path_choices.miter_limit > MAX_MITER_LIMIT {
+         self.error(DiagnosticKind::InvalidStyle, "Miter limit is NaN or above the maximum.");
+      }
+      if let FillPattern::Hatch(hatch_choices) = &path_choices.fill_choices.pattern {
+         if !hatch_choices.is_valid() {
+            self.error(
+               DiagnosticKind::InvalidStyle,
+               "Hatch spacing is not positive, or hatch angle is not finite.",
+            );
+         }
+      }
+   }
+
+   #[allow(clippy::float_cmp)]
+   fn check_rectangle(&mut self, rectangle: &[f64; 4], affine: &Affine, what: &str) {
+      let corners = [[rectangle[0], rectangle[1]], [rectangle[2], rectangle[3]]];
+      if self.check_points(&corners, affine)
+         && (rectangle[0] == rectangle[2] || rectangle[1] == rectangle[3])
+      {
+         self.warn(DiagnosticKind::DegenerateGeometry, &format!("{what} has zero area."));
+      }
+   }
+
+   fn check_drawables(&mut self, drawables: &[QualifiedDrawable], affine: &Affine) {
+      for (i, qualified) in drawables.iter().enumerate() {
+         self.location.push(i);
+         self.check_drawable(&qualified.drawable, affine);
+         self.location.pop();
+      }
+   }
+
+   #[allow(clippy::too_many_lines)]
+   fn check_drawable(&mut self, drawable: &OneOfDrawable, affine: &Affine) {
+      match drawable {
+         OneOfDrawable::Arc(d) => self.check_path_choices(&d.path_choices),
+         OneOfDrawable::Cubic(d) => self.check_path_choices(&d.path_choices),
+         OneOfDrawable::Hyperbolic(d) => self.check_path_choices(&d.path_choices),
+         OneOfDrawable::Polyline(d) => self.check_path_choices(&d.path_choices),
+         OneOfDrawable::RatCubic(d) => self.check_path_choices(&d.path_choices),
+         OneOfDrawable::Lines(d) => self.check_path_choices(&d.path_choices),
+         OneOfDrawable::Circles(d) => self.check_path_choices(&d.path_choices),
+         OneOfDrawable::SegmentSequence(d) => self.check_path_choices(&d.path_choices),
+         OneOfDrawable::Points(d) => self.check_path_choices(&d.custom_marker.path_choices),
+         OneOfDrawable::Group(d) => self.check_path_choices(&d.style),
+         _ => {}
+      }
+
+      match drawable {
+         OneOfDrawable::Neither => {}
+         OneOfDrawable::Arc(d) => {
+            self.check_segments(&[OneOfSegment::Arc(d.path.clone())], affine);
+         }
+         OneOfDrawable::Cubic(d) => {
+            self.check_segments(&[OneOfSegment::Cubic(d.path.clone())], affine);
+         }
+         OneOfDrawable::Hyperbolic(d) => {
+            self.check_segments(&[OneOfSegment::Hyperbolic(d.path.clone())], affine);
+         }
+         OneOfDrawable::Polyline(d) => {
+            self.check_segments(&[OneOfSegment::Polyline(d.path.clone())], affine);
+         }
+         OneOfDrawable::RatCubic(d) => {
+            self.check_segments(&[OneOfSegment::RatCubic(d.path.clone())], affine);
+         }
+         OneOfDrawable::SegmentSequence(d) => self.check_segments(&d.segments, affine),
+         OneOfDrawable::Lines(d) => {
+            if d.path.coords.is_empty() {
+               self.warn(DiagnosticKind::EmptySet, "Lines set has no lines.");
+               return;
+            }
+            let offsets = match &d.path.offsets {
+               Some(offsets) if !offsets.is_empty() => offsets.clone(),
+               _ => vec![[0.0, 0.0]],
+            };
+            let mut points = Vec::new();
+            for (start, end) in &d.path.coords {
+               for offset in &offsets {
+                  points.push([start[0] + offset[0], start[1] + offset[1]]);
+                  points.push([end[0] + offset[0], end[1] + offset[1]]);
+               }
+            }
+            if self.check_points(&points, affine)
+               && d.path.coords.iter().any(|(s, e)| coincident(*s, *e))
+            {
+               self.warn(DiagnosticKind::DegenerateGeometry, "Line has zero length.");
+            }
+         }
+         OneOfDrawable::Circles(d) => {
+            if d.path.centers.is_empty() {
+               self.warn(DiagnosticKind::EmptySet, "Circles set has no centers.");
+               return;
+            }
+            let radius = d.path.radius;
+            if !radius.is_finite() {
+               self.error(DiagnosticKind::NonFiniteCoordinate, "Radius is NaN or infinite.");
+               return;
+            }
+            if radius <= 0.0 {
+               self.warn(DiagnosticKind::DegenerateGeometry, "Circles have zero size.");
+            }
+            let points: Vec<[f64; 2]> = d
+               .path
+               .centers
+               .iter()
+               .flat_map(|c| [[c[0] - radius, c[1] - radius], [c[0] + radius, c[1] + radius]])
+               .collect();
+            self.check_points(&points, affine);
+         }
+         OneOfDrawable::Points(d) => {
+            if d.centers.is_empty() {
+               self.warn(DiagnosticKind::EmptySet, "Points set has no centers.");
+               return;
+            }
+            if !d.size.is_finite() {
+               self.error(DiagnosticKind::NonFiniteCoordinate, "Point size is NaN or infinite.");
+               return;
+            }
+            if d.size <= 0.0 {
+               self.warn(DiagnosticKind::DegenerateGeometry, "Points have zero size.");
+            }
+            if d.point_choice == PointChoice::Custom && d.custom_marker.segments.is_empty() {
+               self.warn(DiagnosticKind::EmptySet, "Custom point marker has no segments.");
+            }
+            self.check_points(&d.centers, affine);
+         }
+         OneOfDrawable::Text(d) => {
+            if d.texts.is_empty() {
+               self.warn(DiagnosticKind::EmptySet, "Text drawable has no texts.");
+               return;
+            }
+            if d.texts.iter().any(|t| t.content.trim().is_empty()) {
+               self.warn(DiagnosticKind::MissingText, "Text has no content.");
+            }
+            let locations: Vec<[f64; 2]> = d.texts.iter().map(|t| t.location).collect();
+            self.check_points(&locations, affine);
+         }
+         OneOfDrawable::TextPath(d) => {
+            if d.content.trim().is_empty() {
+               self.warn(DiagnosticKind::MissingText, "Text along path has no content.");
+            }
+            self.check_segments(&d.segments, affine);
+         }
+         OneOfDrawable::Image(d) => {
+            match &d.source {
+               ImageSource::Unset => {
+                  self.warn(DiagnosticKind::MissingImage, "Image has no source.");
+               }
+               ImageSource::PngFile(path) => {
+                  if !Path::new(path).is_file() {
+                     self.error(DiagnosticKind::MissingImage, "Image file does not exist.");
+                  }
+               }
+               ImageSource::PngData(data) => {
+                  if !data.starts_with(PNG_SIGNATURE) {
+                     self.error(DiagnosticKind::MissingImage, "Image data is not PNG.");
+                  }
+               }
+            }
+            self.check_rectangle(&d.rectangle, affine, "Image");
+         }
+         OneOfDrawable::Clipped(d) => {
+            match &d.clip {
+               ClipRegion::Unclipped => {}
+               ClipRegion::Rectangle(rectangle) => {
+                  self.check_rectangle(rectangle, affine, "Clip rectangle");
+               }
+               ClipRegion::Path(segments) => self.check_segments(segments, affine),
+            }
+            if d.drawables.is_empty() {
+               self.warn(DiagnosticKind::EmptySet, "Clipped drawables are empty.");
+            }
+            self.check_drawables(&d.drawables, affine);
+         }
+         OneOfDrawable::Group(d) => {
+            let local = [
+               d.transform[0],
+               d.transform[1],
+               d.transform[2],
+               d.transform[3],
+               d.offset[0],
+               d.offset[1],
+            ];
+            if !local.iter().all(|v| v.is_finite()) {
+               self.error(
+                  DiagnosticKind::NonFiniteCoordinate,
+                  "Group transform is NaN or infinite.",
+               );
+               return;
+            }
+            if d.transform[0].mul_add(d.transform[3], -d.transform[1] * d.transform[2]) == 0.0 {
+               self.warn(DiagnosticKind::DegenerateGeometry, "Group transform is singular.");
+            }
+            if d.drawables.is_empty() {
+               self.warn(DiagnosticKind::EmptySet, "Group is empty.");
+            }
+            self.check_drawables(&d.drawables, &compose(&local, affine));
+         }
+      }
+   }
+}
+
+impl DrawableDiagram {
+   // Diagnostics for the drawables, in drawable order.
+   #[must_use]
+   pub fn validate(&self) -> Vec<Diagnostic> {
+      let layout = &self.prep.canvas_layout;
+      let canvas_extent = [
+         -layout.offset[0] / layout.scale[0],
+         -layout.offset[1] / layout.scale[1],
+         (layout.canvas_size[0] - layout.offset[0]) / layout.scale[0],
+         (layout.canvas_size[1] - layout.offset[1]) / layout.scale[1],
+      ];
+      let mut validator =
+         Validator { canvas_extent, location: Vec::new(), diagnostics: Vec::new() };
+      validator.check_drawables(&self.drawables, &IDENTITY);
+      validator.diagnostics
+   }
+}
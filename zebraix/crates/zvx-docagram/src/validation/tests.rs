@@ -0,0 +1,306 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::diagram::SpartanPreparation;
+use zvx_base::ArcPath;
+use zvx_drawable::{
+   CirclesSet, ClippedDrawables, ColorChoice, FillChoices, GroupDrawable, HatchChoices,
+   ImageDrawable, LineStyle, LinesSetSet, PointsDrawable, SegmentSequence, Strokeable,
+   TextDrawable, TextPathDrawable, TextSingle,
+};
+
+use DiagnosticKind::{
+   DegenerateGeometry, EmptySet, InvalidStyle, MissingImage, MissingText, NonFiniteCoordinate,
+   OutsideCanvas,
+};
+use DiagnosticSeverity::{Error, Warning};
+
+type Found = (Vec<usize>, DiagnosticSeverity, DiagnosticKind);
+
+// Canvas extent is [-10, -5, 10, 5] in diagram coordinates.
+fn validate(drawables: Vec<OneOfDrawable>) -> Vec<Found> {
+   let mut diagram = DrawableDiagram {
+      prep: SpartanPreparation::default(),
+      drawables: drawables
+         .into_iter()
+         .map(|drawable| QualifiedDrawable { drawable, ..Default::default() })
+         .collect(),
+   };
+   diagram.prep.canvas_layout.scale = [10.0, 10.0];
+   diagram.prep.canvas_layout.offset = [100.0, 50.0];
+   diagram.prep.canvas_layout.canvas_size = [200.0, 100.0];
+   diagram.validate().into_iter().map(|d| (d.location, d.severity, d.kind)).collect()
+}
+
+fn polyline(path: Vec<[f64; 2]>) -> OneOfDrawable {
+   OneOfDrawable::Polyline(Strokeable { path, ..Default::default() })
+}
+
+fn styled_polyline(path_choices: PathChoices) -> OneOfDrawable {
+   OneOfDrawable::Polyline(Strokeable { path: vec![[0.0, 0.0], [1.0, 1.0]], path_choices })
+}
+
+fn dashed(dashes: Vec<f64>) -> PathChoices {
+   PathChoices {
+      line_choice: LineChoice::Custom(LineStyle { dashes, ..Default::default() }),
+      ..Default::default()
+   }
+}
+
+fn hatched(hatch_choices: HatchChoices) -> PathChoices {
+   PathChoices {
+      fill_choices: FillChoices {
+         opacity: 1.0,
+         pattern: FillPattern::Hatch(hatch_choices),
+         ..Default::default()
+      },
+      ..Default::default()
+   }
+}
+
+fn text(content: &str, location: [f64; 2]) -> OneOfDrawable {
+   OneOfDrawable::Text(TextDrawable {
+      texts: vec![TextSingle { content: content.to_string(), location, ..Default::default() }],
+      ..Default::default()
+   })
+}
+
+#[test]
+fn valid_drawables_test() {
+   let found = validate(vec![
+      polyline(vec![[-1.0, -1.0], [1.0, 1.0]]),
+      OneOfDrawable::Circles(Strokeable {
+         path: CirclesSet { radius: 0.5, centers: vec![[2.0, 2.0]] },
+         ..Default::default()
+      }),
+      text("Label", [0.0, 0.0]),
+      styled_polyline(dashed(vec![2.0, 0.0, 1.0])),
+      styled_polyline(hatched(HatchChoices { spacing: 0.5, ..Default::default() })),
+      styled_polyline(PathChoices { miter_limit: MAX_MITER_LIMIT, ..Default::default() }),
+   ]);
+   assert_eq!(found, vec![]);
+}
+
+#[test]
+fn geometry_test() {
+   let found = validate(vec![
+      polyline(vec![[0.0, f64::NAN], [1.0, 1.0]]),
+      polyline(vec![]),
+      polyline(vec![[0.0, 0.0]]),
+      polyline(vec![[20.0, 0.0], [30.0, 1.0]]),
+      OneOfDrawable::Arc(Strokeable {
+         path: ArcPath {
+            angle_range: [0.0, 0.0],
+            center: [0.0, 0.0],
+            transform: [1.0, 0.0, 0.0, 1.0],
+         },
+         ..Default::default()
+      }),
+      OneOfDrawable::Lines(Strokeable {
+         path: LinesSetSet { coords: vec![([1.0, 1.0], [1.0, 1.0])], offsets: None },
+         ..Default::default()
+      }),
+      OneOfDrawable::Circles(Strokeable {
+         path: CirclesSet { radius: f64::INFINITY, centers: vec![[0.0, 0.0]] },
+         ..Default::default()
+      }),
+      OneOfDrawable::Circles(Strokeable {
+         path: CirclesSet { radius: 0.0, centers: vec![[0.0, 0.0]] },
+         ..Default::default()
+      }),
+   ]);
+   assert_eq!(
+      found,
+      vec![
+         (vec![0], Error, NonFiniteCoordinate),
+         (vec![1], Error, DegenerateGeometry),
+         (vec![2], Warning, DegenerateGeometry),
+         (vec![3], Warning, OutsideCanvas),
+         (vec![4], Warning, DegenerateGeometry),
+         (vec![5], Warning, DegenerateGeometry),
+         (vec![6], Error, NonFiniteCoordinate),
+         (vec![7], Warning, DegenerateGeometry),
+      ]
+   );
+}
+
+#[test]
+fn empty_and_missing_test() {
+   let found = validate(vec![
+      OneOfDrawable::SegmentSequence(SegmentSequence::default()),
+      OneOfDrawable::Lines(Strokeable::default()),
+      OneOfDrawable::Circles(Strokeable::default()),
+      OneOfDrawable::Points(PointsDrawable::default()),
+      OneOfDrawable::Text(TextDrawable::default()),
+      text(" ", [0.0, 0.0]),
+      OneOfDrawable::TextPath(TextPathDrawable {
+         segments: vec![OneOfSegment::Polyline(vec![[0.0, 0.0], [1.0, 0.0]])],
+         ..Default::default()
+      }),
+      OneOfDrawable::Image(ImageDrawable { rectangle: [0.0, 0.0, 1.0, 1.0], ..Default::default() }),
+      OneOfDrawable::Image(ImageDrawable {
+         source: ImageSource::PngData(b"\x89PNG\r\n\x1a\n".to_vec()),
+         rectangle: [0.0, 0.0, 0.0, 1.0],
+         ..Default::default()
+      }),
+      OneOfDrawable::Image(ImageDrawable {
+         source: ImageSource::PngFile("missing.png".to_string()),
+         rectangle: [0.0, 0.0, 1.0, 1.0],
+         ..Default::default()
+      }),
+      OneOfDrawable::Image(ImageDrawable {
+         source: ImageSource::PngData(b"Not a PNG image.".to_vec()),
+         rectangle: [0.0, 0.0, 1.0, 1.0],
+         ..Default::default()
+      }),
+   ]);
+   assert_eq!(
+      found,
+      vec![
+         (vec![0], Warning, EmptySet),
+         (vec![1], Warning, EmptySet),
+         (vec![2], Warning, EmptySet),
+         (vec![3], Warning, EmptySet),
+         (vec![4], Warning, EmptySet),
+         (vec![5], Warning, MissingText),
+         (vec![6], Warning, MissingText),
+         (vec![7], Warning, MissingImage),
+         (vec![8], Warning, DegenerateGeometry),
+         (vec![9], Error, MissingImage),
+         (vec![10], Error, MissingImage),
+      ]
+   );
+}
+
+#[test]
+fn points_test() {
+   let points =
+      |size: f64| PointsDrawable { centers: vec![[0.0, 0.0]], size, ..Default::default() };
+   let found = validate(vec![
+      OneOfDrawable::Points(points(f64::NAN)),
+      OneOfDrawable::Points(points(0.0)),
+      OneOfDrawable::Points(PointsDrawable { point_choice: PointChoice::Custom, ..points(1.0) }),
+      OneOfDrawable::Points(PointsDrawable {
+         point_choice: PointChoice::Custom,
+         custom_marker: SegmentSequence {
+            path_choices: dashed(vec![1.0, -1.0]),
+            segments: vec![OneOfSegment::Polyline(vec![[0.0, 1.0], [1.0, 0.0]])],
+            ..Default::default()
+         },
+         ..points(1.0)
+      }),
+   ]);
+   assert_eq!(
+      found,
+      vec![
+         (vec![0], Error, NonFiniteCoordinate),
+         (vec![1], Warning, DegenerateGeometry),
+         (vec![2], Warning, EmptySet),
+         (vec![3], Error, InvalidStyle),
+      ]
+   );
+}
+
+#[test]
+fn style_test() {
+   let found = validate(vec![
+      styled_polyline(dashed(vec![2.0, -1.0])),
+      styled_polyline(dashed(vec![0.0, 0.0])),
+      styled_polyline(dashed(vec![2.0, f64::INFINITY])),
+      styled_polyline(hatched(HatchChoices { spacing: 0.0, ..Default::default() })),
+      styled_polyline(hatched(HatchChoices { spacing: f64::NAN, ..Default::default() })),
+      styled_polyline(hatched(HatchChoices { angle: f64::INFINITY, ..Default::default() })),
+      styled_polyline(PathChoices { miter_limit: 900_000.0, ..Default::default() }),
+      styled_polyline(PathChoices { miter_limit: f64::NAN, ..Default::default() }),
+   ]);
+   let expected: Vec<Found> = (0..8).map(|i| (vec![i], Error, InvalidStyle)).collect();
+   assert_eq!(found, expected);
+}
+
+#[test]
+fn nesting_test() {
+   let group = |transform: [f64; 4], offset: [f64; 2], drawables: Vec<OneOfDrawable>| {
+      OneOfDrawable::Group(GroupDrawable {
+         transform,
+         offset,
+         drawables: drawables
+            .into_iter()
+            .map(|drawable| QualifiedDrawable { drawable, ..Default::default() })
+            .collect(),
+         ..Default::default()
+      })
+   };
+   let identity = [1.0, 0.0, 0.0, 1.0];
+   let found = validate(vec![
+      // Child is placed by the group offset, and so is outside the canvas.
+      group(identity, [50.0, 0.0], vec![text("Label", [0.0, 0.0])]),
+      // Child is placed by the group transform, and so is inside the canvas.
+      group([0.1, 0.0, 0.0, 0.1], [0.0, 0.0], vec![text("Label", [50.0, 0.0])]),
+      group([0.0, 0.0, 0.0, 1.0], [0.0, 0.0], vec![polyline(vec![[0.0, 0.0]])]),
+      group([f64::NAN, 0.0, 0.0, 1.0], [0.0, 0.0], vec![polyline(vec![])]),
+      group(identity, [0.0, 0.0], vec![]),
+      OneOfDrawable::Group(GroupDrawable {
+         style: dashed(vec![-1.0]),
+         drawables: vec![QualifiedDrawable {
+            drawable: polyline(vec![[0.0, 0.0], [1.0, 1.0]]),
+            ..Default::default()
+         }],
+         ..Default::default()
+      }),
+      OneOfDrawable::Clipped(ClippedDrawables {
+         clip: ClipRegion::Rectangle([0.0, 0.0, 1.0, 1.0]),
+         drawables: vec![QualifiedDrawable {
+            drawable: group(identity, [0.0, 0.0], vec![polyline(vec![])]),
+            ..Default::default()
+         }],
+      }),
+      OneOfDrawable::Clipped(ClippedDrawables::default()),
+   ]);
+   assert_eq!(
+      found,
+      vec![
+         (vec![0, 0], Warning, OutsideCanvas),
+         (vec![2], Warning, DegenerateGeometry),
+         (vec![2, 0], Warning, DegenerateGeometry),
+         (vec![3], Error, NonFiniteCoordinate),
+         (vec![4], Warning, EmptySet),
+         (vec![5], Error, InvalidStyle),
+         (vec![6, 0, 0], Error, DegenerateGeometry),
+         (vec![7], Warning, EmptySet),
+      ]
+   );
+}
+
+#[test]
+fn message_test() {
+   let diagram = DrawableDiagram {
+      prep: SpartanPreparation::default(),
+      drawables: vec![QualifiedDrawable {
+         drawable: OneOfDrawable::Group(GroupDrawable {
+            drawables: vec![QualifiedDrawable {
+               drawable: OneOfDrawable::Points(PointsDrawable {
+                  color_choice: ColorChoice::RED,
+                  ..Default::default()
+               }),
+               ..Default::default()
+            }],
+            ..Default::default()
+         }),
+         ..Default::default()
+      }],
+   };
+   let messages: Vec<String> = diagram.validate().iter().map(ToString::to_string).collect();
+   assert_eq!(messages, vec!["warning at drawable 0.0: Points set has no centers."]);
+}
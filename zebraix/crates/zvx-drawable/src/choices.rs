@@ -12,7 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod tests;
+
+use crate::kinds::QualifiedDrawable;
+use crate::palette::{ColorRole, Palette};
 use serde::{Deserialize, Serialize};
+use serde_default::DefaultFromSerde;
+use zvx_base::{default_unit_f64, is_default, is_default_unit_f64};
 
 pub struct LineParameters {
    pub line_width: f64,
@@ -20,11 +27,52 @@ pub struct LineParameters {
    pub dash_offset: f64,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+// Dash arrays are usable if empty, or if all lengths are finite and non-negative, and not all
+// zero.  Renderers such as Cairo fail on other arrays.
+fn valid_dashes(dashes: &[f64], dash_offset: f64) -> bool {
+   dashes.is_empty()
+      || (dash_offset.is_finite()
+         && dashes.iter().all(|d| d.is_finite() && *d >= 0.0)
+         && dashes.iter().any(|d| *d > 0.0))
+}
+
+impl LineParameters {
+   #[must_use]
+   pub fn has_valid_dashes(&self) -> bool {
+      valid_dashes(&self.dashes, self.dash_offset)
+   }
+}
+
+// Weight classes, from heaviest to lightest, are Heavy, Ordinary, Light and Hairline.  Light
+// lines are dashed.  Custom styles are specified relative to the diagram choices.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub enum LineChoice {
    #[default]
    Ordinary,
    Light,
+   Heavy,
+   Hairline,
+   Custom(LineStyle),
+}
+
+// The weight is relative to the diagram line width.  The dashes, alternating on and off
+// lengths, and the dash offset are scaled by the annotation linear scale.  An empty dash array
+// gives a solid line, as does an invalid array, such as one with negative lengths.
+#[derive(Serialize, Deserialize, Debug, Clone, DefaultFromSerde, PartialEq)]
+pub struct LineStyle {
+   #[serde(skip_serializing_if = "is_default_unit_f64", default = "default_unit_f64")]
+   pub weight: f64,
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub dashes: Vec<f64>,
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub dash_offset: f64,
+}
+
+impl LineStyle {
+   #[must_use]
+   pub fn has_valid_dashes(&self) -> bool {
+      valid_dashes(&self.dashes, self.dash_offset)
+   }
 }
 
 // Non-sequential drawables should not use continuation, since their order is not guaranteed.
@@ -54,14 +102,54 @@ pub enum PathCompletion {
    Closed,
 }
 
+// Stroke end caps, as in Cairo and SVG.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum LineCapChoice {
+   #[default]
+   Butt,
+   Round,
+   Square,
+}
+
+// Stroke joins, as in Cairo and SVG.  Miter joins fall back to bevel beyond the miter limit.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum LineJoinChoice {
+   #[default]
+   Miter,
+   Round,
+   Bevel,
+}
+
+// Markers, such as arrowheads, at path ends and interior vertices.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MarkerChoice {
+   #[default]
+   None,
+   Open,   // Stroked chevron.
+   Filled, // Filled triangle.
+   Barbed, // Filled triangle with notched back.
+   Circle, // Filled circle centred on the end.
+}
+
+// Point markers, sized relative to the diagram point size.  Unfilled shapes are stroked.
 #[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub enum PointChoice {
    #[default]
    Circle,
-   Dot,
+   Dot, // Filled circle.
    Plus,
    Times,
    Square,
+   FilledSquare,
+   Triangle, // Pointing up.
+   FilledTriangle,
+   TriangleDown,
+   FilledTriangleDown,
+   Diamond,
+   FilledDiamond,
+   Star, // Five-pointed.
+   FilledStar,
+   Custom, // The custom marker of the points drawable.
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
@@ -89,6 +177,103 @@ pub enum TextSizeChoice {
    Small,
 }
 
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum FontWeightChoice {
+   Light,
+   #[default]
+   Normal,
+   SemiBold,
+   Bold,
+   Heavy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum FontSlantChoice {
+   #[default]
+   Normal,
+   Italic,
+   Oblique,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum FontStretchChoice {
+   Condensed,
+   SemiCondensed,
+   #[default]
+   Normal,
+   SemiExpanded,
+   Expanded,
+}
+
+// Families are listed in order of preference, with later families as fallbacks for missing
+// fonts or glyphs.  Generic families, such as "sans", "serif" and "monospace", are always
+// available.  Unset (default) choices are taken from the diagram, and an unset family from the
+// diagram is sans.
+#[derive(Serialize, Deserialize, Debug, Clone, DefaultFromSerde, PartialEq, Eq)]
+pub struct FontChoices {
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub family: Vec<String>,
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub weight: FontWeightChoice,
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub slant: FontSlantChoice,
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub stretch: FontStretchChoice,
+}
+
+impl FontChoices {
+   pub const DEFAULT_FAMILY: &str = "sans";
+
+   // Each unset (default) choice is replaced by that inherited.
+   #[must_use]
+   pub fn inherit_from(&self, inherited: &Self) -> Self {
+      Self {
+         family: if self.family.is_empty() {
+            inherited.family.clone()
+         } else {
+            self.family.clone()
+         },
+         weight: if is_default(&self.weight) { inherited.weight } else { self.weight },
+         slant: if is_default(&self.slant) { inherited.slant } else { self.slant },
+         stretch: if is_default(&self.stretch) { inherited.stretch } else { self.stretch },
+      }
+   }
+
+   // Comma-separated list of families, in the manner of CSS and Pango.
+   #[must_use]
+   pub fn family_list(&self) -> String {
+      if self.family.is_empty() {
+         Self::DEFAULT_FAMILY.to_string()
+      } else {
+         self.family.join(",")
+      }
+   }
+}
+
+// Alignment of lines within multi-line text.  Justified lines, other than the last of each
+// paragraph, are stretched to the wrapping width.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum TextAlignChoice {
+   #[default]
+   Left,
+   Center,
+   Right,
+   Justified,
+}
+
+// Layout of multi-line text.  Lines break at newlines in the content and, if the maximum width
+// is positive, wrap at word boundaries so as to fit.  The maximum width is in canvas units, as
+// for font sizes.  Line spacing is a factor of the natural spacing for the font.
+#[derive(Serialize, Deserialize, Debug, Clone, DefaultFromSerde, PartialEq)]
+pub struct ParagraphChoices {
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub alignment: TextAlignChoice,
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub max_width: f64,
+   #[serde(skip_serializing_if = "is_default_unit_f64", default = "default_unit_f64")]
+   pub line_spacing: f64,
+}
+
 // Directions (horizontal, vertical) over which to offset anchoring.
 #[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub enum TextOffsetChoice {
@@ -104,79 +289,159 @@ pub enum TextAnchorChoice {
    ThreeByThree(TextAnchorHorizontal, TextAnchorVertical),
 }
 
-// This is really too high-level. Colour palettes will move higher up, and the lower level will
-// be a choice between RGB and CMYK, with conversion.
+// Colour, as RGB, RGBA or CMYK.  Components are in [0, 1].  Named colours are constants.
+// Colours can also be given by role, such as axis or accent, resolved through the diagram
+// palette at render time.
 //
-// Although trivial now, we do not derive Copy because it may grow in future.
-#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+// The default, DefaultBlack, marks the colour as unset so that it can be replaced by
+// diagram-wide choices.  If still unset when rendered, it takes the palette foreground.
+//
+// Although small, we do not derive Copy because it may grow in future.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub enum ColorChoice {
    #[default]
    DefaultBlack,
-   Black,
-   Gray,
-   DarkGray,
-   LightGray,
-   LightBlue,
-   LightGreen,
-   BrightRed,
-   BrightGreen,
-   BrightBlue,
-   BrightYellow,
-   BrightCyan,
-   BrightMagenta,
-   Red,
-   Green,
-   Blue,
-   YellowBrown,
-   BlueGreen,
-   BlueRed,
-   RedRedGreen,
-   GreenGreenRed,
-   BlueBlueGreen,
-   GreenGreenBlue,
-   RedRedBlue,
-   BlueBlueRed,
-   ZvxBackground, // Standard Heptodes / Zebraix background colour.
+   Rgb(f64, f64, f64),
+   Rgba(f64, f64, f64, f64),
+   Cmyk(f64, f64, f64, f64),
+   Role(ColorRole),
 }
 
 impl LineChoice {
    // Diagram-specific simple values.
    const LIGHT_DASH_LENGTH: f64 = 10.0;
    const LIGHT_DASH_SEPARATION: f64 = 7.0;
+   // Heavy is relative to ordinary lines, and hairline relative to light lines.
+   const HEAVY_WEIGHT: f64 = 2.0;
+   const HAIRLINE_WEIGHT: f64 = 0.5;
 }
 
 impl ColorChoice {
-   #[must_use]
+   pub const BLACK: Self = Self::Rgb(0.0, 0.0, 0.0);
+   pub const GRAY: Self = Self::Rgb(0.55, 0.55, 0.55);
+   pub const DARK_GRAY: Self = Self::Rgb(0.35, 0.35, 0.35);
+   pub const LIGHT_GRAY: Self = Self::Rgb(0.7, 0.7, 0.7);
+   pub const LIGHT_BLUE: Self = Self::Rgb(0.5, 0.5, 1.0);
+   pub const LIGHT_GREEN: Self = Self::Rgb(0.35, 0.85, 0.35);
+   pub const BRIGHT_RED: Self = Self::Rgb(1.0, 0.0, 0.0);
+   pub const BRIGHT_GREEN: Self = Self::Rgb(0.0, 1.0, 0.0);
+   pub const BRIGHT_BLUE: Self = Self::Rgb(0.0, 0.0, 1.0);
+   pub const BRIGHT_YELLOW: Self = Self::Rgb(1.0, 1.0, 0.0);
+   pub const BRIGHT_CYAN: Self = Self::Rgb(0.0, 1.0, 1.0);
+   pub const BRIGHT_MAGENTA: Self = Self::Rgb(1.0, 0.0, 1.0);
+   pub const RED: Self = Self::Rgb(0.6, 0.0, 0.0);
+   pub const GREEN: Self = Self::Rgb(0.0, 0.4, 0.0);
+   pub const BLUE: Self = Self::Rgb(0.0, 0.0, 0.65);
+   pub const YELLOW_BROWN: Self = Self::Rgb(0.37, 0.28, 0.0);
+   pub const BLUE_GREEN: Self = Self::Rgb(0.0, 0.3, 0.3);
+   pub const BLUE_RED: Self = Self::Rgb(0.35, 0.0, 0.5);
+   pub const RED_RED_GREEN: Self = Self::Rgb(0.45, 0.18, 0.0);
+   pub const GREEN_GREEN_RED: Self = Self::Rgb(0.24, 0.32, 0.0);
+   pub const BLUE_BLUE_GREEN: Self = Self::Rgb(0.0, 0.18, 0.45);
+   pub const GREEN_GREEN_BLUE: Self = Self::Rgb(0.0, 0.36, 0.18);
+   pub const RED_RED_BLUE: Self = Self::Rgb(0.47, 0.0, 0.34);
+   pub const BLUE_BLUE_RED: Self = Self::Rgb(0.23, 0.0, 0.55);
+   // Standard Heptodes / Zebraix background colour, #FFFAE8.
    #[allow(clippy::eq_op)]
-   pub const fn to_rgb(&self) -> (f64, f64, f64) {
+   pub const ZVX_BACKGROUND: Self = Self::Rgb(255.0 / 255.0, 250.0 / 255.0, 232.0 / 255.0);
+
+   // Alpha is ignored, and CMYK is converted naively, without colour management.  Roles should
+   // be resolved through the palette first, otherwise they are treated as black.
+   #[must_use]
+   pub fn to_rgb(&self) -> (f64, f64, f64) {
+      let (r, g, b, _) = self.to_rgba();
+      (r, g, b)
+   }
+
+   #[must_use]
+   #[allow(clippy::many_single_char_names)]
+   pub fn to_rgba(&self) -> (f64, f64, f64, f64) {
+      match self {
+         Self::DefaultBlack | Self::Role(_) => (0.0, 0.0, 0.0, 1.0),
+         Self::Rgb(r, g, b) => (*r, *g, *b, 1.0),
+         Self::Rgba(r, g, b, a) => (*r, *g, *b, *a),
+         Self::Cmyk(c, m, y, k) => {
+            ((1.0 - c) * (1.0 - k), (1.0 - m) * (1.0 - k), (1.0 - y) * (1.0 - k), 1.0)
+         }
+      }
+   }
+
+   // Naive conversion, with maximal black component.  Alpha is ignored.
+   #[must_use]
+   #[allow(clippy::many_single_char_names)]
+   pub fn to_cmyk(&self) -> (f64, f64, f64, f64) {
+      if let Self::Cmyk(c, m, y, k) = self {
+         return (*c, *m, *y, *k);
+      }
+      let (r, g, b) = self.to_rgb();
+      let k = 1.0 - r.max(g).max(b);
+      if k >= 1.0 {
+         return (0.0, 0.0, 0.0, 1.0);
+      }
+      let scale = 1.0 / (1.0 - k);
+      ((1.0 - r - k) * scale, (1.0 - g - k) * scale, (1.0 - b - k) * scale, k)
+   }
+
+   #[must_use]
+   pub const fn alpha(&self) -> f64 {
       match self {
-         Self::DefaultBlack | Self::Black => (0.0, 0.0, 0.0),
-         Self::Gray => (0.55, 0.55, 0.55),
-         Self::DarkGray => (0.35, 0.35, 0.35),
-         Self::LightGray => (0.7, 0.7, 0.7),
-         Self::LightBlue => (0.5, 0.5, 1.0),
-         Self::LightGreen => (0.35, 0.85, 0.35),
-         Self::BrightRed => (1.0, 0.0, 0.0),
-         Self::BrightGreen => (0.0, 1.0, 0.0),
-         Self::BrightBlue => (0.0, 0.0, 1.0),
-         Self::BrightYellow => (1.0, 1.0, 0.0),
-         Self::BrightCyan => (0.0, 1.0, 1.0),
-         Self::BrightMagenta => (1.0, 0.0, 1.0),
-         Self::Red => (0.6, 0.0, 0.0),
-         Self::Green => (0.0, 0.4, 0.0),
-         Self::Blue => (0.0, 0.0, 0.65),
-         Self::YellowBrown => (0.37, 0.28, 0.0),
-         Self::BlueGreen => (0.0, 0.3, 0.3),
-         Self::BlueRed => (0.35, 0.0, 0.5),
-         Self::RedRedGreen => (0.45, 0.18, 0.0),
-         Self::GreenGreenRed => (0.24, 0.32, 0.0),
-         Self::BlueBlueGreen => (0.0, 0.18, 0.45),
-         Self::GreenGreenBlue => (0.0, 0.36, 0.18),
-         Self::RedRedBlue => (0.47, 0.0, 0.34),
-         Self::BlueBlueRed => (0.23, 0.0, 0.55),
-         Self::ZvxBackground => (255.0 / 255.0, 250.0 / 255.0, 232.0 / 255.0), // #FFFAE8.
+         Self::Rgba(_, _, _, a) => *a,
+         _ => 1.0,
+      }
+   }
+
+   // Grey of same luma, keeping alpha if the colour has it.
+   #[must_use]
+   #[allow(clippy::suboptimal_flops)]
+   pub fn to_greyscale(&self) -> Self {
+      let (r, g, b, a) = self.to_rgba();
+      let luma = 0.299 * r + 0.587 * g + 0.114 * b;
+      if matches!(self, Self::Rgba(..)) {
+         Self::Rgba(luma, luma, luma, a)
+      } else {
+         Self::Rgb(luma, luma, luma)
       }
    }
+
+   // Colour with alpha replaced, converted to RGBA.
+   #[must_use]
+   pub fn with_alpha(&self, alpha: f64) -> Self {
+      let (r, g, b) = self.to_rgb();
+      Self::Rgba(r, g, b, alpha)
+   }
+}
+
+pub struct MarkerParameters {
+   pub length: f64,     // Distance from tip to back.
+   pub half_width: f64, // Half-width at back, or radius of circle.
+   // Distance by which to shorten the path, so that the stroke does not poke through the tip.
+   pub setback: f64,
+}
+
+impl MarkerChoice {
+   // Diagram-specific simple values, relative to line width.
+   const LENGTH_FACTOR: f64 = 8.0;
+   const HALF_WIDTH_FACTOR: f64 = 3.0;
+   // Depth of notch in barbed markers, relative to length.
+   pub const BARB_NOTCH: f64 = 0.35;
+
+   #[must_use]
+   pub fn to_marker_parameters(
+      &self,
+      size: f64,
+      diagram_choices: &DiagramChoices,
+   ) -> MarkerParameters {
+      let length = Self::LENGTH_FACTOR * size * diagram_choices.line_width;
+      let half_width = Self::HALF_WIDTH_FACTOR * size * diagram_choices.line_width;
+      let setback = match self {
+         Self::None => 0.0,
+         Self::Open => diagram_choices.line_width,
+         Self::Filled => 0.5 * length,
+         Self::Barbed => (1.0 - Self::BARB_NOTCH) * length,
+         Self::Circle => half_width,
+      };
+      MarkerParameters { length, half_width, setback }
+   }
 }
 
 impl LineChoice {
@@ -196,6 +461,27 @@ impl LineChoice {
             ]),
             dash_offset: 0.0,
          },
+         Self::Heavy => LineParameters {
+            line_width: diagram_choices.line_width * Self::HEAVY_WEIGHT,
+            dashes: Box::new([]),
+            dash_offset: 0.0,
+         },
+         Self::Hairline => LineParameters {
+            line_width: diagram_choices.line_width
+               * diagram_choices.annotation_linear_scale
+               * Self::HAIRLINE_WEIGHT,
+            dashes: Box::new([]),
+            dash_offset: 0.0,
+         },
+         Self::Custom(style) => LineParameters {
+            line_width: diagram_choices.line_width * style.weight,
+            dashes: style
+               .dashes
+               .iter()
+               .map(|d| d * diagram_choices.annotation_linear_scale)
+               .collect(),
+            dash_offset: style.dash_offset * diagram_choices.annotation_linear_scale,
+         },
       }
    }
 }
@@ -220,6 +506,118 @@ pub struct DiagramChoices {
    pub point_size: f64,
    pub line_width: f64,
    pub annotation_offset_absolute: [f64; 2], // Horiz and vert text offsets, relative to font size.
+   pub font_choices: FontChoices,
+
+   pub palette: Palette,
+
+   pub layer_choices: LayerChoices,
+
+   // Accessible title and description of the whole diagram.
+   pub title: Option<String>,
+   pub description: Option<String>,
+   // Prefix for the ids of document elements, such as the title and description.
+   pub document_id: Option<String>,
+}
+
+// A named layer, being the drawables with the given layer number.  Drawables on a hidden layer
+// are not rendered, and the opacity applies to the drawables of the layer as a whole.  These
+// apply to top-level drawables.  The children of groups and clipped drawables are only sorted
+// by layer, and otherwise follow their parent.
+#[derive(Serialize, Deserialize, Debug, Clone, DefaultFromSerde, PartialEq)]
+pub struct NamedLayer {
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub name: String,
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub layer: i32,
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub hidden: bool,
+   #[serde(skip_serializing_if = "is_default_unit_f64", default = "default_unit_f64")]
+   pub opacity: f64,
+}
+
+// Declared layers.  Layers are drawn in order of layer number, and drawables within a layer in
+// the order given, whatever the order of declaration.  Layer numbers that are not declared are
+// rendered as normal.  Drawables refer to layers by number, so layers declared with the same
+// number are one layer, and the first declared gives its visibility and opacity.
+#[derive(Serialize, Deserialize, Debug, Clone, DefaultFromSerde, PartialEq)]
+pub struct LayerChoices {
+   #[serde(skip_serializing_if = "is_default", default)]
+   pub layers: Vec<NamedLayer>,
+}
+
+impl LayerChoices {
+   pub const BACKGROUND: i32 = -1000;
+   pub const GRID: i32 = 0;
+   pub const CONTENT: i32 = 30;
+   pub const ANNOTATION: i32 = 60;
+
+   // Background, grid, content and annotation layers, as used for generated axes.
+   #[must_use]
+   pub fn standard() -> Self {
+      let named = |name: &str, layer: i32| NamedLayer {
+         name: name.to_string(),
+         layer,
+         ..Default::default()
+      };
+      Self {
+         layers: vec![
+            named("background", Self::BACKGROUND),
+            named("grid", Self::GRID),
+            named("content", Self::CONTENT),
+            named("annotation", Self::ANNOTATION),
+         ],
+      }
+   }
+
+   // Adds a layer, replacing any of the same name.
+   #[must_use]
+   pub fn with_layer(mut self, layer: NamedLayer) -> Self {
+      self.layers.retain(|existing| existing.name != layer.name);
+      self.layers.push(layer);
+      self
+   }
+
+   #[must_use]
+   pub fn number(&self, name: &str) -> Option<i32> {
+      self.layers.iter().find(|named| named.name == name).map(|named| named.layer)
+   }
+
+   // The first declared layer with the number.
+   #[must_use]
+   pub fn get(&self, layer: i32) -> Option<&NamedLayer> {
+      self.layers.iter().find(|named| named.layer == layer)
+   }
+
+   pub fn get_mut(&mut self, name: &str) -> Option<&mut NamedLayer> {
+      self.layers.iter_mut().find(|named| named.name == name)
+   }
+
+   #[must_use]
+   pub fn is_hidden(&self, layer: i32) -> bool {
+      self.get(layer).is_some_and(|named| named.hidden)
+   }
+
+   #[must_use]
+   pub fn opacity(&self, layer: i32) -> f64 {
+      self.get(layer).map_or(1.0, |named| named.opacity)
+   }
+
+   // Indices of drawables sorted by layer number.
+   #[must_use]
+   pub fn sort_order(drawables: &[QualifiedDrawable]) -> Vec<usize> {
+      let mut indices = (0..drawables.len()).collect::<Vec<_>>();
+      // Sorting is stable, so that drawables within a layer keep their order.
+      indices.sort_by_key(|&i| drawables[i].layer);
+      indices
+   }
+
+   // Indices of top-level drawables in drawing order, omitting those on hidden layers.
+   #[must_use]
+   pub fn draw_order(&self, drawables: &[QualifiedDrawable]) -> Vec<usize> {
+      let mut indices = Self::sort_order(drawables);
+      indices.retain(|&i| !self.is_hidden(drawables[i].layer));
+      indices
+   }
 }
 
 // impl TextOffsetChoice {
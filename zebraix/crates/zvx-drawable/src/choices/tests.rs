@@ -0,0 +1,62 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn on_layer(layer: i32) -> QualifiedDrawable {
+   QualifiedDrawable { layer, ..Default::default() }
+}
+
+#[test]
+fn layer_order_test() {
+   let drawables: Vec<QualifiedDrawable> = [30, 0, 60, 30, -5, 0].map(on_layer).into();
+
+   // Layer number order, then the order given, regardless of declaration order.
+   let layer_choices = LayerChoices::default()
+      .with_layer(NamedLayer { name: "top".to_string(), layer: 60, ..Default::default() })
+      .with_layer(NamedLayer { name: "base".to_string(), layer: -5, ..Default::default() });
+   assert_eq!(layer_choices.draw_order(&drawables), vec![4, 1, 5, 0, 3, 2]);
+   assert_eq!(LayerChoices::sort_order(&drawables), vec![4, 1, 5, 0, 3, 2]);
+
+   // Hidden layers are omitted.
+   let mut layer_choices = LayerChoices::standard();
+   layer_choices.get_mut("content").unwrap().hidden = true;
+   assert_eq!(layer_choices.draw_order(&drawables), vec![4, 1, 5, 2]);
+
+   // Sorting alone, as for nested drawables, keeps drawables on hidden layers.
+   assert_eq!(LayerChoices::sort_order(&drawables), vec![4, 1, 5, 0, 3, 2]);
+}
+
+#[test]
+fn same_number_layers_test() {
+   // The first declared of layers with the same number gives the visibility and opacity.
+   let layer_choices = LayerChoices::default()
+      .with_layer(NamedLayer {
+         name: "labels".to_string(),
+         layer: 30,
+         opacity: 0.5,
+         ..Default::default()
+      })
+      .with_layer(NamedLayer {
+         name: "shapes".to_string(),
+         layer: 30,
+         hidden: true,
+         ..Default::default()
+      });
+   assert_eq!(layer_choices.get(30).unwrap().name, "labels");
+   assert!(!layer_choices.is_hidden(30));
+   assert!((layer_choices.opacity(30) - 0.5).abs() < 1e-12);
+   assert_eq!(layer_choices.number("shapes"), Some(30));
+   assert_eq!(layer_choices.draw_order(&[on_layer(30), on_layer(0)]), vec![1, 0]);
+}
@@ -0,0 +1,459 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::extension::ZvxExtensible;
+use crate::interface::{ClusterExtent, LineMetrics, TextMetrics, ZvxTextLayout};
+use crate::markup::auto_markup;
+use crate::{
+   FontChoices, FontStretchChoice, FontWeightChoice, MarkupChoice, ParagraphChoices,
+   TextAlignChoice, TextSingle,
+};
+use std::error::Error;
+
+// Text layout that estimates metrics from embedded advance tables, without any font system.
+// Results are deterministic, and so suitable for layout code that is unit tested.  Glyph
+// advances are those of the standard Helvetica, Times and Courier fonts, in thousandths of an
+// em, for printable ASCII.  Other characters take the width of a digit.  Vertical metrics
+// approximate those of the metric-compatible Liberation fonts.
+//
+// Markup is interpreted as for rendering, with bold, sub- and superscripts, and small and big
+// text affecting widths.  Slant does not affect widths.  Layouts cannot be rendered.
+
+// Helvetica, printable ASCII from space.
+const SANS_ADVANCES: [u16; 95] = [
+   // ' ' to '/'
+   278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+   // '0' to '?'
+   556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+   // '@' to 'O'
+   1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+   // 'P' to '_'
+   667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+   // '`' to 'o'
+   333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+   // 'p' to '~'
+   556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+// Helvetica-Bold.
+const SANS_BOLD_ADVANCES: [u16; 95] = [
+   // ' ' to '/'
+   278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+   // '0' to '?'
+   556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+   // '@' to 'O'
+   975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+   // 'P' to '_'
+   667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+   // '`' to 'o'
+   333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+   // 'p' to '~'
+   611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+// Times-Roman.
+const SERIF_ADVANCES: [u16; 95] = [
+   // ' ' to '/'
+   250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278,
+   // '0' to '?'
+   500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444,
+   // '@' to 'O'
+   921, 722, 667, 667, 722, 611, 556, 722, 722, 333, 389, 722, 611, 889, 722, 722,
+   // 'P' to '_'
+   556, 722, 667, 556, 611, 722, 722, 944, 722, 722, 611, 333, 278, 333, 469, 500,
+   // '`' to 'o'
+   333, 444, 500, 444, 500, 444, 333, 500, 500, 278, 278, 500, 278, 778, 500, 500,
+   // 'p' to '~'
+   500, 500, 333, 389, 278, 500, 500, 722, 500, 500, 444, 480, 200, 480, 541,
+];
+
+// Courier is fixed width.
+const MONO_ADVANCE: u16 = 600;
+
+// Bold serif text is widened, in the absence of a table.
+const SERIF_BOLD_FACTOR: f64 = 1.05;
+// Scaling for sub- and superscripts, and small text, as by Pango.
+const SMALLER_FACTOR: f64 = 1.0 / 1.2;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum EstimatedFamily {
+   Sans,
+   Serif,
+   Mono,
+}
+
+// Vertical metrics, in ems.  The strikethrough position is to the top of the line.
+struct EstimatedFontMetrics {
+   ascent: f64,
+   descent: f64,
+   height: f64,
+   strikethrough_position: f64,
+   strikethrough_thickness: f64,
+}
+
+impl EstimatedFamily {
+   // The first family in the list that is recognized, or sans.
+   fn from_font_choices(font_choices: &FontChoices) -> Self {
+      for family in &font_choices.family {
+         let family = family.to_lowercase();
+         if family.contains("mono") || family.contains("courier") {
+            return Self::Mono;
+         } else if family.contains("sans") || family.contains("helvetica") {
+            return Self::Sans;
+         } else if family.contains("serif") || family.contains("times") {
+            return Self::Serif;
+         }
+      }
+      Self::Sans
+   }
+
+   const fn font_metrics(self) -> EstimatedFontMetrics {
+      match self {
+         Self::Sans => EstimatedFontMetrics {
+            ascent: 0.905,
+            descent: 0.212,
+            height: 1.149,
+            strikethrough_position: 0.295,
+            strikethrough_thickness: 0.073,
+         },
+         Self::Serif => EstimatedFontMetrics {
+            ascent: 0.891,
+            descent: 0.216,
+            height: 1.149,
+            strikethrough_position: 0.284,
+            strikethrough_thickness: 0.049,
+         },
+         Self::Mono => EstimatedFontMetrics {
+            ascent: 0.833,
+            descent: 0.300,
+            height: 1.133,
+            strikethrough_position: 0.280,
+            strikethrough_thickness: 0.041,
+         },
+      }
+   }
+
+   // Advance in ems.
+   fn advance(self, c: char, bold: bool) -> f64 {
+      let index = (c as usize).checked_sub(0x20).filter(|i| *i < 95).unwrap_or(0x10);
+      let thousandths = match (self, bold) {
+         (Self::Sans, false) => f64::from(SANS_ADVANCES[index]),
+         (Self::Sans, true) => f64::from(SANS_BOLD_ADVANCES[index]),
+         (Self::Serif, false) => f64::from(SERIF_ADVANCES[index]),
+         (Self::Serif, true) => SERIF_BOLD_FACTOR * f64::from(SERIF_ADVANCES[index]),
+         (Self::Mono, _) => f64::from(MONO_ADVANCE),
+      };
+      0.001 * thousandths
+   }
+}
+
+const fn stretch_factor(stretch: FontStretchChoice) -> f64 {
+   match stretch {
+      FontStretchChoice::Condensed => 0.75,
+      FontStretchChoice::SemiCondensed => 0.875,
+      FontStretchChoice::Normal => 1.0,
+      FontStretchChoice::SemiExpanded => 1.125,
+      FontStretchChoice::Expanded => 1.25,
+   }
+}
+
+// Character of text, with the size relative to the font and whether bold.
+#[derive(Debug, Copy, Clone)]
+struct StyledChar {
+   c: char,
+   scale: f64,
+   bold: bool,
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+   match entity {
+      "amp" => Some('&'),
+      "lt" => Some('<'),
+      "gt" => Some('>'),
+      "quot" => Some('"'),
+      "apos" => Some('\''),
+      _ => {
+         let number = entity.strip_prefix('#')?;
+         let code = match number.strip_prefix('x') {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+         };
+         char::from_u32(code)
+      }
+   }
+}
+
+// Approximate interpretation of Pango markup.  An accelerator marker, if given, is dropped
+// unless doubled.
+fn styled_from_markup(markup: &str, bold: bool, accel_marker: Option<char>) -> Vec<StyledChar> {
+   let mut styled = Vec::new();
+   let mut tags: Vec<String> = Vec::new();
+   let mut chars = markup.chars().peekable();
+   while let Some(c) = chars.next() {
+      let text_char = match c {
+         '<' => {
+            let tag: String = chars.by_ref().take_while(|t| *t != '>').collect();
+            if let Some(closing) = tag.strip_prefix('/') {
+               if let Some(position) = tags.iter().rposition(|open| open == closing.trim()) {
+                  tags.truncate(position);
+               }
+            } else if !tag.ends_with('/') {
+               tags.push(tag.split_whitespace().next().unwrap_or_default().to_string());
+            }
+            continue;
+         }
+         '&' => {
+            let entity: String = chars.by_ref().take_while(|t| *t != ';').collect();
+            decode_entity(&entity).unwrap_or('?')
+         }
+         _ if Some(c) == accel_marker => {
+            if chars.next_if_eq(&c).is_none() {
+               continue;
+            }
+            c
+         }
+         _ => c,
+      };
+      let mut scale = 1.0;
+      let mut tagged_bold = bold;
+      for tag in &tags {
+         match tag.as_str() {
+            "sub" | "sup" | "small" => scale *= SMALLER_FACTOR,
+            "big" => scale /= SMALLER_FACTOR,
+            "b" => tagged_bold = true,
+            _ => {}
+         }
+      }
+      styled.push(StyledChar { c: text_char, scale, bold: tagged_bold });
+   }
+   styled
+}
+
+fn styled_from_text(single_text: &TextSingle, bold: bool) -> Vec<StyledChar> {
+   let plain = |content: &str| -> Vec<StyledChar> {
+      content.chars().map(|c| StyledChar { c, scale: 1.0, bold }).collect()
+   };
+   match single_text.markup {
+      MarkupChoice::Plain => plain(&single_text.content),
+      MarkupChoice::Pango => styled_from_markup(&single_text.content, bold, Some('_')),
+      MarkupChoice::Auto => auto_markup(&single_text.content).map_or_else(
+         || plain(&single_text.content),
+         |markup| styled_from_markup(&markup, bold, None),
+      ),
+   }
+}
+
+// Content of a text as displayed, with any markup interpreted approximately.
+#[must_use]
+pub fn displayed_text(single_text: &TextSingle) -> String {
+   styled_from_text(single_text, false).iter().map(|styled| styled.c).collect()
+}
+
+// Splits a paragraph into lines, greedily at spaces if the maximum width is positive.  Returns
+// the end of each line, and the start of the next.
+fn wrap_paragraph(advances: &[f64], chars: &[StyledChar], max_width: f64) -> Vec<(usize, usize)> {
+   let mut breaks = Vec::new();
+   let mut line_start = 0;
+   let mut last_space: Option<usize> = None;
+   let mut width = 0.0;
+   let mut i = 0;
+   while i < chars.len() {
+      if chars[i].c == ' ' {
+         last_space = Some(i);
+      }
+      width += advances[i];
+      if max_width > 0.0 && width > max_width && chars[i].c != ' ' {
+         if let Some(space) = last_space.filter(|space| *space > line_start) {
+            breaks.push((space, space + 1));
+            line_start = space + 1;
+            last_space = None;
+            width = 0.0;
+            i = line_start;
+            continue;
+         }
+      }
+      i += 1;
+   }
+   breaks.push((chars.len(), chars.len()));
+   breaks
+}
+
+pub struct EstimatedTextLayout {
+   units_per_point: f64,
+   metrics: Option<TextMetrics>,
+   clusters: Vec<ClusterExtent>,
+}
+
+impl EstimatedTextLayout {
+   // Metrics are reported in the given units per point (or canvas unit).  For consistency
+   // with the Cairo-Pango layout, this would be `pango::SCALE`.
+   #[must_use]
+   pub const fn new(units_per_point: f64) -> Self {
+      Self { units_per_point, metrics: None, clusters: Vec::new() }
+   }
+}
+
+impl Default for EstimatedTextLayout {
+   fn default() -> Self {
+      Self::new(1.0)
+   }
+}
+
+impl ZvxExtensible for EstimatedTextLayout {}
+
+impl ZvxTextLayout for EstimatedTextLayout {
+   fn set_layout(
+      &mut self,
+      font_choices: &FontChoices,
+      paragraph_choices: &ParagraphChoices,
+      font_size: f64,
+      single_text: &TextSingle,
+   ) {
+      let family = EstimatedFamily::from_font_choices(font_choices);
+      let bold = matches!(
+         font_choices.weight,
+         FontWeightChoice::SemiBold | FontWeightChoice::Bold | FontWeightChoice::Heavy
+      );
+      let em = font_size * self.units_per_point;
+      let font_metrics = family.font_metrics();
+
+      let font_ascent = font_metrics.ascent * em;
+      let font_descent = font_metrics.descent * em;
+      let font_height = font_metrics.height * em;
+      let strikethrough_center =
+         font_metrics.strikethrough_thickness.mul_add(-0.5, font_metrics.strikethrough_position)
+            * em;
+      let even_half_height =
+         f64::max(font_ascent - strikethrough_center, font_descent + strikethrough_center);
+
+      // Text content dependence below.
+
+      let styled = styled_from_text(single_text, bold);
+      let stretch = stretch_factor(font_choices.stretch);
+      let advances: Vec<f64> =
+         styled.iter().map(|s| family.advance(s.c, s.bold) * s.scale * stretch * em).collect();
+      let max_width = paragraph_choices.max_width * self.units_per_point;
+
+      // Lines as ranges of characters, excluding the breaks, and whether the line ends a
+      // paragraph.
+      let mut line_ranges: Vec<(usize, usize, bool)> = Vec::new();
+      let mut paragraph_start = 0;
+      for paragraph in styled.split(|s| s.c == '\n') {
+         let paragraph_end = paragraph_start + paragraph.len();
+         let breaks =
+            wrap_paragraph(&advances[paragraph_start..paragraph_end], paragraph, max_width);
+         let mut line_start = paragraph_start;
+         for (j, (end, next)) in breaks.iter().enumerate() {
+            line_ranges.push((line_start, paragraph_start + end, j + 1 == breaks.len()));
+            line_start = paragraph_start + next;
+         }
+         paragraph_start = paragraph_end + 1;
+      }
+
+      // Trailing spaces do not contribute to line widths.
+      let natural_widths: Vec<f64> = line_ranges
+         .iter()
+         .map(|(start, end, _)| {
+            let trimmed =
+               styled[*start..*end].iter().rposition(|s| s.c != ' ').map_or(0, |p| p + 1);
+            advances[*start..*start + trimmed].iter().sum()
+         })
+         .collect();
+      let available = if max_width > 0.0 {
+         max_width
+      } else {
+         natural_widths.iter().copied().fold(0.0, f64::max)
+      };
+
+      let line_spacing = font_height * paragraph_choices.line_spacing;
+      let mut lines = Vec::with_capacity(line_ranges.len());
+      self.clusters.clear();
+      for (i, ((start, end, ends_paragraph), natural_width)) in
+         line_ranges.iter().zip(&natural_widths).enumerate()
+      {
+         let justify = paragraph_choices.alignment == TextAlignChoice::Justified
+            && max_width > 0.0
+            && !ends_paragraph;
+         let spaces = styled[*start..*end].iter().filter(|s| s.c == ' ').count();
+         let (line_start, line_width, space_stretch) = if justify && spaces > 0 {
+            #[allow(clippy::cast_precision_loss)]
+            let space_stretch = (available - natural_width) / spaces as f64;
+            (0.0, available, space_stretch)
+         } else {
+            let line_start = match paragraph_choices.alignment {
+               TextAlignChoice::Left | TextAlignChoice::Justified => 0.0,
+               TextAlignChoice::Center => 0.5 * (available - natural_width),
+               TextAlignChoice::Right => available - natural_width,
+            };
+            (line_start, *natural_width, 0.0)
+         };
+
+         #[allow(clippy::cast_precision_loss)]
+         let top = i as f64 * line_spacing;
+         lines.push(LineMetrics {
+            start: line_start,
+            width: line_width,
+            baseline: top + font_ascent,
+            height: font_height,
+         });
+
+         let mut x = line_start;
+         for (s, advance) in styled[*start..*end].iter().zip(&advances[*start..*end]) {
+            let width = if s.c == ' ' { advance + space_stretch } else { *advance };
+            self.clusters.push(ClusterExtent { content: s.c.to_string(), start: x, width });
+            x += width;
+         }
+      }
+
+      let text_start = lines.iter().map(|line| line.start).fold(f64::INFINITY, f64::min);
+      let text_end = lines.iter().map(|line| line.start + line.width).fold(0.0, f64::max);
+      #[allow(clippy::cast_precision_loss)]
+      let text_height = ((lines.len() - 1) as f64).mul_add(line_spacing, font_height);
+
+      self.metrics = Some(TextMetrics {
+         strikethrough_center,
+         even_half_height,
+         font_ascent,
+         font_descent,
+         font_height,
+         text_start,
+         text_width: text_end - text_start,
+         text_height,
+         lines,
+      });
+   }
+
+   // Call set_layout first.
+   fn get_metrics(&mut self) -> &Option<TextMetrics> {
+      &self.metrics
+   }
+
+   #[allow(clippy::missing_errors_doc)]
+   fn render_layout(&mut self) -> Result<(), Box<dyn Error>> {
+      Err("Estimated text layout cannot be rendered.".into())
+   }
+
+   // Clusters are single characters.
+   fn get_clusters(&mut self) -> Vec<ClusterExtent> {
+      self.clusters.iter().map(|c| ClusterExtent { content: c.content.clone(), ..*c }).collect()
+   }
+
+   #[allow(clippy::missing_errors_doc)]
+   fn render_layout_rotated(&mut self, _: [f64; 2], _: f64) -> Result<(), Box<dyn Error>> {
+      Err("Estimated text layout cannot be rendered.".into())
+   }
+}
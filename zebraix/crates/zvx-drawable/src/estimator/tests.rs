@@ -0,0 +1,137 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const TOLERANCE: f64 = 1e-9;
+
+fn assert_close(actual: f64, expected: f64) {
+   assert!((actual - expected).abs() < TOLERANCE, "{actual} differs from {expected}");
+}
+
+fn estimate(
+   font_choices: &FontChoices,
+   paragraph_choices: &ParagraphChoices,
+   content: &str,
+   markup: MarkupChoice,
+) -> (TextMetrics, Vec<ClusterExtent>) {
+   let mut layout = EstimatedTextLayout::default();
+   let single_text = TextSingle { content: content.to_string(), markup, ..Default::default() };
+   layout.set_layout(font_choices, paragraph_choices, 10.0, &single_text);
+   let clusters = layout.get_clusters();
+   (layout.metrics.take().unwrap(), clusters)
+}
+
+#[test]
+fn advance_test() {
+   let sans = FontChoices::default();
+   let (metrics, clusters) =
+      estimate(&sans, &ParagraphChoices::default(), "Hello", MarkupChoice::Plain);
+   assert_close(metrics.text_width, 0.01 * f64::from(722 + 556 + 222 + 222 + 556));
+   assert_close(metrics.text_start, 0.0);
+   assert_close(metrics.text_height, 11.49);
+   assert_eq!(clusters.len(), 5);
+   assert_close(clusters[1].start, 7.22);
+
+   let mono = FontChoices { family: vec!["monospace".to_string()], ..Default::default() };
+   let (metrics, _) = estimate(&mono, &ParagraphChoices::default(), "abc", MarkupChoice::Plain);
+   assert_close(metrics.text_width, 18.0);
+
+   let bold_condensed = FontChoices {
+      weight: FontWeightChoice::Bold,
+      stretch: FontStretchChoice::Condensed,
+      ..Default::default()
+   };
+   let (metrics, _) =
+      estimate(&bold_condensed, &ParagraphChoices::default(), "bd", MarkupChoice::Plain);
+   assert_close(metrics.text_width, 0.75 * 12.22);
+
+   let mut scaled_layout = EstimatedTextLayout::new(1024.0);
+   scaled_layout.set_layout(
+      &mono,
+      &ParagraphChoices::default(),
+      10.0,
+      &TextSingle { content: "abc".to_string(), ..Default::default() },
+   );
+   assert_close(scaled_layout.get_metrics().as_ref().unwrap().text_width, 1024.0 * 18.0);
+}
+
+#[test]
+fn markup_test() {
+   let mono = FontChoices { family: vec!["monospace".to_string()], ..Default::default() };
+   let paragraph_choices = ParagraphChoices::default();
+
+   let (metrics, clusters) = estimate(&mono, &paragraph_choices, "x_1^2", MarkupChoice::Auto);
+   assert_eq!(clusters.len(), 3);
+   assert_close(metrics.text_width, 6.0 + 2.0 * 6.0 / 1.2);
+
+   let (metrics, _) = estimate(&mono, &paragraph_choices, "a &lt; <b>b</b>", MarkupChoice::Pango);
+   assert_close(metrics.text_width, 5.0 * 6.0);
+
+   // Accelerator markers are dropped unless doubled.
+   let (metrics, _) = estimate(&mono, &paragraph_choices, "_a__b", MarkupChoice::Pango);
+   assert_close(metrics.text_width, 3.0 * 6.0);
+
+   let (metrics, _) = estimate(&mono, &paragraph_choices, "x_1^2", MarkupChoice::Plain);
+   assert_close(metrics.text_width, 5.0 * 6.0);
+}
+
+#[test]
+fn paragraph_test() {
+   let mono = FontChoices { family: vec!["monospace".to_string()], ..Default::default() };
+
+   let (metrics, _) = estimate(
+      &mono,
+      &ParagraphChoices { line_spacing: 1.5, ..Default::default() },
+      "one\nthree",
+      MarkupChoice::Plain,
+   );
+   assert_eq!(metrics.lines.len(), 2);
+   assert_close(metrics.text_width, 30.0);
+   assert_close(metrics.lines[0].width, 18.0);
+   assert_close(metrics.lines[1].baseline - metrics.lines[0].baseline, 1.5 * 11.33);
+   assert_close(metrics.text_height, 2.5 * 11.33);
+
+   // Wraps to "aa bb", "cc", "dd dd" and "dd", with the maximum width of 6 characters.
+   for (alignment, expected_starts) in [
+      (TextAlignChoice::Left, [0.0, 0.0, 0.0, 0.0]),
+      (TextAlignChoice::Center, [3.0, 12.0, 3.0, 12.0]),
+      (TextAlignChoice::Right, [6.0, 24.0, 6.0, 24.0]),
+   ] {
+      let (metrics, _) = estimate(
+         &mono,
+         &ParagraphChoices { alignment, max_width: 36.0, ..Default::default() },
+         "aa bb cc\ndd dd dd",
+         MarkupChoice::Plain,
+      );
+      assert_eq!(metrics.lines.len(), 4);
+      for (line, expected_start) in metrics.lines.iter().zip(expected_starts) {
+         assert_close(line.start, expected_start);
+      }
+   }
+
+   let (metrics, clusters) = estimate(
+      &mono,
+      &ParagraphChoices {
+         alignment: TextAlignChoice::Justified,
+         max_width: 36.0,
+         ..Default::default()
+      },
+      "aa bb cc",
+      MarkupChoice::Plain,
+   );
+   assert_close(metrics.lines[0].width, 36.0);
+   assert_close(metrics.lines[1].width, 12.0);
+   assert_close(clusters[3].start, 24.0);
+}
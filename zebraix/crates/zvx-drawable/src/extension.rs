@@ -0,0 +1,91 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Typed extensions for backend-specific features.
+//
+// Rust is (as of rustc 1.85.1) unable to convert a boxed heap object to (a reference to) its
+// concrete implementation type when any kind of non-static lifetime is involved.  Callers of
+// a render engine or text layout therefore cannot reach backend-specific methods directly.
+// Instead, backends declare named capabilities, each with typed argument and output, and
+// callers query and invoke them through the `ZvxCapabilities` methods.  Only arguments and
+// outputs are type-erased in between, and these are owned.
+//
+// Refs: https://users.rust-lang.org/t/borrowing-as-any-non-static/131565,
+// https://crates.io/crates/better_any
+
+use std::any::Any;
+use std::error::Error;
+
+// A backend feature.  Capabilities are usually unit structs defined by the backend crate, and
+// names should be unique across backends.
+pub trait Capability {
+   const NAME: &'static str;
+   type Argument: Any;
+   type Output: Any;
+}
+
+// Typed handler, as implemented by a backend.
+pub type CapabilityHandler<T, C> =
+   fn(&mut T, <C as Capability>::Argument) -> Result<<C as Capability>::Output, Box<dyn Error>>;
+
+// Invokes a typed handler with a type-erased argument, for use in `invoke_erased`, which
+// typically matches the name against `C::NAME` for each supported capability.
+#[allow(clippy::missing_errors_doc)]
+pub fn invoke_handler<T: ?Sized, C: Capability>(
+   target: &mut T,
+   argument: Box<dyn Any>,
+   handler: CapabilityHandler<T, C>,
+) -> Result<Box<dyn Any>, Box<dyn Error>> {
+   let argument = argument
+      .downcast::<C::Argument>()
+      .map_err(|_| format!("Mismatched argument for capability `{}`.", C::NAME))?;
+   Ok(Box::new(handler(target, *argument)?))
+}
+
+// Implemented by render engines and text layouts.  The defaults support no capabilities.
+pub trait ZvxExtensible {
+   fn capability_names(&self) -> Vec<&'static str> {
+      Vec::new()
+   }
+
+   #[allow(clippy::missing_errors_doc)]
+   fn invoke_erased(
+      &mut self,
+      name: &str,
+      _argument: Box<dyn Any>,
+   ) -> Result<Box<dyn Any>, Box<dyn Error>> {
+      Err(format!("Capability `{name}` is not supported.").into())
+   }
+}
+
+// Typed access to capabilities.
+pub trait ZvxCapabilities {
+   fn supports<C: Capability>(&self) -> bool;
+   #[allow(clippy::missing_errors_doc)]
+   fn invoke<C: Capability>(&mut self, argument: C::Argument) -> Result<C::Output, Box<dyn Error>>;
+}
+
+impl<E: ZvxExtensible + ?Sized> ZvxCapabilities for E {
+   fn supports<C: Capability>(&self) -> bool {
+      self.capability_names().contains(&C::NAME)
+   }
+
+   fn invoke<C: Capability>(&mut self, argument: C::Argument) -> Result<C::Output, Box<dyn Error>> {
+      let output = self.invoke_erased(C::NAME, Box::new(argument))?;
+      output
+         .downcast::<C::Output>()
+         .map(|output| *output)
+         .map_err(|_| format!("Mismatched output from capability `{}`.", C::NAME).into())
+   }
+}
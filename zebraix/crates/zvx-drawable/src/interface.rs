@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{QualifiedDrawable, TextSingle};
+use crate::extension::ZvxExtensible;
+use crate::{FontChoices, ParagraphChoices, QualifiedDrawable, TextSingle};
 use std::error::Error;
 
 pub struct TextMetrics {
@@ -22,42 +23,57 @@ pub struct TextMetrics {
    pub font_descent: f64,
    pub font_height: f64,
    // Fields above are generally independent of text content.
+   pub text_start: f64,
    pub text_width: f64,
    pub text_height: f64,
+   pub lines: Vec<LineMetrics>,
 }
 
-// Note on special functions.
-//
-// Rust is (as of rustc 1.85.1) unable to convert a boxed heap object to (a reference to) its
-// concrete implementation type when any kind of non-static lifetime is involved.  As a result
-// an implementation such as Cairo+Pango has no means to call functions with its own data.  In
-// order to work around this, the `render_layout` method was created for the text layout trait,
-// even though this really is the business of the implementation.  In order to future-proof the
-// interface, extra placeholder special functions were added.
-//
-// Refs: https://users.rust-lang.org/t/borrowing-as-any-non-static/131565,
-// https://crates.io/crates/better_any
+// Logical extent of one line within a layout, in the same units as the text metrics.  The
+// start is horizontal, and the baseline is down from the top of the layout.
+pub struct LineMetrics {
+   pub start: f64,
+   pub width: f64,
+   pub baseline: f64,
+   pub height: f64,
+}
+
+// Extent of a cluster of characters within a layout, horizontally and in the same units as
+// the metrics.
+pub struct ClusterExtent {
+   pub content: String,
+   pub start: f64,
+   pub width: f64,
+}
 
-pub trait ZvxTextLayout {
-   fn set_layout(&mut self, font_family: &str, font_size: f64, single_text: &TextSingle);
+// Backend-specific features are reached through capabilities.  See the `extension` module.
+
+pub trait ZvxTextLayout: ZvxExtensible {
+   fn set_layout(
+      &mut self,
+      font_choices: &FontChoices,
+      paragraph_choices: &ParagraphChoices,
+      font_size: f64,
+      single_text: &TextSingle,
+   );
    // fn set_markup_with_accel(&self, markup: &str, accel_marker: char) -> char;
    fn get_metrics(&mut self) -> &Option<TextMetrics>;
    #[allow(clippy::missing_errors_doc)]
    fn render_layout(&mut self) -> Result<(), Box<dyn Error>>;
-
-   // See note above about special functions.
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_0(&mut self) -> Result<(), Box<dyn Error>>;
+   // Call set_layout first.  Clusters are in logical order.
+   fn get_clusters(&mut self) -> Vec<ClusterExtent>;
+   // Renders rotated anticlockwise by angle (in radians) about the current point.  The layout
+   // origin is offset from the point by the negated adjustment, before rotation, in the same
+   // units as the metrics.
    #[allow(clippy::missing_errors_doc)]
-   fn special_function_1(&mut self) -> Result<(), Box<dyn Error>>;
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_2(&mut self) -> Result<(), Box<dyn Error>>;
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_3(&mut self) -> Result<(), Box<dyn Error>>;
+   fn render_layout_rotated(&mut self, adjust: [f64; 2], angle: f64) -> Result<(), Box<dyn Error>>;
 }
 
 // Move to an interface location, but note dependence on QualifiedDrawable.
-pub trait ZvxRenderEngine {
+pub trait ZvxRenderEngine: ZvxExtensible {
+   // Result of rendering, such as the finished output stream.
+   type Output;
+
    #[must_use]
    fn create_text_layout<'parent, 'a>(&'parent self) -> Box<dyn ZvxTextLayout + 'a>
    where
@@ -67,15 +83,5 @@ pub trait ZvxRenderEngine {
    fn render_drawables(
       &mut self,
       drawables: &[QualifiedDrawable],
-   ) -> Result<Box<dyn core::any::Any>, Box<dyn Error>>;
-
-   // See note above about special functions.
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_0(&mut self) -> Result<(), Box<dyn Error>>;
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_1(&mut self) -> Result<(), Box<dyn Error>>;
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_2(&mut self) -> Result<(), Box<dyn Error>>;
-   #[allow(clippy::missing_errors_doc)]
-   fn special_function_3(&mut self) -> Result<(), Box<dyn Error>>;
+   ) -> Result<Self::Output, Box<dyn Error>>;
 }
@@ -13,12 +13,17 @@
 // limitations under the License.
 
 use crate::choices::{
-   ColorChoice, ContinuationChoice, LineChoice, LineClosureChoice, PathCompletion, PointChoice,
+   ColorChoice, ContinuationChoice, DiagramChoices, FontChoices, LineCapChoice, LineChoice,
+   LineClosureChoice, LineJoinChoice, MarkerChoice, ParagraphChoices, PathCompletion, PointChoice,
    TextAnchorChoice, TextOffsetChoice, TextSizeChoice,
 };
 use serde::Serialize;
 use serde_default::DefaultFromSerde;
-use zvx_base::{is_default, ArcPath, CubicPath, HyperbolicPath, OneOfSegment, PolylinePath};
+use std::borrow::Cow;
+use zvx_base::{
+   default_unit_f64, is_default, is_default_unit_f64, is_near_float, ArcPath, CubicPath,
+   HyperbolicPath, OneOfSegment, PolylinePath, RatCubicHomogWeighted,
+};
 
 #[derive(Serialize, Debug, Clone, DefaultFromSerde, PartialEq, Eq)]
 pub struct SegmentChoices {
@@ -28,15 +33,141 @@ pub struct SegmentChoices {
    pub closure: LineClosureChoice,
 }
 
-// Default opacity is 0.0, which disables.
-//
-// Opacity will likely eventually be folded into color.
+// Offset is the position along the gradient, from 0.0 to 1.0.
+#[derive(Serialize, Debug, Clone, DefaultFromSerde, PartialEq)]
+pub struct ColorStop {
+   #[serde(skip_serializing_if = "is_default")]
+   pub offset: f64,
+   #[serde(skip_serializing_if = "is_default")]
+   pub color: ColorChoice,
+}
+
+// Gradient geometry is in diagram coordinates, so that it moves with the filled shape.
+#[derive(Serialize, Debug, Clone, DefaultFromSerde, PartialEq)]
+pub struct LinearGradient {
+   #[serde(skip_serializing_if = "is_default")]
+   pub start: [f64; 2],
+   #[serde(skip_serializing_if = "is_default")]
+   pub end: [f64; 2],
+   #[serde(skip_serializing_if = "is_default")]
+   pub stops: Vec<ColorStop>,
+}
+
+// The gradient radiates from the focus, which is the center if unset, out to the circle.
+#[derive(Serialize, Debug, Clone, DefaultFromSerde, PartialEq)]
+pub struct RadialGradient {
+   #[serde(skip_serializing_if = "is_default")]
+   pub center: [f64; 2],
+   #[serde(skip_serializing_if = "is_default_unit_f64", default = "default_unit_f64")]
+   pub radius: f64,
+   #[serde(skip_serializing_if = "is_default")]
+   pub focus: Option<[f64; 2]>,
+   #[serde(skip_serializing_if = "is_default")]
+   pub stops: Vec<ColorStop>,
+}
+
+// Hatching is laid out on the canvas, not in diagram coordinates, so that spacing is consistent
+// in print.  Angle is in degrees, anticlockwise from horizontal, and spacing scales the standard
+// separation.  Cross-hatching adds a second set of lines at right angles.  Hatching that cannot be
+// laid out, such as with zero spacing, is drawn as a solid fill.
+#[derive(Serialize, Debug, Clone, DefaultFromSerde, PartialEq)]
+pub struct HatchChoices {
+   #[serde(skip_serializing_if = "is_default_hatch_angle", default = "default_hatch_angle")]
+   pub angle: f64,
+   #[serde(skip_serializing_if = "is_default_unit_f64", default = "default_unit_f64")]
+   pub spacing: f64,
+   #[serde(skip_serializing_if = "is_default")]
+   pub cross: bool,
+}
+
+#[must_use]
+pub const fn default_hatch_angle() -> f64 {
+   45.0
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+#[must_use]
+pub fn is_default_hatch_angle(v: &f64) -> bool {
+   is_near_float(*v, default_hatch_angle())
+}
+
+pub struct HatchParameters {
+   pub spacing: f64,
+   pub line_width: f64,
+}
+
+impl HatchChoices {
+   // Diagram-specific simple values, in the manner of light-line dashes.
+   const HATCH_SEPARATION: f64 = 6.0;
+
+   // Spacing must be positive and the angle finite for the lines to be laid out.
+   #[must_use]
+   pub fn is_valid(&self) -> bool {
+      self.spacing.is_finite() && self.spacing > 0.0 && self.angle.is_finite()
+   }
+
+   #[must_use]
+   pub fn to_hatch_parameters(&self, diagram_choices: &DiagramChoices) -> HatchParameters {
+      HatchParameters {
+         spacing: Self::HATCH_SEPARATION * self.spacing * diagram_choices.annotation_linear_scale,
+         line_width: diagram_choices.line_width * diagram_choices.annotation_linear_scale,
+      }
+   }
+}
+
+// Solid fills use the fill colour, and hatching draws lines in the fill colour.  Gradients take
+// their colours from the stops.
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub enum FillPattern {
+   #[default]
+   Solid,
+   Linear(LinearGradient),
+   Radial(RadialGradient),
+   Hatch(HatchChoices),
+}
+
+// Default opacity is 0.0, which disables.  Otherwise opacity multiplies the alpha of the fill
+// colours.
 #[derive(Serialize, Debug, Clone, DefaultFromSerde, PartialEq)]
 pub struct FillChoices {
    #[serde(skip_serializing_if = "is_default")]
    pub color: ColorChoice,
    #[serde(skip_serializing_if = "is_default")]
    pub opacity: f64,
+   #[serde(skip_serializing_if = "is_default")]
+   pub pattern: FillPattern,
+}
+
+// Miter limit is the ratio of miter length to line width, beyond which miter joins are
+// beveled.  The default is that of Cairo and SVG.
+#[must_use]
+pub const fn default_miter_limit() -> f64 {
+   10.0
+}
+
+// Larger miter limits are clamped on rendering.  Those well above are reserved, since backends
+// may use them to mark structure in their output.
+pub const MAX_MITER_LIMIT: f64 = 100_000.0;
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+#[must_use]
+pub fn is_default_miter_limit(v: &f64) -> bool {
+   (*v - default_miter_limit()).abs() < 0.0001
+}
+
+// Markers at the start and end of open paths, and at interior vertices.  A path without
+// interior vertices, such as a single curve, has its mid marker at the middle of its range.  The
+// size scales the standard marker, which is proportional to line width.
+#[derive(Serialize, Debug, Clone, DefaultFromSerde, PartialEq)]
+pub struct MarkerChoices {
+   #[serde(skip_serializing_if = "is_default")]
+   pub start: MarkerChoice,
+   #[serde(skip_serializing_if = "is_default")]
+   pub mid: MarkerChoice,
+   #[serde(skip_serializing_if = "is_default")]
+   pub end: MarkerChoice,
+   #[serde(skip_serializing_if = "is_default_unit_f64", default = "default_unit_f64")]
+   pub size: f64,
 }
 
 #[derive(Serialize, Debug, Clone, DefaultFromSerde, PartialEq)]
@@ -47,10 +178,70 @@ pub struct PathChoices {
    pub color: ColorChoice,
    #[serde(skip_serializing_if = "is_default")]
    pub fill_choices: FillChoices,
+   #[serde(skip_serializing_if = "is_default")]
+   pub line_cap: LineCapChoice,
+   #[serde(skip_serializing_if = "is_default")]
+   pub line_join: LineJoinChoice,
+   #[serde(skip_serializing_if = "is_default_miter_limit", default = "default_miter_limit")]
+   pub miter_limit: f64,
+   #[serde(skip_serializing_if = "is_default")]
+   pub markers: MarkerChoices,
+}
+
+fn inherit_choice<T: Default + PartialEq + Clone>(own: &T, inherited: &T) -> T {
+   if is_default(own) {
+      inherited.clone()
+   } else {
+      own.clone()
+   }
+}
+
+impl FillChoices {
+   // Inherited field by field, so that, for example, a group can set a fill colour without
+   // filling all of its children.
+   #[must_use]
+   pub fn inherit_from(&self, inherited: &Self) -> Self {
+      Self {
+         color: inherit_choice(&self.color, &inherited.color),
+         opacity: inherit_choice(&self.opacity, &inherited.opacity),
+         pattern: inherit_choice(&self.pattern, &inherited.pattern),
+      }
+   }
+}
+
+impl PathChoices {
+   // Each unset (default) choice is replaced by that inherited.
+   #[must_use]
+   pub fn inherit_from(&self, inherited: &Self) -> Self {
+      Self {
+         line_choice: inherit_choice(&self.line_choice, &inherited.line_choice),
+         color: inherit_choice(&self.color, &inherited.color),
+         fill_choices: self.fill_choices.inherit_from(&inherited.fill_choices),
+         line_cap: inherit_choice(&self.line_cap, &inherited.line_cap),
+         line_join: inherit_choice(&self.line_join, &inherited.line_join),
+         miter_limit: if is_default_miter_limit(&self.miter_limit) {
+            inherited.miter_limit
+         } else {
+            self.miter_limit
+         },
+         markers: inherit_choice(&self.markers, &inherited.markers),
+      }
+   }
+
+   // Miter limit for rendering.  Limits beyond the maximum are clamped, and NaN is taken as
+   // the default.
+   #[must_use]
+   pub const fn clamped_miter_limit(&self) -> f64 {
+      if self.miter_limit.is_nan() {
+         default_miter_limit()
+      } else {
+         self.miter_limit.min(MAX_MITER_LIMIT)
+      }
+   }
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Serialize, Default, PartialEq)]
+#[derive(Debug, Serialize, Default, Clone, PartialEq)]
 pub struct Strokeable<T: Default + PartialEq> {
    #[serde(skip_serializing_if = "is_default")]
    pub path: T,
@@ -62,7 +253,7 @@ pub struct Strokeable<T: Default + PartialEq> {
 // sets.
 //
 // Outer-product of an optional set of offsets and a set of lines.
-#[derive(Debug, Serialize, DefaultFromSerde, PartialEq)]
+#[derive(Debug, Serialize, DefaultFromSerde, Clone, PartialEq)]
 pub struct LinesSetSet {
    #[serde(skip_serializing_if = "is_default")]
    pub coords: Vec<([f64; 2], [f64; 2])>,
@@ -71,7 +262,7 @@ pub struct LinesSetSet {
    pub offsets: Option<Vec<[f64; 2]>>,
 }
 
-#[derive(Debug, Serialize, DefaultFromSerde, PartialEq)]
+#[derive(Debug, Serialize, DefaultFromSerde, Clone, PartialEq)]
 pub struct CirclesSet {
    #[serde(skip_serializing_if = "is_default")]
    pub radius: f64,
@@ -79,7 +270,10 @@ pub struct CirclesSet {
    pub centers: Vec<[f64; 2]>,
 }
 
-#[derive(Debug, Serialize, DefaultFromSerde, PartialEq)]
+// The size scales the diagram point size.  A custom marker is a path in units of the marker
+// radius, centred on the origin with y upwards, and so is not distorted by the canvas scaling.
+// Its path choices, such as fill, apply, with the colour inherited from the points drawable.
+#[derive(Debug, Serialize, DefaultFromSerde, Clone, PartialEq)]
 pub struct PointsDrawable {
    #[serde(skip_serializing_if = "is_default")]
    pub point_choice: PointChoice,
@@ -87,10 +281,15 @@ pub struct PointsDrawable {
    pub color_choice: ColorChoice,
    #[serde(skip_serializing_if = "is_default")]
    pub centers: Vec<[f64; 2]>,
+   #[serde(skip_serializing_if = "is_default_unit_f64", default = "default_unit_f64")]
+   pub size: f64,
+   #[serde(skip_serializing_if = "is_default")]
+   pub custom_marker: SegmentSequence,
 }
 
-// Type of markup.  For now, Auto means Plain.
-#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+// Type of markup.  Auto detects Pango markup, and otherwise converts maths-lite syntax to Pango
+// markup, as described in the `markup` module.
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
 pub enum MarkupChoice {
    #[default]
    Auto,
@@ -98,7 +297,7 @@ pub enum MarkupChoice {
    Pango,
 }
 
-#[derive(Debug, Serialize, DefaultFromSerde, PartialEq)]
+#[derive(Debug, Serialize, DefaultFromSerde, Clone, PartialEq)]
 pub struct TextSingle {
    #[serde(skip_serializing_if = "is_default")]
    pub content: String,
@@ -106,9 +305,12 @@ pub struct TextSingle {
    pub location: [f64; 2],
    #[serde(skip_serializing_if = "is_default")]
    pub markup: MarkupChoice,
+   // Anticlockwise, in degrees, about the anchor location.
+   #[serde(skip_serializing_if = "is_default")]
+   pub rotation: f64,
 }
 
-#[derive(Debug, Serialize, DefaultFromSerde, PartialEq)]
+#[derive(Debug, Serialize, DefaultFromSerde, Clone, PartialEq)]
 pub struct TextDrawable {
    #[serde(skip_serializing_if = "is_default")]
    pub size_choice: TextSizeChoice,
@@ -119,10 +321,36 @@ pub struct TextDrawable {
    #[serde(skip_serializing_if = "is_default")]
    pub color_choice: ColorChoice,
    #[serde(skip_serializing_if = "is_default")]
+   pub font_choices: FontChoices,
+   #[serde(skip_serializing_if = "is_default")]
+   pub paragraph_choices: ParagraphChoices,
+   #[serde(skip_serializing_if = "is_default")]
    pub texts: Vec<TextSingle>,
 }
 
-#[derive(Debug, Serialize, DefaultFromSerde, PartialEq)]
+// Text laid along a path, such as an edge label that follows a curved connector.  Clusters of
+// characters are placed by arc length on the canvas, and rotated to follow the path.  The
+// horizontal anchor places the text at the start, middle or end of the path, and the vertical
+// anchor places it relative to the path, as for a horizontal path.  Markup is not supported.
+#[derive(Debug, Serialize, DefaultFromSerde, Clone, PartialEq)]
+pub struct TextPathDrawable {
+   #[serde(skip_serializing_if = "is_default")]
+   pub size_choice: TextSizeChoice,
+   #[serde(skip_serializing_if = "is_default")]
+   pub offset_choice: TextOffsetChoice,
+   #[serde(skip_serializing_if = "is_default")]
+   pub anchor_choice: TextAnchorChoice,
+   #[serde(skip_serializing_if = "is_default")]
+   pub color_choice: ColorChoice,
+   #[serde(skip_serializing_if = "is_default")]
+   pub font_choices: FontChoices,
+   #[serde(skip_serializing_if = "is_default")]
+   pub content: String,
+   #[serde(skip_serializing_if = "is_default")]
+   pub segments: Vec<OneOfSegment>,
+}
+
+#[derive(Debug, Serialize, DefaultFromSerde, Clone, PartialEq)]
 pub struct SegmentSequence {
    #[serde(skip_serializing_if = "is_default")]
    pub completion: PathCompletion,
@@ -132,7 +360,142 @@ pub struct SegmentSequence {
    pub segments: Vec<OneOfSegment>,
 }
 
-#[derive(Serialize, Debug, Default, PartialEq)]
+// Rectangles are specified as [left, bottom, right, top], in the manner of the axes range.
+// Paths are always closed.
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub enum ClipRegion {
+   #[default]
+   Unclipped,
+   Rectangle([f64; 4]),
+   Path(Vec<OneOfSegment>),
+}
+
+// Drawables are limited to the clip region.  They are sorted by layer among themselves, and
+// are drawn together at the layer of the clipped drawable.
+#[derive(Serialize, Debug, DefaultFromSerde, Clone, PartialEq)]
+pub struct ClippedDrawables {
+   #[serde(skip_serializing_if = "is_default")]
+   pub clip: ClipRegion,
+   #[serde(skip_serializing_if = "is_default")]
+   pub drawables: Vec<QualifiedDrawable>,
+}
+
+// Children are placed by the local transform, applied to their coordinates before those of
+// any enclosing group, in the manner of the arc transform and center.  Group style choices are
+// inherited by children whose own choices are unset.  Children are sorted by layer among
+// themselves, and are drawn together at the layer of the group.  The id, CSS class, hyperlink,
+// tooltip and alternative text are written by backends that support them, such as to SVG.  The
+// alternative text describes the group as a whole for screen readers.
+#[derive(Serialize, Debug, DefaultFromSerde, Clone, PartialEq)]
+pub struct GroupDrawable {
+   #[serde(skip_serializing_if = "is_default")]
+   pub id: Option<String>,
+   #[serde(skip_serializing_if = "is_default")]
+   pub class: Option<String>,
+   #[serde(skip_serializing_if = "is_default")]
+   pub link: Option<String>,
+   #[serde(skip_serializing_if = "is_default")]
+   pub tooltip: Option<String>,
+   #[serde(skip_serializing_if = "is_default")]
+   pub alt_text: Option<String>,
+   #[serde(
+      skip_serializing_if = "is_default_identity_transform",
+      default = "default_identity_transform"
+   )]
+   pub transform: [f64; 4],
+   #[serde(skip_serializing_if = "is_default")]
+   pub offset: [f64; 2],
+   #[serde(skip_serializing_if = "is_default")]
+   pub style: PathChoices,
+   #[serde(skip_serializing_if = "is_default")]
+   pub drawables: Vec<QualifiedDrawable>,
+}
+
+#[must_use]
+pub const fn default_identity_transform() -> [f64; 4] {
+   [1.0, 0.0, 0.0, 1.0]
+}
+
+#[must_use]
+pub fn is_default_identity_transform(v: &[f64; 4]) -> bool {
+   v.iter().zip(default_identity_transform()).all(|(a, b)| is_near_float(*a, b))
+}
+
+impl GroupDrawable {
+   // Children with the group style applied, borrowed if the style is unset.
+   #[must_use]
+   pub fn styled_drawables(&self) -> Cow<'_, [QualifiedDrawable]> {
+      if is_default(&self.style) {
+         return Cow::Borrowed(&self.drawables);
+      }
+      Cow::Owned(
+         self
+            .drawables
+            .iter()
+            .map(|q| QualifiedDrawable {
+               layer: q.layer,
+               drawable: q.drawable.with_inherited_style(&self.style),
+            })
+            .collect(),
+      )
+   }
+}
+
+// PNG content, as data or as a file path.  Data is serialized in base64.
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub enum ImageSource {
+   #[default]
+   Unset,
+   PngData(#[serde(serialize_with = "serialize_base64")] Vec<u8>),
+   PngFile(String),
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+   b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn serialize_base64<S: serde::Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+   let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+   for chunk in data.chunks(3) {
+      let bits =
+         chunk.iter().enumerate().fold(0_u32, |acc, (i, b)| acc | u32::from(*b) << (16 - 8 * i));
+      for i in 0..4 {
+         if i <= chunk.len() {
+            encoded.push(char::from(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize]));
+         } else {
+            encoded.push('=');
+         }
+      }
+   }
+   serializer.serialize_str(&encoded)
+}
+
+// Interpolation when the image is scaled.  This is a hint, since not all outputs support it.
+// For example, the SVG surface of Cairo 1.16 leaves scaling to the viewer.
+#[derive(Serialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ImageInterpolation {
+   #[default]
+   Smooth,
+   Nearest,
+}
+
+// Raster image, stretched to fill the rectangle, which is [left, bottom, right, top] in
+// diagram coordinates.  The top row of the image is at the top of the rectangle.  The
+// alternative text is written as for groups, by backends that support it.
+#[derive(Serialize, Debug, DefaultFromSerde, Clone, PartialEq)]
+pub struct ImageDrawable {
+   #[serde(skip_serializing_if = "is_default")]
+   pub source: ImageSource,
+   #[serde(skip_serializing_if = "is_default")]
+   pub rectangle: [f64; 4],
+   #[serde(skip_serializing_if = "is_default")]
+   pub interpolation: ImageInterpolation,
+   #[serde(skip_serializing_if = "is_default_unit_f64", default = "default_unit_f64")]
+   pub opacity: f64,
+   #[serde(skip_serializing_if = "is_default")]
+   pub alt_text: Option<String>,
+}
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
 pub enum OneOfDrawable {
    #[default]
    Neither,
@@ -140,16 +503,51 @@ pub enum OneOfDrawable {
    Cubic(Strokeable<CubicPath>),
    Hyperbolic(Strokeable<HyperbolicPath>),
    Polyline(Strokeable<PolylinePath>),
+   RatCubic(Strokeable<RatCubicHomogWeighted>),
    Lines(Strokeable<LinesSetSet>),
    Circles(Strokeable<CirclesSet>),
    Points(PointsDrawable),
    Text(TextDrawable),
+   TextPath(TextPathDrawable),
    SegmentSequence(SegmentSequence),
+   Clipped(ClippedDrawables),
+   Group(GroupDrawable),
+   Image(ImageDrawable),
+}
+
+impl OneOfDrawable {
+   // Unset choices are taken from the style.  Nested groups inherit into their own style.
+   #[must_use]
+   pub fn with_inherited_style(&self, style: &PathChoices) -> Self {
+      let mut result = self.clone();
+      match &mut result {
+         Self::Arc(d) => d.path_choices = d.path_choices.inherit_from(style),
+         Self::Cubic(d) => d.path_choices = d.path_choices.inherit_from(style),
+         Self::Hyperbolic(d) => d.path_choices = d.path_choices.inherit_from(style),
+         Self::Polyline(d) => d.path_choices = d.path_choices.inherit_from(style),
+         Self::RatCubic(d) => d.path_choices = d.path_choices.inherit_from(style),
+         Self::Lines(d) => d.path_choices = d.path_choices.inherit_from(style),
+         Self::Circles(d) => d.path_choices = d.path_choices.inherit_from(style),
+         Self::Points(d) => d.color_choice = inherit_choice(&d.color_choice, &style.color),
+         Self::Text(d) => d.color_choice = inherit_choice(&d.color_choice, &style.color),
+         Self::TextPath(d) => d.color_choice = inherit_choice(&d.color_choice, &style.color),
+         Self::SegmentSequence(d) => d.path_choices = d.path_choices.inherit_from(style),
+         Self::Clipped(d) => {
+            for q in &mut d.drawables {
+               q.drawable = q.drawable.with_inherited_style(style);
+            }
+         }
+         Self::Group(d) => d.style = d.style.inherit_from(style),
+         Self::Image(_) | Self::Neither => {}
+      }
+      result
+   }
 }
 
 // Layer is logically a cross-drawable / path choice, but we want to make it trivial to be able
-// to sort drawables by layer before further processing.
-#[derive(Debug, Serialize, DefaultFromSerde, PartialEq)]
+// to sort drawables by layer before further processing.  Sorting is stable, so drawables within
+// a layer keep their order.  Layers may be named, and hidden, in the diagram layer choices.
+#[derive(Debug, Serialize, DefaultFromSerde, Clone, PartialEq)]
 pub struct QualifiedDrawable {
    #[serde(skip_serializing_if = "is_default")]
    pub layer: i32,
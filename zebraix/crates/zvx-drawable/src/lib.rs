@@ -13,14 +13,23 @@
 // limitations under the License.
 
 pub mod choices;
+pub mod estimator;
+pub mod extension;
 pub mod interface;
 pub mod kinds;
+pub mod markup;
+pub mod palette;
 
 pub use crate::choices::{
-   ColorChoice, LineChoice, PathCompletion, PointChoice, TextAnchorChoice, TextAnchorHorizontal,
-   TextAnchorVertical, TextOffsetChoice, TextSizeChoice,
+   ColorChoice, FontChoices, FontSlantChoice, FontStretchChoice, FontWeightChoice, LayerChoices,
+   LineCapChoice, LineChoice, LineJoinChoice, LineStyle, MarkerChoice, NamedLayer,
+   ParagraphChoices, PathCompletion, PointChoice, TextAlignChoice, TextAnchorChoice,
+   TextAnchorHorizontal, TextAnchorVertical, TextOffsetChoice, TextSizeChoice,
 };
 pub use crate::kinds::{
-   CirclesSet, FillChoices, LinesSetSet, MarkupChoice, OneOfDrawable, PathChoices, PointsDrawable,
-   QualifiedDrawable, SegmentSequence, Strokeable, TextDrawable, TextSingle,
+   CirclesSet, ClipRegion, ClippedDrawables, ColorStop, FillChoices, FillPattern, GroupDrawable,
+   HatchChoices, ImageDrawable, ImageInterpolation, ImageSource, LinearGradient, LinesSetSet,
+   MarkerChoices, MarkupChoice, OneOfDrawable, PathChoices, PointsDrawable, QualifiedDrawable,
+   RadialGradient, SegmentSequence, Strokeable, TextDrawable, TextPathDrawable, TextSingle,
 };
+pub use crate::palette::{ColorRole, Palette, ThemeChoice};
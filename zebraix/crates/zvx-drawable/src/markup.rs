@@ -0,0 +1,330 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Markup resolution for `MarkupChoice::Auto`, shared by backends so that text is interpreted
+// consistently.
+//
+// Content is taken as Pango markup if it contains a recognized tag or entity, and is well
+// formed.  Malformed markup is plain text.  Otherwise, if it uses maths-lite syntax, it is
+// converted to Pango markup.  Maths-lite syntax is:
+//
+// * `_` and `^` for subscript and superscript.  These apply to the next character, command, or
+//   group in braces, as in `x_1^2` and `x_{ij}`.
+// * Backslash commands for Greek letters, such as `\alpha` and `\Omega`.  Unknown commands are
+//   kept literally.
+// * Backslash escapes for special characters, as in `\_`, `\^`, `\{`, `\}` and `\\`.
+//
+// Anything else is plain text.
+
+#[cfg(test)]
+mod tests;
+
+use std::borrow::Cow;
+
+const PANGO_TAGS: [&str; 11] =
+   ["b", "big", "i", "markup", "s", "small", "span", "sub", "sup", "tt", "u"];
+const PANGO_ENTITIES: [&str; 6] = ["&amp;", "&lt;", "&gt;", "&quot;", "&apos;", "&#"];
+// Attributes accepted by Pango on `span`.  Other tags take no attributes.
+const SPAN_ATTRIBUTES: [&str; 44] = [
+   "alpha",
+   "allow_breaks",
+   "background",
+   "background_alpha",
+   "baseline_shift",
+   "bgalpha",
+   "bgcolor",
+   "color",
+   "face",
+   "fallback",
+   "fgalpha",
+   "fgcolor",
+   "font",
+   "font_desc",
+   "font_family",
+   "font_features",
+   "font_scale",
+   "font_size",
+   "font_stretch",
+   "font_style",
+   "font_variant",
+   "font_weight",
+   "foreground",
+   "gravity",
+   "gravity_hint",
+   "insert_hyphens",
+   "lang",
+   "letter_spacing",
+   "line_height",
+   "overline",
+   "overline_color",
+   "rise",
+   "segment",
+   "show",
+   "size",
+   "stretch",
+   "strikethrough",
+   "strikethrough_color",
+   "style",
+   "text_transform",
+   "underline",
+   "underline_color",
+   "variant",
+   "weight",
+];
+
+const GREEK_LETTERS: [(&str, char); 36] = [
+   ("alpha", 'α'),
+   ("beta", 'β'),
+   ("gamma", 'γ'),
+   ("delta", 'δ'),
+   ("epsilon", 'ε'),
+   ("zeta", 'ζ'),
+   ("eta", 'η'),
+   ("theta", 'θ'),
+   ("iota", 'ι'),
+   ("kappa", 'κ'),
+   ("lambda", 'λ'),
+   ("mu", 'μ'),
+   ("nu", 'ν'),
+   ("xi", 'ξ'),
+   ("omicron", 'ο'),
+   ("pi", 'π'),
+   ("rho", 'ρ'),
+   ("sigma", 'σ'),
+   ("tau", 'τ'),
+   ("upsilon", 'υ'),
+   ("phi", 'φ'),
+   ("chi", 'χ'),
+   ("psi", 'ψ'),
+   ("omega", 'ω'),
+   ("varepsilon", 'ϵ'),
+   ("vartheta", 'ϑ'),
+   ("varphi", 'ϕ'),
+   ("Gamma", 'Γ'),
+   ("Delta", 'Δ'),
+   ("Theta", 'Θ'),
+   ("Lambda", 'Λ'),
+   ("Xi", 'Ξ'),
+   ("Pi", 'Π'),
+   ("Sigma", 'Σ'),
+   ("Phi", 'Φ'),
+   ("Omega", 'Ω'),
+];
+
+fn greek_letter(name: &str) -> Option<char> {
+   GREEK_LETTERS.iter().find(|(n, _)| *n == name).map(|(_, c)| *c)
+}
+
+// True if the content contains a recognized Pango tag, such as `<sub>` or `</b>`, or an entity.
+#[must_use]
+pub fn is_pango_markup(content: &str) -> bool {
+   if PANGO_ENTITIES.iter().any(|entity| content.contains(entity)) {
+      return true;
+   }
+   content.match_indices('<').any(|(i, _)| {
+      let rest = content[i + 1..].strip_prefix('/').unwrap_or_else(|| &content[i + 1..]);
+      let name_end = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+      PANGO_TAGS.contains(&&rest[..name_end]) && rest[name_end..].starts_with(['>', ' '])
+   })
+}
+
+// Length of the entity or character reference at the start of the content, if valid.
+fn entity_length(content: &str) -> Option<usize> {
+   let end = content.find(';')?;
+   let name = &content[1..end];
+   let code_point = name
+      .strip_prefix("#x")
+      .map(|hex| u32::from_str_radix(hex, 16))
+      .or_else(|| name.strip_prefix('#').map(str::parse::<u32>));
+   let valid = code_point.map_or_else(
+      || ["amp", "lt", "gt", "quot", "apos"].contains(&name),
+      |parsed| parsed.ok().and_then(char::from_u32).is_some_and(|c| c != '\0'),
+   );
+   valid.then_some(end + 1)
+}
+
+// Length of the attribute list of an opening tag, up to and including the closing `>` or
+// `/>`, if all attributes are accepted by the tag and properly quoted.
+fn attributes_length(tag: &str, content: &str) -> Option<usize> {
+   let mut rest = content;
+   loop {
+      let trimmed = rest.trim_start();
+      if trimmed.starts_with('>') || trimmed.starts_with("/>") {
+         let close_length = if trimmed.starts_with('>') { 1 } else { 2 };
+         return Some(content.len() - trimmed.len() + close_length);
+      }
+      let name_end = trimmed.find(|c: char| !(c.is_ascii_alphabetic() || c == '_'))?;
+      if (tag != "span") || !SPAN_ATTRIBUTES.contains(&&trimmed[..name_end]) {
+         return None;
+      }
+      let value = trimmed[name_end..].trim_start().strip_prefix('=')?.trim_start();
+      let quote = value.chars().next().filter(|c| (*c == '"') || (*c == '\''))?;
+      let value_end = value[1..].find(quote)? + 1;
+      let mut escaped = &value[1..value_end];
+      if escaped.contains('<') {
+         return None;
+      }
+      while let Some(i) = escaped.find('&') {
+         escaped = &escaped[i + entity_length(&escaped[i..])?..];
+      }
+      rest = &value[value_end + 1..];
+   }
+}
+
+// True if Pango can parse the markup: tags are recognized, take only accepted attributes, and
+// are properly nested, and `&` only starts an entity.  Attribute values are not checked.
+#[must_use]
+pub fn is_well_formed_markup(content: &str) -> bool {
+   let mut open_tags: Vec<&str> = Vec::new();
+   let mut i = 0;
+   while let Some(offset) = content[i..].find(['<', '&']) {
+      i += offset;
+      let rest = &content[i..];
+      if rest.starts_with('&') {
+         match entity_length(rest) {
+            Some(length) => i += length,
+            None => return false,
+         }
+         continue;
+      }
+      let closing = rest[1..].starts_with('/');
+      let name_start = if closing { 2 } else { 1 };
+      let name_end = rest[name_start..]
+         .find(|c: char| !c.is_ascii_alphabetic())
+         .map_or(rest.len(), |end| end + name_start);
+      let name = &rest[name_start..name_end];
+      if !PANGO_TAGS.contains(&name) {
+         return false;
+      }
+      if closing {
+         let after = rest[name_end..].trim_start();
+         if !after.starts_with('>') || (open_tags.pop() != Some(name)) {
+            return false;
+         }
+         i += rest.len() - after.len() + 1;
+      } else {
+         let Some(length) = attributes_length(name, &rest[name_end..]) else {
+            return false;
+         };
+         if !rest[name_end..name_end + length].ends_with("/>") {
+            open_tags.push(name);
+         }
+         i += name_end + length;
+      }
+   }
+   open_tags.is_empty()
+}
+
+// True if the content contains maths-lite scripts or backslash commands.
+#[must_use]
+pub fn is_maths_lite(content: &str) -> bool {
+   content.contains(['_', '^', '\\'])
+}
+
+fn push_escaped(markup: &mut String, c: char) {
+   match c {
+      '&' => markup.push_str("&amp;"),
+      '<' => markup.push_str("&lt;"),
+      '>' => markup.push_str("&gt;"),
+      _ => markup.push(c),
+   }
+}
+
+struct MathsLiteConverter<'a> {
+   chars: std::iter::Peekable<std::str::Chars<'a>>,
+   markup: String,
+}
+
+impl MathsLiteConverter<'_> {
+   // Converts a backslash command or escape, the backslash having been consumed.
+   fn convert_command(&mut self) {
+      let mut name = String::new();
+      while let Some(c) = self.chars.next_if(char::is_ascii_alphabetic) {
+         name.push(c);
+      }
+      if name.is_empty() {
+         // Escaped character, or a trailing backslash.
+         push_escaped(&mut self.markup, self.chars.next().unwrap_or('\\'));
+      } else if let Some(letter) = greek_letter(&name) {
+         self.markup.push(letter);
+      } else {
+         self.markup.push('\\');
+         self.markup.push_str(&name);
+      }
+   }
+
+   // Converts the argument of a script: a group, command or single character.
+   fn convert_argument(&mut self) {
+      match self.chars.next() {
+         Some('{') => {
+            self.convert_until_close();
+         }
+         Some('\\') => self.convert_command(),
+         Some(c) => push_escaped(&mut self.markup, c),
+         None => {}
+      }
+   }
+
+   fn convert_script(&mut self, tag: &str) {
+      self.markup.push('<');
+      self.markup.push_str(tag);
+      self.markup.push('>');
+      self.convert_argument();
+      self.markup.push_str("</");
+      self.markup.push_str(tag);
+      self.markup.push('>');
+   }
+
+   // Converts up to the closing brace of a group, or to the end.  Returns true if closed.
+   fn convert_until_close(&mut self) -> bool {
+      while let Some(c) = self.chars.next() {
+         match c {
+            '}' => return true,
+            '{' => {
+               self.convert_until_close();
+            }
+            '_' => self.convert_script("sub"),
+            '^' => self.convert_script("sup"),
+            '\\' => self.convert_command(),
+            _ => push_escaped(&mut self.markup, c),
+         }
+      }
+      false
+   }
+}
+
+// Converts maths-lite syntax to Pango markup.
+#[must_use]
+pub fn maths_lite_to_markup(content: &str) -> String {
+   let mut converter =
+      MathsLiteConverter { chars: content.chars().peekable(), markup: String::new() };
+   // Unmatched closing braces are kept.
+   while converter.convert_until_close() {
+      converter.markup.push('}');
+   }
+   converter.markup
+}
+
+// Resolves content under `MarkupChoice::Auto`.  Returns Pango markup, or None if the content is
+// plain text, including if it is malformed markup.
+#[must_use]
+pub fn auto_markup(content: &str) -> Option<Cow<'_, str>> {
+   if is_pango_markup(content) {
+      is_well_formed_markup(content).then_some(Cow::Borrowed(content))
+   } else if is_maths_lite(content) {
+      Some(Cow::Owned(maths_lite_to_markup(content)))
+   } else {
+      None
+   }
+}
@@ -0,0 +1,105 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[test]
+fn pango_detection_test() {
+   // Nested spans and tags.
+   assert!(is_pango_markup("<span foreground=\"red\">a <b>bold</b> word</span>"));
+   assert!(is_pango_markup("<span size=\"small\"><span font_family=\"mono\">x</span></span>"));
+   // Detection is by tag, so unbalanced tags are still markup.
+   assert!(is_pango_markup("<b>unclosed"));
+   assert!(is_pango_markup("stray</i>"));
+   // Unknown tags and comparisons are not.
+   assert!(!is_pango_markup("<bold>x</bold>"));
+   assert!(!is_pango_markup("a < b > c"));
+   assert!(!is_pango_markup("x<sub"));
+   // Entities.
+   assert!(is_pango_markup("Salt &amp; pepper"));
+   assert!(is_pango_markup("&#x3B1;"));
+   assert!(!is_pango_markup("Salt & pepper"));
+}
+
+#[test]
+fn nested_scripts_test() {
+   assert_eq!(maths_lite_to_markup("x_{i_j}^2"), "x<sub>i<sub>j</sub></sub><sup>2</sup>");
+   assert_eq!(maths_lite_to_markup("e^{-x^{2}}"), "e<sup>-x<sup>2</sup></sup>");
+   // Plain groups are removed.
+   assert_eq!(maths_lite_to_markup("{a_{b}}c"), "a<sub>b</sub>c");
+   assert_eq!(maths_lite_to_markup("\\alpha_\\beta"), "α<sub>β</sub>");
+}
+
+#[test]
+fn unbalanced_braces_test() {
+   // Unclosed groups end with the content, and closing tags are still written.
+   assert_eq!(maths_lite_to_markup("x_{ab"), "x<sub>ab</sub>");
+   assert_eq!(maths_lite_to_markup("x^{a_{b"), "x<sup>a<sub>b</sub></sup>");
+   // Unmatched closing braces are kept.
+   assert_eq!(maths_lite_to_markup("a}b_1"), "a}b<sub>1</sub>");
+   assert_eq!(maths_lite_to_markup("}}"), "}}");
+   // Scripts without arguments are empty.
+   assert_eq!(maths_lite_to_markup("x_"), "x<sub></sub>");
+}
+
+#[test]
+fn escaping_test() {
+   assert_eq!(maths_lite_to_markup("a < b_1 & c > d"), "a &lt; b<sub>1</sub> &amp; c &gt; d");
+   assert_eq!(maths_lite_to_markup("x_<"), "x<sub>&lt;</sub>");
+   assert_eq!(maths_lite_to_markup("\\<\\&"), "&lt;&amp;");
+   // Backslash escapes of special characters.
+   assert_eq!(maths_lite_to_markup("a\\_b\\^c\\{d\\}\\\\"), "a_b^c{d}\\");
+   // Unknown commands and trailing backslashes are kept.
+   assert_eq!(maths_lite_to_markup("\\foo_1"), "\\foo<sub>1</sub>");
+   assert_eq!(maths_lite_to_markup("a\\"), "a\\");
+}
+
+#[test]
+fn auto_markup_test() {
+   // Pango markup is used as is, without conversion of scripts.
+   assert_eq!(auto_markup("<i>x_1</i>"), Some(Cow::Borrowed("<i>x_1</i>")));
+   assert_eq!(auto_markup("R&amp;D_1").as_deref(), Some("R&amp;D_1"));
+   assert_eq!(auto_markup("R&D_1").as_deref(), Some("R&amp;D<sub>1</sub>"));
+   assert_eq!(auto_markup("Plain & simple <text>"), None);
+   // Malformed markup is detected, but is plain text, as Pango cannot parse it.
+   assert_eq!(auto_markup("<b>unclosed"), None);
+   assert_eq!(auto_markup("<b>x_1"), None);
+   assert_eq!(auto_markup("stray</i>"), None);
+   assert_eq!(auto_markup("Salt &amp; pepper & more"), None);
+}
+
+#[test]
+fn well_formed_markup_test() {
+   assert!(is_well_formed_markup("<span foreground=\"red\">a <b>bold</b> word</span>"));
+   assert!(is_well_formed_markup("<span size='small' font_family=\"mono\">x</span>"));
+   assert!(is_well_formed_markup("a &lt; b &#x3B1; &#945;<sub >1</sub >"));
+   assert!(is_well_formed_markup("<span foreground=\"red\"/>a > b"));
+   assert!(is_well_formed_markup("<span size = \"small\"weight=\"bold\">x</span>"));
+   // Unbalanced or crossed tags.
+   assert!(!is_well_formed_markup("<b>unclosed"));
+   assert!(!is_well_formed_markup("<b><i>x</b></i>"));
+   assert!(!is_well_formed_markup("x<sub"));
+   // Unknown tags and attributes, and attributes on tags that take none.
+   assert!(!is_well_formed_markup("<bold>x</bold>"));
+   assert!(!is_well_formed_markup("<span colour=\"red\">x</span>"));
+   assert!(!is_well_formed_markup("<b weight=\"bold\">x</b>"));
+   // Unquoted and unterminated attribute values.
+   assert!(!is_well_formed_markup("<span size=small>x</span>"));
+   assert!(!is_well_formed_markup("<span size=\"small>x</span>"));
+   // Bare ampersands and unknown entities.
+   assert!(!is_well_formed_markup("R&D"));
+   assert!(!is_well_formed_markup("&nbsp;"));
+   assert!(!is_well_formed_markup("&#0;"));
+   assert!(!is_well_formed_markup("<span lang=\"R&D\">x</span>"));
+}
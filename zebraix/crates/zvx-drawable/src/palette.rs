@@ -0,0 +1,180 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::choices::ColorChoice;
+use serde::{Deserialize, Serialize};
+
+// Semantic colour roles, resolved through the diagram palette at render time.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ColorRole {
+   #[default]
+   Foreground,
+   Background,
+   Axis,
+   Grid,
+   Text,
+   Primary,
+   Secondary,
+   Accent,
+}
+
+// Mapping from colour roles to concrete colours.  Unset colours (DefaultBlack) take the
+// foreground colour.  If greyscale is set, all colours, including those given explicitly, are
+// converted to grey on resolution.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Palette {
+   pub foreground: ColorChoice,
+   pub background: ColorChoice,
+   pub axis: ColorChoice,
+   pub grid: ColorChoice,
+   pub text: ColorChoice,
+   pub primary: ColorChoice,
+   pub secondary: ColorChoice,
+   pub accent: ColorChoice,
+   pub greyscale: bool,
+}
+
+// Predefined palettes, or a custom palette.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub enum ThemeChoice {
+   #[default]
+   Light,
+   Dark,
+   // Okabe-Ito colours, distinguishable under common forms of colour blindness.
+   ColorBlindSafe,
+   // For monochrome printing.
+   Greyscale,
+   Custom(Box<Palette>),
+}
+
+impl Default for Palette {
+   fn default() -> Self {
+      Self::light()
+   }
+}
+
+impl Palette {
+   #[must_use]
+   pub const fn light() -> Self {
+      Self {
+         foreground: ColorChoice::BLACK,
+         background: ColorChoice::ZVX_BACKGROUND,
+         axis: ColorChoice::BLACK,
+         grid: ColorChoice::BLACK,
+         text: ColorChoice::BLACK,
+         primary: ColorChoice::BLUE,
+         secondary: ColorChoice::GREEN,
+         accent: ColorChoice::RED_RED_GREEN,
+         greyscale: false,
+      }
+   }
+
+   #[must_use]
+   pub const fn dark() -> Self {
+      Self {
+         foreground: ColorChoice::Rgb(0.9, 0.9, 0.9),
+         background: ColorChoice::Rgb(0.12, 0.12, 0.14),
+         axis: ColorChoice::Rgb(0.8, 0.8, 0.8),
+         grid: ColorChoice::GRAY,
+         text: ColorChoice::Rgb(0.9, 0.9, 0.9),
+         primary: ColorChoice::LIGHT_BLUE,
+         secondary: ColorChoice::LIGHT_GREEN,
+         accent: ColorChoice::Rgb(1.0, 0.6, 0.2),
+         greyscale: false,
+      }
+   }
+
+   #[must_use]
+   pub const fn color_blind_safe() -> Self {
+      Self {
+         foreground: ColorChoice::BLACK,
+         background: ColorChoice::Rgb(1.0, 1.0, 1.0),
+         axis: ColorChoice::BLACK,
+         grid: ColorChoice::BLACK,
+         text: ColorChoice::BLACK,
+         primary: ColorChoice::Rgb(0.0, 0.45, 0.7),
+         secondary: ColorChoice::Rgb(0.9, 0.6, 0.0),
+         accent: ColorChoice::Rgb(0.8, 0.4, 0.0),
+         greyscale: false,
+      }
+   }
+
+   #[must_use]
+   pub const fn greyscale() -> Self {
+      Self {
+         foreground: ColorChoice::BLACK,
+         background: ColorChoice::Rgb(1.0, 1.0, 1.0),
+         axis: ColorChoice::BLACK,
+         grid: ColorChoice::BLACK,
+         text: ColorChoice::BLACK,
+         primary: ColorChoice::DARK_GRAY,
+         secondary: ColorChoice::GRAY,
+         accent: ColorChoice::BLACK,
+         greyscale: true,
+      }
+   }
+
+   #[must_use]
+   pub const fn color_for_role(&self, role: ColorRole) -> &ColorChoice {
+      match role {
+         ColorRole::Foreground => &self.foreground,
+         ColorRole::Background => &self.background,
+         ColorRole::Axis => &self.axis,
+         ColorRole::Grid => &self.grid,
+         ColorRole::Text => &self.text,
+         ColorRole::Primary => &self.primary,
+         ColorRole::Secondary => &self.secondary,
+         ColorRole::Accent => &self.accent,
+      }
+   }
+
+   // Concrete colour, that is neither unset nor a role.  Roles in the palette itself are
+   // resolved as the foreground, which should not itself be a role.
+   #[must_use]
+   pub fn resolve(&self, color: &ColorChoice) -> ColorChoice {
+      let concrete_foreground = match &self.foreground {
+         ColorChoice::DefaultBlack | ColorChoice::Role(_) => ColorChoice::BLACK,
+         foreground => foreground.clone(),
+      };
+      let concrete = match color {
+         ColorChoice::DefaultBlack => concrete_foreground,
+         ColorChoice::Role(role) => match self.color_for_role(*role) {
+            ColorChoice::DefaultBlack | ColorChoice::Role(_) => concrete_foreground,
+            role_color => role_color.clone(),
+         },
+         _ => color.clone(),
+      };
+      if self.greyscale {
+         concrete.to_greyscale()
+      } else {
+         concrete
+      }
+   }
+}
+
+impl ThemeChoice {
+   #[must_use]
+   pub fn to_palette(&self) -> Palette {
+      match self {
+         Self::Light => Palette::light(),
+         Self::Dark => Palette::dark(),
+         Self::ColorBlindSafe => Palette::color_blind_safe(),
+         Self::Greyscale => Palette::greyscale(),
+         Self::Custom(palette) => (**palette).clone(),
+      }
+   }
+}
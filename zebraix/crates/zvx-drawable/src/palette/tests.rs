@@ -0,0 +1,138 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const ALL_ROLES: [ColorRole; 8] = [
+   ColorRole::Foreground,
+   ColorRole::Background,
+   ColorRole::Axis,
+   ColorRole::Grid,
+   ColorRole::Text,
+   ColorRole::Primary,
+   ColorRole::Secondary,
+   ColorRole::Accent,
+];
+
+fn assert_grey(color: &ColorChoice, expected: f64) {
+   let ColorChoice::Rgb(r, g, b) = color else {
+      panic!("{color:?} is not RGB");
+   };
+   assert!((r - expected).abs() < 1e-9, "{color:?} differs from grey {expected}");
+   assert_eq!((r, r), (g, b));
+}
+
+#[test]
+fn light_theme_test() {
+   let palette = ThemeChoice::Light.to_palette();
+   assert_eq!(palette, Palette::default());
+   assert_eq!(palette.resolve(&ColorChoice::DefaultBlack), ColorChoice::BLACK);
+   assert_eq!(palette.resolve(&ColorChoice::Role(ColorRole::Primary)), ColorChoice::BLUE);
+   assert_eq!(palette.resolve(&ColorChoice::Role(ColorRole::Accent)), ColorChoice::RED_RED_GREEN);
+   assert_eq!(
+      palette.resolve(&ColorChoice::Role(ColorRole::Background)),
+      ColorChoice::ZVX_BACKGROUND
+   );
+   // Explicit colours are unchanged.
+   assert_eq!(palette.resolve(&ColorChoice::RED), ColorChoice::RED);
+}
+
+#[test]
+fn dark_theme_test() {
+   let palette = ThemeChoice::Dark.to_palette();
+   let light_grey = ColorChoice::Rgb(0.9, 0.9, 0.9);
+   // Unset colours take the light foreground, so that they show on the dark background.
+   assert_eq!(palette.resolve(&ColorChoice::DefaultBlack), light_grey);
+   assert_eq!(palette.resolve(&ColorChoice::Role(ColorRole::Text)), light_grey);
+   assert_eq!(
+      palette.resolve(&ColorChoice::Role(ColorRole::Background)),
+      ColorChoice::Rgb(0.12, 0.12, 0.14)
+   );
+   assert_eq!(palette.resolve(&ColorChoice::Role(ColorRole::Primary)), ColorChoice::LIGHT_BLUE);
+   assert_eq!(palette.resolve(&ColorChoice::BLUE), ColorChoice::BLUE);
+}
+
+#[test]
+fn color_blind_safe_theme_test() {
+   let palette = ThemeChoice::ColorBlindSafe.to_palette();
+   assert_eq!(
+      palette.resolve(&ColorChoice::Role(ColorRole::Primary)),
+      ColorChoice::Rgb(0.0, 0.45, 0.7)
+   );
+   assert_eq!(
+      palette.resolve(&ColorChoice::Role(ColorRole::Secondary)),
+      ColorChoice::Rgb(0.9, 0.6, 0.0)
+   );
+   assert_eq!(
+      palette.resolve(&ColorChoice::Role(ColorRole::Accent)),
+      ColorChoice::Rgb(0.8, 0.4, 0.0)
+   );
+   assert_eq!(palette.resolve(&ColorChoice::DefaultBlack), ColorChoice::BLACK);
+}
+
+#[test]
+fn greyscale_theme_test() {
+   let palette = ThemeChoice::Greyscale.to_palette();
+   for role in ALL_ROLES {
+      let resolved = palette.resolve(&ColorChoice::Role(role));
+      let (r, _, _) = resolved.to_rgb();
+      assert_grey(&resolved, r);
+   }
+   // Explicit colours are also converted, by luma.
+   assert_grey(&palette.resolve(&ColorChoice::BRIGHT_GREEN), 0.587);
+   // Alpha is kept.
+   let translucent = palette.resolve(&ColorChoice::BRIGHT_RED.with_alpha(0.5));
+   assert!(matches!(translucent, ColorChoice::Rgba(r, g, b, 0.5) if r == g && g == b));
+}
+
+#[test]
+fn missing_role_test() {
+   // Unset roles fall back to the foreground, and roles within the palette are not followed.
+   let palette = Palette {
+      foreground: ColorChoice::BLUE_GREEN,
+      primary: ColorChoice::DefaultBlack,
+      accent: ColorChoice::Role(ColorRole::Secondary),
+      ..Palette::light()
+   };
+   assert_eq!(palette.resolve(&ColorChoice::Role(ColorRole::Primary)), ColorChoice::BLUE_GREEN);
+   assert_eq!(palette.resolve(&ColorChoice::Role(ColorRole::Accent)), ColorChoice::BLUE_GREEN);
+   assert_eq!(palette.resolve(&ColorChoice::DefaultBlack), ColorChoice::BLUE_GREEN);
+
+   // A foreground that is not concrete falls back to black.
+   let palette = Palette { foreground: ColorChoice::Role(ColorRole::Text), ..Palette::dark() };
+   assert_eq!(palette.resolve(&ColorChoice::DefaultBlack), ColorChoice::BLACK);
+   for role in ALL_ROLES {
+      assert!(!matches!(
+         palette.resolve(&ColorChoice::Role(role)),
+         ColorChoice::Role(_) | ColorChoice::DefaultBlack
+      ));
+   }
+}
+
+#[test]
+fn override_role_test() {
+   let theme = ThemeChoice::Custom(Box::new(Palette {
+      primary: ColorChoice::BRIGHT_MAGENTA,
+      ..Palette::light()
+   }));
+   let palette = theme.to_palette();
+   let light = Palette::light();
+   assert_eq!(palette.resolve(&ColorChoice::Role(ColorRole::Primary)), ColorChoice::BRIGHT_MAGENTA);
+   for role in ALL_ROLES.into_iter().filter(|role| *role != ColorRole::Primary) {
+      assert_eq!(
+         palette.resolve(&ColorChoice::Role(role)),
+         light.resolve(&ColorChoice::Role(role))
+      );
+   }
+}
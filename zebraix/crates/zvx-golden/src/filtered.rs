@@ -120,25 +120,73 @@ impl SvgGoldenTest {
 
    #[allow(clippy::missing_panics_doc)]
    pub fn handover_result(&self, golden_writeable: SvgGoldenBoxedContent) {
-      let unboxed_result = &**golden_writeable.downcast::<Vec<u8>>().unwrap();
-      Self::filter_result(unboxed_result, &self.out_stream);
+      self.handover_content(&golden_writeable.downcast::<SvgGoldenWriteable>().unwrap());
    }
 
-   // Replace surface ID with generic ID, since this is changeable in tests.
+   // As `handover_result`, for content that is already unboxed.
+   pub fn handover_content(&self, golden_writeable: &SvgGoldenWriteable) {
+      Self::filter_result(&golden_writeable[..], &self.out_stream);
+   }
+
+   // Replace surface ID with generic ID, since this is changeable in tests.  References to
+   // surfaces, such as groups painted with opacity, are likewise made generic.  Image IDs are
+   // also taken from a global counter, and are renumbered in order of appearance.
    #[allow(clippy::missing_panics_doc)]
    pub fn filter_result<R: io::Read, W: io::Write>(boxed_sample_svg: R, mut out_stream: W) {
       let line_reader = std::io::BufReader::new(boxed_sample_svg);
+      let mut image_ids: Vec<String> = Vec::new();
 
       for l in line_reader.lines() {
          let line = l.unwrap();
          if line.starts_with(r#"<g id="surface"#) {
             writeln!(out_stream, r#"<g id="surfaceXXXX">"#).unwrap();
          } else {
+            let line = Self::renumber_image_ids(&line, &mut image_ids);
+            let line = Self::mask_surface_references(&line);
             out_stream.write_all(line.as_bytes()).unwrap();
             out_stream.write_all(b"\n").unwrap();
          }
       }
    }
+
+   fn mask_surface_references(line: &str) -> String {
+      const PREFIX: &str = r##"href="#surface"##;
+      let mut result = String::with_capacity(line.len());
+      let mut rest = line;
+      while let Some(start) = rest.find(PREFIX) {
+         let digits_start = start + PREFIX.len();
+         let digits = &rest[digits_start..];
+         let digits_len = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+         result.push_str(&rest[..digits_start]);
+         result.push_str("XXXX");
+         rest = &rest[digits_start + digits_len..];
+      }
+      result.push_str(rest);
+      result
+   }
+
+   fn renumber_image_ids(line: &str, image_ids: &mut Vec<String>) -> String {
+      const PREFIXES: [&str; 2] = [r#"id="image"#, r##"href="#image"##];
+      let mut result = String::with_capacity(line.len());
+      let mut rest = line;
+      while let Some((start, prefix)) =
+         PREFIXES.iter().filter_map(|p| rest.find(p).map(|i| (i, p))).min()
+      {
+         let digits_start = start + prefix.len();
+         let digits = &rest[digits_start..];
+         let digits_len = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+         let id = &digits[..digits_len];
+         let index = image_ids.iter().position(|i| i == id).unwrap_or_else(|| {
+            image_ids.push(id.to_string());
+            image_ids.len() - 1
+         });
+         result.push_str(&rest[..digits_start]);
+         result.push_str(&(index + 1).to_string());
+         rest = &rest[digits_start + digits_len..];
+      }
+      result.push_str(rest);
+      result
+   }
 }
 
 // Helper that handles a golden (Mint) test for Json output.
@@ -18,9 +18,12 @@ pub mod tests {
    use zvx_cairo::CairoSpartanCombo;
    use zvx_docagram::diagram::{SpartanDiagram, SpartanPreparation};
    use zvx_docagram::{AxesSpec, SizingScheme};
-   use zvx_drawable::{ColorChoice, FillChoices, LineChoice, PathChoices};
+   use zvx_drawable::{
+      ColorChoice, ColorRole, FillChoices, FontChoices, LineChoice, PathChoices, ThemeChoice,
+   };
    use zvx_golden::filtered::JsonGoldenTest;
    use zvx_golden::filtered::SvgGoldenTest;
+   use zvx_golden::filtered::SvgGoldenWriteable;
    use zvx_golden::reduced::to_writer_pretty_reduced;
 
    #[must_use]
@@ -113,6 +116,8 @@ pub mod tests {
       pub debug_box: [f64; 4],
       pub axes_spec: AxesSpec,
       pub background_box: BackgroundBox,
+      pub theme: ThemeChoice,
+      pub font_choices: FontChoices,
    }
 
    #[must_use]
@@ -122,6 +127,8 @@ pub mod tests {
          canvas_size: (sizing.canvas_size[0], sizing.canvas_size[1]),
          axes_range: sizing.axes_range.clone(),
          padding: sizing.padding.clone(),
+         theme: sizing.theme.clone(),
+         base_font_choices: sizing.font_choices.clone(),
          ..Default::default()
       };
 
@@ -131,7 +138,12 @@ pub mod tests {
          BackgroundBox::Shrink => Some(PathChoices {
             line_choice: LineChoice::Ordinary,
             color: spartan.base_color_choice.clone(),
-            fill_choices: FillChoices { color: ColorChoice::ZvxBackground, opacity: 1.0 },
+            fill_choices: FillChoices {
+               color: ColorChoice::Role(ColorRole::Background),
+               opacity: 1.0,
+               ..Default::default()
+            },
+            ..Default::default()
          }),
       };
 
@@ -141,16 +153,19 @@ pub mod tests {
    pub struct JsonSvgRunner {
       filestem: String,
       svg_golden: SvgGoldenTest,
-      pub combo: CairoSpartanCombo,
-      raw_result: Option<Box<dyn core::any::Any>>,
+      pub combo: CairoSpartanCombo<SvgGoldenWriteable>,
+      raw_result: Option<SvgGoldenWriteable>,
    }
 
    impl JsonSvgRunner {
+      // Document element ids are prefixed by the file stem, unless otherwise set.
       #[must_use]
       pub fn new(filestem: &str, preparation: &SpartanPreparation) -> Self {
          let svg_golden = SvgGoldenTest::new("tests/goldenfiles/", filestem);
+         let mut preparation = preparation.clone();
+         preparation.diagram_choices.document_id.get_or_insert_with(|| filestem.to_string());
          let combo =
-            CairoSpartanCombo::create_for_stream(svg_golden.get_raw_writeable(), preparation);
+            CairoSpartanCombo::create_for_stream(svg_golden.get_raw_writeable(), &preparation);
 
          Self { filestem: filestem.to_string(), svg_golden, combo, raw_result: None }
       }
@@ -163,7 +178,7 @@ pub mod tests {
          let raw_result = self.raw_result.take();
          self
             .svg_golden
-            .handover_result(raw_result.expect("No output generated in SVG test runner."));
+            .handover_content(&raw_result.expect("No output generated in SVG test runner."));
 
          //
 
@@ -15,9 +15,12 @@
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use zvx_base::{
-   ArcPath, CubicPath, HyperbolicPath, OneOfSegment, PolylinePath, RatQuadHomogWeighted,
+   ArcPath, CubicPath, HyperbolicPath, OneOfSegment, PolylinePath, RatCubicHomogWeighted,
+   RatQuadHomogWeighted,
+};
+use zvx_curves::{
+   CurveEval, ManagedCubic, ManagedRatCubic, ManagedRatQuad, RatQuadOoeSubclassed, SpecifiedRatQuad,
 };
-use zvx_curves::{CurveEval, ManagedCubic, ManagedRatQuad, RatQuadOoeSubclassed, SpecifiedRatQuad};
 use zvx_docagram::diagram::DrawableDiagram;
 use zvx_drawable::{
    ColorChoice, LineChoice, LinesSetSet, MarkupChoice, OneOfDrawable, PathChoices, PathCompletion,
@@ -86,6 +89,15 @@ fn push_path_segment_drawable(
             }),
          });
       }
+      OneOfSegment::RatCubic(path) => {
+         drawables.push(QualifiedDrawable {
+            layer,
+            drawable: OneOfDrawable::RatCubic(Strokeable::<RatCubicHomogWeighted> {
+               path: path.clone(),
+               path_choices,
+            }),
+         });
+      }
       OneOfSegment::Neither => {
          panic!("Unreachable code.");
       }
@@ -117,10 +129,10 @@ pub struct SampleCurveConfig {
 impl Default for SampleCurveConfig {
    fn default() -> Self {
       Self {
-         main_color: Some(ColorChoice::Blue),
+         main_color: Some(ColorChoice::BLUE),
          main_line_choice: LineChoice::Ordinary,
          approx_num_segments: 0,
-         points_color: Some(ColorChoice::Green),
+         points_color: Some(ColorChoice::GREEN),
          points_choice: PointChoice::Dot,
          points_num_segments: 12,
          sample_options: SampleOption::Normal,
@@ -160,7 +172,10 @@ pub fn draw_sample_rat_quad(
                [x_3 - dx_2 * scale, y_3 - dy_2 * scale],
             ];
          }
-         SpecifiedRatQuad::ThreePointAngle => {
+         SpecifiedRatQuad::ThreePointAngle
+         | SpecifiedRatQuad::FivePoint
+         | SpecifiedRatQuad::ThreePointTangent
+         | SpecifiedRatQuad::SvgArc => {
             let ([[x_0, y_0], [x_3, y_3]], [[dx_1, dy_1], [dx_2, dy_2]]) =
                deprecated_rat_quad.characterize_endpoints();
             let det = dx_1 * dy_2 - dx_2 * dy_1;
@@ -185,6 +200,7 @@ pub fn draw_sample_rat_quad(
             point_choice: curve_config.control_point_choices[0],
             color_choice: color_choice.clone(),
             centers: end_points_vec.clone(),
+            ..Default::default()
          }),
       });
       spartan.drawables.push(QualifiedDrawable {
@@ -193,6 +209,7 @@ pub fn draw_sample_rat_quad(
             point_choice: curve_config.control_point_choices[1],
             color_choice: color_choice.clone(),
             centers: control_points_vec.clone(),
+            ..Default::default()
          }),
       });
 
@@ -209,7 +226,7 @@ pub fn draw_sample_rat_quad(
          drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
             path_choices: PathChoices {
                color: color_choice.clone(),
-               line_choice: curve_config.control_line_choice,
+               line_choice: curve_config.control_line_choice.clone(),
                ..Default::default()
             },
             path: LinesSetSet {
@@ -252,6 +269,7 @@ pub fn draw_sample_rat_quad(
             point_choice: curve_config.points_choice,
             color_choice: color_choice.clone(),
             centers: pattern_vec,
+            ..Default::default()
          }),
       });
    }
@@ -282,7 +300,7 @@ pub fn draw_sample_rat_quad(
             drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
                path_choices: PathChoices {
                   color: color_choice.clone(),
-                  line_choice: curve_config.main_line_choice,
+                  line_choice: curve_config.main_line_choice.clone(),
                   ..Default::default()
                },
                path: pattern_vec,
@@ -296,7 +314,7 @@ pub fn draw_sample_rat_quad(
             &one_of_path,
             PathChoices {
                color: color_choice.clone(),
-               line_choice: curve_config.main_line_choice,
+               line_choice: curve_config.main_line_choice.clone(),
                ..Default::default()
             },
             curve_config.main_line_layer,
@@ -339,7 +357,7 @@ pub fn draw_derivatives_rat_quad(
          drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
             path_choices: PathChoices {
                color: color_choice.clone(),
-               line_choice: curve_config.main_line_choice,
+               line_choice: curve_config.main_line_choice.clone(),
                ..Default::default()
             },
             path: LinesSetSet { coords: delta_lines, ..Default::default() },
@@ -354,6 +372,7 @@ pub fn draw_derivatives_rat_quad(
             point_choice: curve_config.points_choice,
             color_choice: color_choice.clone(),
             centers: end_vec,
+            ..Default::default()
          }),
       });
    }
@@ -397,6 +416,7 @@ pub fn draw_sample_cubilinear(
             point_choice: curve_config.control_point_choices[0],
             color_choice: color_choice.clone(),
             centers: end_points_vec.clone(),
+            ..Default::default()
          }),
       });
       spartan.drawables.push(QualifiedDrawable {
@@ -405,6 +425,7 @@ pub fn draw_sample_cubilinear(
             point_choice: curve_config.control_point_choices[1],
             color_choice: color_choice.clone(),
             centers: control_points_vec.clone(),
+            ..Default::default()
          }),
       });
       if need_sigma {
@@ -414,6 +435,7 @@ pub fn draw_sample_cubilinear(
                point_choice: PointChoice::Square,
                color_choice: color_choice.clone(),
                centers: sigma_control_points_vec.clone(),
+               ..Default::default()
             }),
          });
       }
@@ -425,7 +447,7 @@ pub fn draw_sample_cubilinear(
          drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
             path_choices: PathChoices {
                color: color_choice.clone(),
-               line_choice: curve_config.control_line_choice,
+               line_choice: curve_config.control_line_choice.clone(),
                ..Default::default()
             },
             path: LinesSetSet {
@@ -468,6 +490,7 @@ pub fn draw_sample_cubilinear(
             point_choice: curve_config.points_choice,
             color_choice: color_choice.clone(),
             centers: pattern_vec,
+            ..Default::default()
          }),
       });
    }
@@ -478,7 +501,7 @@ pub fn draw_sample_cubilinear(
          drawable: OneOfDrawable::Cubic(Strokeable::<CubicPath> {
             path_choices: PathChoices {
                color: color_choice.clone(),
-               line_choice: curve_config.main_line_choice,
+               line_choice: curve_config.main_line_choice.clone(),
                ..Default::default()
             },
             path: four_point.clone(),
@@ -521,7 +544,7 @@ pub fn draw_derivatives_cubilinear(
          drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
             path_choices: PathChoices {
                color: color_choice.clone(),
-               line_choice: curve_config.main_line_choice,
+               line_choice: curve_config.main_line_choice.clone(),
                ..Default::default()
             },
             path: LinesSetSet { coords: delta_lines, ..Default::default() },
@@ -536,6 +559,7 @@ pub fn draw_derivatives_cubilinear(
             point_choice: curve_config.points_choice,
             color_choice: color_choice.clone(),
             centers: end_vec,
+            ..Default::default()
          }),
       });
    }
@@ -545,6 +569,7 @@ pub fn draw_derivatives_cubilinear(
 pub enum OneOfManagedSegment {
    ManagedCubic(ManagedCubic),
    ManagedRatQuad(ManagedRatQuad),
+   ManagedRatCubic(ManagedRatCubic),
    Polyline(Vec<[f64; 2]>),
 }
 
@@ -570,6 +595,9 @@ pub fn draw_sample_segment_sequence(
             let ordinary_rat_quad: &RatQuadHomogWeighted = &managed_rat_quad.rq_curve;
             segments_paths.push(create_rat_quad_path(ordinary_rat_quad));
          }
+         OneOfManagedSegment::ManagedRatCubic(managed_rat_cubic) => {
+            segments_paths.push(OneOfSegment::RatCubic(managed_rat_cubic.rc_curve.clone()));
+         }
          OneOfManagedSegment::Polyline(locations) => {
             segments_paths.push(OneOfSegment::Polyline(locations.clone()));
          }
@@ -606,7 +634,9 @@ pub fn add_centered_text(
             content: text.to_string(),
             location,
             markup: MarkupChoice::Pango,
+            ..Default::default()
          }],
+         ..Default::default()
       }),
    });
 }
@@ -72,45 +72,45 @@ mod tests {
          &managed_curve_a,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::RedRedGreen),
+            main_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::RED_RED_GREEN),
             points_color: None,
             ..Default::default()
          },
       );
       add_centered_text(
          drawable_diagram,
-         &SampleCurveConfig { main_color: Some(ColorChoice::BlueBlueRed), ..Default::default() },
+         &SampleCurveConfig { main_color: Some(ColorChoice::BLUE_BLUE_RED), ..Default::default() },
          "Intermediate control points",
          [0.0, 1.75],
       );
       add_centered_text(
          drawable_diagram,
-         &SampleCurveConfig { main_color: Some(ColorChoice::BlueBlueRed), ..Default::default() },
+         &SampleCurveConfig { main_color: Some(ColorChoice::BLUE_BLUE_RED), ..Default::default() },
          "P<sub>1</sub>",
          mul_add_p(&main_curve[1], [1.0, 1.0], [0.0, 0.5]),
       );
       add_centered_text(
          drawable_diagram,
-         &SampleCurveConfig { main_color: Some(ColorChoice::BlueBlueRed), ..Default::default() },
+         &SampleCurveConfig { main_color: Some(ColorChoice::BLUE_BLUE_RED), ..Default::default() },
          "P<sub>2</sub>",
          mul_add_p(&main_curve[2], [1.0, 1.0], [0.0, 0.5]),
       );
       add_centered_text(
          drawable_diagram,
-         &SampleCurveConfig { main_color: Some(ColorChoice::RedRedBlue), ..Default::default() },
+         &SampleCurveConfig { main_color: Some(ColorChoice::RED_RED_BLUE), ..Default::default() },
          "End control points",
          [0.0, -2.0],
       );
       add_centered_text(
          drawable_diagram,
-         &SampleCurveConfig { main_color: Some(ColorChoice::RedRedBlue), ..Default::default() },
+         &SampleCurveConfig { main_color: Some(ColorChoice::RED_RED_BLUE), ..Default::default() },
          "P<sub>0</sub>",
          mul_add_p(&main_curve[0], [1.0, 1.0], [0.0, -0.5]),
       );
       add_centered_text(
          drawable_diagram,
-         &SampleCurveConfig { main_color: Some(ColorChoice::RedRedBlue), ..Default::default() },
+         &SampleCurveConfig { main_color: Some(ColorChoice::RED_RED_BLUE), ..Default::default() },
          "P<sub>3</sub>",
          mul_add_p(&main_curve[3], [1.0, 1.0], [0.0, -0.5]),
       );
@@ -139,9 +139,9 @@ mod tests {
          &managed_curve_a,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::RedRedGreen),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::RED_RED_GREEN),
+            points_color: Some(ColorChoice::BLUE),
             ..Default::default()
          },
       );
@@ -164,9 +164,9 @@ mod tests {
          &managed_curve_b,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::RedRedGreen),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::RED_RED_GREEN),
+            points_color: Some(ColorChoice::BLUE),
             ..Default::default()
          },
       );
@@ -190,9 +190,9 @@ mod tests {
          &managed_curve_c,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::RedRedGreen),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::RED_RED_GREEN),
+            points_color: Some(ColorChoice::BLUE),
             ..Default::default()
          },
       );
@@ -235,9 +235,9 @@ mod tests {
          &managed_curve_d,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::RedRedGreen),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::RED_RED_GREEN),
+            points_color: Some(ColorChoice::BLUE),
             ..Default::default()
          },
       );
@@ -277,7 +277,7 @@ mod tests {
             drawable: OneOfDrawable::SegmentSequence(SegmentSequence {
                path_choices: PathChoices {
                   line_choice: LineChoice::Ordinary,
-                  color: ColorChoice::Black,
+                  color: ColorChoice::BLACK,
                   ..Default::default()
                },
                completion: PathCompletion::Closed,
@@ -300,8 +300,12 @@ mod tests {
             layer: layer_slot + 1,
             drawable: OneOfDrawable::SegmentSequence(SegmentSequence {
                path_choices: PathChoices {
-                  color: ColorChoice::Green,
-                  fill_choices: FillChoices { color: ColorChoice::ZvxBackground, opacity: 1.0 },
+                  color: ColorChoice::GREEN,
+                  fill_choices: FillChoices {
+                     color: ColorChoice::ZVX_BACKGROUND,
+                     opacity: 1.0,
+                     ..Default::default()
+                  },
                   ..Default::default()
                },
                completion: PathCompletion::Closed,
@@ -324,7 +328,7 @@ mod tests {
             layer: layer_slot + 1,
             drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
                path_choices: PathChoices {
-                  color: ColorChoice::Blue,
+                  color: ColorChoice::BLUE,
                   line_choice: LineChoice::Ordinary,
                   ..Default::default()
                },
@@ -346,7 +350,7 @@ mod tests {
             layer: layer_slot,
             drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
                path_choices: PathChoices {
-                  color: ColorChoice::Red,
+                  color: ColorChoice::RED,
                   line_choice: LineChoice::Ordinary,
                   ..Default::default()
                },
@@ -376,9 +380,9 @@ mod tests {
          &managed_curve_b,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
+            main_color: Some(ColorChoice::GREEN),
             control_color: None,
-            points_color: Some(ColorChoice::Blue),
+            points_color: Some(ColorChoice::BLUE),
             ..Default::default()
          },
       );
@@ -395,8 +399,8 @@ mod tests {
          &managed_curve_c,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::RedRedGreen),
+            main_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::RED_RED_GREEN),
             points_color: None,
             ..Default::default()
          },
@@ -420,9 +424,9 @@ mod tests {
          &managed_curve_d,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::RedRedGreen),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::RED_RED_GREEN),
+            points_color: Some(ColorChoice::BLUE),
             ..Default::default()
          },
       );
@@ -460,7 +464,7 @@ mod tests {
             &managed_curve_d,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -491,7 +495,7 @@ mod tests {
             &managed_curve_d,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -520,7 +524,7 @@ mod tests {
             &managed_curve_d,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -552,7 +556,7 @@ mod tests {
             &managed_curve_d,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -585,7 +589,7 @@ mod tests {
             &managed_curve_c,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -610,7 +614,7 @@ mod tests {
             &managed_curve_d,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -644,7 +648,7 @@ mod tests {
             &managed_curve_d,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -681,7 +685,7 @@ mod tests {
             &managed_curve_a,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -694,7 +698,7 @@ mod tests {
             drawable_diagram,
             &SampleCurveConfig {
                main_line_choice: LineChoice::Light,
-               main_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::BLUE),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -707,7 +711,7 @@ mod tests {
             drawable_diagram,
             &SampleCurveConfig {
                main_line_choice: LineChoice::Light,
-               main_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::BLUE),
                control_color: None,
                points_color: None,
                ..Default::default()
@@ -734,7 +738,7 @@ mod tests {
                &managed_curve,
                drawable_diagram,
                &SampleCurveConfig {
-                  main_color: Some(ColorChoice::Green),
+                  main_color: Some(ColorChoice::GREEN),
                   points_color: None,
                   control_color: None,
                   ..Default::default()
@@ -747,7 +751,7 @@ mod tests {
                &managed_curve,
                drawable_diagram,
                &SampleCurveConfig {
-                  main_color: Some(ColorChoice::Green),
+                  main_color: Some(ColorChoice::GREEN),
                   points_color: None,
                   control_color: None,
                   ..Default::default()
@@ -789,8 +793,8 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_num_segments: 18,
                ..Default::default()
             },
@@ -16,21 +16,31 @@
 mod tests {
    use std::collections::VecDeque;
    use zvx_base::{
-      CubicFourPoint, CubicHomog, OneOfSegment, PolylinePath, RatQuadHomog, RatQuadHomogPower,
-      RatQuadHomogWeighted,
+      ArcPath, CubicFourPoint, CubicHomog, CubicPath, OneOfSegment, PolylinePath, RatQuadHomog,
+      RatQuadHomogPower, RatQuadHomogWeighted,
    };
+   use zvx_cairo::capabilities::{PangoFontDescription, SvgDocumentUnit, SvgUnit};
+   use zvx_cairo::CairoSpartanCombo;
    use zvx_curves::rat_quad::rq_weighted_collapse_bilinear;
    use zvx_curves::{
-      CurveEval, CurveTransform, FourPointRatQuad, ManagedCubic, ManagedRatQuad,
-      ThreePointAngleRepr, ZebraixAngle,
+      CurveEval, CurveTransform, FourPointRatCubic, FourPointRatQuad, ManagedCubic,
+      ManagedRatCubic, ManagedRatQuad, ThreePointAngleRepr, ZebraixAngle,
    };
    use zvx_docagram::diagram::DrawableDiagram;
-   use zvx_docagram::{AxesSpec, AxesStyle, AxisNumbering, SizingScheme};
+   use zvx_docagram::{
+      AxesSpec, AxesStyle, AxisNumbering, DiagnosticKind, DiagnosticSeverity, SizingScheme,
+   };
+   use zvx_drawable::extension::ZvxCapabilities;
    use zvx_drawable::{
-      CirclesSet, ColorChoice, LineChoice, LinesSetSet, OneOfDrawable, PathChoices, PathCompletion,
-      PointChoice, PointsDrawable, QualifiedDrawable, SegmentSequence, Strokeable,
-      TextAnchorChoice, TextAnchorHorizontal, TextAnchorVertical, TextDrawable, TextOffsetChoice,
-      TextSingle, TextSizeChoice,
+      CirclesSet, ClipRegion, ClippedDrawables, ColorChoice, ColorRole, ColorStop, FillChoices,
+      FillPattern, FontChoices, FontSlantChoice, FontStretchChoice, FontWeightChoice,
+      GroupDrawable, HatchChoices, ImageDrawable, ImageInterpolation, ImageSource, LayerChoices,
+      LineCapChoice, LineChoice, LineJoinChoice, LineStyle, LinearGradient, LinesSetSet,
+      MarkerChoice, MarkerChoices, MarkupChoice, NamedLayer, OneOfDrawable, ParagraphChoices,
+      PathChoices, PathCompletion, PointChoice, PointsDrawable, QualifiedDrawable, RadialGradient,
+      SegmentSequence, Strokeable, TextAlignChoice, TextAnchorChoice, TextAnchorHorizontal,
+      TextAnchorVertical, TextDrawable, TextOffsetChoice, TextPathDrawable, TextSingle,
+      TextSizeChoice, ThemeChoice,
    };
    use zvx_simples::exemplary::tests::{
       build_from_sizing, create_sized_diagram, p_from_x_y_3, p_from_x_y_4, render_and_check,
@@ -244,7 +254,7 @@ mod tests {
          layer: title_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Large,
-            color_choice: ColorChoice::BrightBlue,
+            color_choice: ColorChoice::BRIGHT_BLUE,
             // offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Center,
@@ -262,7 +272,7 @@ mod tests {
          layer: title_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Normal,
-            color_choice: ColorChoice::BrightBlue,
+            color_choice: ColorChoice::BRIGHT_BLUE,
             // offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Center,
@@ -432,8 +442,9 @@ mod tests {
             layer: pattern_layer,
             drawable: OneOfDrawable::Points(PointsDrawable {
                point_choice: PointChoice::Dot,
-               color_choice: ColorChoice::Gray,
+               color_choice: ColorChoice::GRAY,
                centers: scale_coord_vec(&pattern_vec, 1.0),
+               ..Default::default()
             }),
          });
       }
@@ -442,8 +453,9 @@ mod tests {
             layer: pattern_layer,
             drawable: OneOfDrawable::Points(PointsDrawable {
                point_choice: PointChoice::Dot,
-               color_choice: ColorChoice::Gray,
+               color_choice: ColorChoice::GRAY,
                centers: scale_coord_vec(&pattern_vec, -1.0),
+               ..Default::default()
             }),
          };
          drawable_diagram.drawables.push(qualified_drawable);
@@ -468,7 +480,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Large,
-            color_choice: ColorChoice::Red,
+            color_choice: ColorChoice::RED,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Left,
@@ -479,13 +491,14 @@ mod tests {
                location: [2.0, 1.5],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Normal,
-            color_choice: ColorChoice::Green,
+            color_choice: ColorChoice::GREEN,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Left,
@@ -496,13 +509,14 @@ mod tests {
                location: [2.0, 0.0],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Small,
-            color_choice: ColorChoice::Blue,
+            color_choice: ColorChoice::BLUE,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Left,
@@ -513,13 +527,14 @@ mod tests {
                location: [2.0, -1.5],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Normal,
-            color_choice: ColorChoice::BlueBlueGreen,
+            color_choice: ColorChoice::BLUE_BLUE_GREEN,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Left,
                TextAnchorVertical::Top,
@@ -536,7 +551,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Large,
-            color_choice: ColorChoice::YellowBrown,
+            color_choice: ColorChoice::YELLOW_BROWN,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Left,
                TextAnchorVertical::Middle,
@@ -553,7 +568,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Normal,
-            color_choice: ColorChoice::BlueGreen,
+            color_choice: ColorChoice::BLUE_GREEN,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Left,
                TextAnchorVertical::Middle,
@@ -570,7 +585,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Small,
-            color_choice: ColorChoice::BlueRed,
+            color_choice: ColorChoice::BLUE_RED,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Left,
                TextAnchorVertical::Middle,
@@ -603,7 +618,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Normal,
-            color_choice: ColorChoice::RedRedBlue,
+            color_choice: ColorChoice::RED_RED_BLUE,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Center,
@@ -614,13 +629,14 @@ mod tests {
                location: [0.0, 1.5],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Large,
-            color_choice: ColorChoice::BlueBlueRed,
+            color_choice: ColorChoice::BLUE_BLUE_RED,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Center,
@@ -631,6 +647,7 @@ mod tests {
                location: [0.0, 3.0],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
@@ -654,7 +671,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Large,
-            color_choice: ColorChoice::Blue,
+            color_choice: ColorChoice::BLUE,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Right,
@@ -665,13 +682,14 @@ mod tests {
                location: [-2.0, 1.5],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Normal,
-            color_choice: ColorChoice::Green,
+            color_choice: ColorChoice::GREEN,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Right,
@@ -682,13 +700,14 @@ mod tests {
                location: [-2.0, 0.0],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Small,
-            color_choice: ColorChoice::Red,
+            color_choice: ColorChoice::RED,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Right,
@@ -699,13 +718,14 @@ mod tests {
                location: [-2.0, -1.5],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Large,
-            color_choice: ColorChoice::BlueRed,
+            color_choice: ColorChoice::BLUE_RED,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Right,
                TextAnchorVertical::Middle,
@@ -722,7 +742,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Normal,
-            color_choice: ColorChoice::BlueGreen,
+            color_choice: ColorChoice::BLUE_GREEN,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Right,
                TextAnchorVertical::Middle,
@@ -739,7 +759,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Small,
-            color_choice: ColorChoice::YellowBrown,
+            color_choice: ColorChoice::YELLOW_BROWN,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Right,
                TextAnchorVertical::Middle,
@@ -772,7 +792,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Normal,
-            color_choice: ColorChoice::RedRedGreen,
+            color_choice: ColorChoice::RED_RED_GREEN,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Right,
                TextAnchorVertical::Top,
@@ -789,7 +809,7 @@ mod tests {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Normal,
-            color_choice: ColorChoice::GreenGreenBlue,
+            color_choice: ColorChoice::GREEN_GREEN_BLUE,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Center,
@@ -800,13 +820,14 @@ mod tests {
                location: [0.0, -1.5],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: pattern_layer,
          drawable: OneOfDrawable::Text(TextDrawable {
             size_choice: TextSizeChoice::Large,
-            color_choice: ColorChoice::GreenGreenRed,
+            color_choice: ColorChoice::GREEN_GREEN_RED,
             offset_choice: TextOffsetChoice::Diagram,
             anchor_choice: TextAnchorChoice::ThreeByThree(
                TextAnchorHorizontal::Center,
@@ -817,6 +838,7 @@ mod tests {
                location: [0.0, -3.0],
                ..Default::default()
             }],
+            ..Default::default()
          }),
       });
       drawable_diagram.drawables.push(QualifiedDrawable {
@@ -870,7 +892,7 @@ mod tests {
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: front_layer,
          drawable: OneOfDrawable::Circles(Strokeable::<CirclesSet> {
-            path_choices: PathChoices { color: ColorChoice::BrightRed, ..Default::default() },
+            path_choices: PathChoices { color: ColorChoice::BRIGHT_RED, ..Default::default() },
             path: CirclesSet { radius: 1.2, centers: vec![[-1.5, 3.0], [1.5, 3.0]] },
          }),
       });
@@ -878,7 +900,7 @@ mod tests {
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: behind_layer,
          drawable: OneOfDrawable::Circles(Strokeable::<CirclesSet> {
-            path_choices: PathChoices { color: ColorChoice::Blue, ..Default::default() },
+            path_choices: PathChoices { color: ColorChoice::BLUE, ..Default::default() },
             path: CirclesSet { radius: 1.2, centers: vec![[-3.0, 3.0], [0.0, 3.0], [3.0, 3.0]] },
          }),
       });
@@ -910,7 +932,7 @@ mod tests {
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: drawable_layer,
          drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
-            path_choices: PathChoices { color: ColorChoice::Red, ..Default::default() },
+            path_choices: PathChoices { color: ColorChoice::RED, ..Default::default() },
             path: vec![
                [-3.0, 2.0],
                [-2.0, 3.0],
@@ -926,7 +948,7 @@ mod tests {
       drawable_diagram.drawables.push(QualifiedDrawable {
          layer: drawable_layer,
          drawable: OneOfDrawable::SegmentSequence(SegmentSequence {
-            path_choices: PathChoices { color: ColorChoice::Green, ..Default::default() },
+            path_choices: PathChoices { color: ColorChoice::GREEN, ..Default::default() },
             completion: PathCompletion::Closed,
             segments: vec![OneOfSegment::Polyline(vec![
                [-3.0, -2.0],
@@ -979,8 +1001,8 @@ mod tests {
          &managed_curve,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Red),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::RED),
+            points_color: Some(ColorChoice::BLUE),
             points_choice: PointChoice::Circle,
             points_num_segments: 12,
             approx_num_segments: 50,
@@ -1027,8 +1049,8 @@ mod tests {
          &managed_curve,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Red),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::RED),
+            points_color: Some(ColorChoice::BLUE),
             points_choice: PointChoice::Circle,
             points_num_segments: 12,
             approx_num_segments: 50,
@@ -1079,8 +1101,8 @@ mod tests {
          &managed_curve,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Red),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::RED),
+            points_color: Some(ColorChoice::BLUE),
             points_choice: PointChoice::Circle,
             points_num_segments: 12,
             approx_num_segments: 50,
@@ -1132,8 +1154,8 @@ mod tests {
          &managed_curve,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Red),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::RED),
+            points_color: Some(ColorChoice::BLUE),
             points_choice: PointChoice::Circle,
             points_num_segments: 12,
             approx_num_segments: 50,
@@ -1191,8 +1213,8 @@ mod tests {
          &managed_curve,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Red),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::RED),
+            points_color: Some(ColorChoice::BLUE),
             points_choice: PointChoice::Circle,
             points_num_segments: 12,
             approx_num_segments: 50,
@@ -1251,8 +1273,8 @@ mod tests {
          &managed_curve,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Red),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::RED),
+            points_color: Some(ColorChoice::BLUE),
             points_choice: PointChoice::Circle,
             points_num_segments: 12,
             approx_num_segments: 50,
@@ -1299,8 +1321,8 @@ mod tests {
          &managed_curve,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::GREEN),
+            points_color: Some(ColorChoice::BLUE),
             points_choice: PointChoice::Circle,
             points_num_segments: 12,
             approx_num_segments: 30,
@@ -1315,7 +1337,7 @@ mod tests {
          &managed_curve,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::BrightBlue),
+            main_color: Some(ColorChoice::BRIGHT_BLUE),
             points_color: None,
             ..Default::default()
          },
@@ -1453,9 +1475,9 @@ mod tests {
          &managed_curve_a,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::YellowBrown),
-            points_color: Some(ColorChoice::BlueGreen),
+            main_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::YELLOW_BROWN),
+            points_color: Some(ColorChoice::BLUE_GREEN),
             ..Default::default()
          },
       );
@@ -1467,8 +1489,8 @@ mod tests {
          &managed_curve_b,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::BrightBlue),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::BRIGHT_BLUE),
+            points_color: Some(ColorChoice::BLUE),
             points_num_segments: 12,
             ..Default::default()
          },
@@ -1480,9 +1502,9 @@ mod tests {
          &managed_curve_d,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            points_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::YellowBrown),
+            main_color: Some(ColorChoice::GREEN),
+            points_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::YELLOW_BROWN),
             points_choice: PointChoice::Circle,
             points_num_segments: 6,
             ..Default::default()
@@ -1497,8 +1519,8 @@ mod tests {
          &managed_curve_c,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::BrightBlue),
-            points_color: Some(ColorChoice::Blue),
+            main_color: Some(ColorChoice::BRIGHT_BLUE),
+            points_color: Some(ColorChoice::BLUE),
             points_num_segments: 12,
             ..Default::default()
          },
@@ -1510,9 +1532,9 @@ mod tests {
          &managed_curve_e,
          drawable_diagram,
          &SampleCurveConfig {
-            main_color: Some(ColorChoice::Green),
-            points_color: Some(ColorChoice::Green),
-            control_color: Some(ColorChoice::YellowBrown),
+            main_color: Some(ColorChoice::GREEN),
+            points_color: Some(ColorChoice::GREEN),
+            control_color: Some(ColorChoice::YELLOW_BROWN),
             points_choice: PointChoice::Circle,
             points_num_segments: 6,
             ..Default::default()
@@ -1567,10 +1589,10 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -1600,10 +1622,10 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -1635,10 +1657,10 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -1667,10 +1689,10 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -1699,10 +1721,10 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -1733,10 +1755,10 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -1766,10 +1788,10 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -1800,10 +1822,10 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -1832,10 +1854,10 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -1887,7 +1909,7 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                points_color: None,
                points_num_segments: 12,
                approx_num_segments: 30,
@@ -1926,7 +1948,7 @@ mod tests {
             drawable_diagram,
             &SampleCurveConfig {
                main_color: None,
-               points_color: Some(ColorChoice::Blue),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
                points_num_segments: 12,
                approx_num_segments: 30,
@@ -1959,8 +1981,8 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Dot,
                points_num_segments: 10,
                approx_num_segments: 30,
@@ -1990,8 +2012,8 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Dot,
                points_num_segments: 12,
                approx_num_segments: 30,
@@ -2055,8 +2077,8 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::BrightGreen),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BRIGHT_GREEN),
                points_choice: PointChoice::Dot,
                points_num_segments: 12,
                approx_num_segments: 30,
@@ -2081,7 +2103,7 @@ mod tests {
             drawable_diagram,
             &SampleCurveConfig {
                main_color: None,
-               points_color: Some(ColorChoice::Blue),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
                ..Default::default()
             },
@@ -2105,8 +2127,8 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::BrightGreen),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BRIGHT_GREEN),
                points_choice: PointChoice::Dot,
                points_num_segments: 12,
                approx_num_segments: 30,
@@ -2131,7 +2153,7 @@ mod tests {
             drawable_diagram,
             &SampleCurveConfig {
                main_color: None,
-               points_color: Some(ColorChoice::Blue),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
                ..Default::default()
             },
@@ -2155,8 +2177,8 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::BrightGreen),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BRIGHT_GREEN),
                points_choice: PointChoice::Dot,
                points_num_segments: 12,
                approx_num_segments: 30,
@@ -2181,7 +2203,7 @@ mod tests {
             drawable_diagram,
             &SampleCurveConfig {
                main_color: None,
-               points_color: Some(ColorChoice::Blue),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Circle,
                ..Default::default()
             },
@@ -2306,9 +2328,9 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
-               control_color: Some(ColorChoice::YellowBrown),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -2339,9 +2361,9 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
-               control_color: Some(ColorChoice::YellowBrown),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -2372,9 +2394,9 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
-               control_color: Some(ColorChoice::YellowBrown),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -2405,9 +2427,9 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
-               control_color: Some(ColorChoice::YellowBrown),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -2438,7 +2460,7 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                points_color: None,
                ..Default::default()
             },
@@ -2470,7 +2492,7 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                points_color: None,
                ..Default::default()
             },
@@ -2502,7 +2524,7 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                points_color: None,
                ..Default::default()
             },
@@ -2534,7 +2556,7 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                points_color: None,
                ..Default::default()
             },
@@ -2566,7 +2588,7 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
+               main_color: Some(ColorChoice::GREEN),
                points_color: None,
                ..Default::default()
             },
@@ -2646,10 +2668,10 @@ mod tests {
             drawable_diagram,
             &SampleCurveConfig {
                // main_color: None,
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                // points_choice: PointChoice::Circle,
-               control_color: Some(ColorChoice::YellowBrown),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -2682,9 +2704,9 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
-               control_color: Some(ColorChoice::YellowBrown),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
                ..Default::default()
             },
          );
@@ -2714,7 +2736,7 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::RedRedBlue),
+               main_color: Some(ColorChoice::RED_RED_BLUE),
                // main_line_choice: LineChoice::Light,
                points_color: None,
                ..Default::default()
@@ -2761,8 +2783,8 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Green),
-               points_color: Some(ColorChoice::Blue),
+               main_color: Some(ColorChoice::GREEN),
+               points_color: Some(ColorChoice::BLUE),
                points_choice: PointChoice::Dot,
                ..Default::default()
             },
@@ -2816,10 +2838,10 @@ mod tests {
                &managed_curve,
                drawable_diagram,
                &SampleCurveConfig {
-                  main_color: Some(ColorChoice::Green),
-                  points_color: Some(ColorChoice::Blue),
+                  main_color: Some(ColorChoice::GREEN),
+                  points_color: Some(ColorChoice::BLUE),
                   points_choice: PointChoice::Circle,
-                  control_color: Some(ColorChoice::YellowBrown),
+                  control_color: Some(ColorChoice::YELLOW_BROWN),
                   control_point_choices: [PointChoice::Circle, PointChoice::Plus],
                   points_num_segments: 2,
                   ..Default::default()
@@ -2831,7 +2853,7 @@ mod tests {
                drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
                   path_choices: PathChoices {
                      line_choice: LineChoice::Ordinary,
-                     color: ColorChoice::Red,
+                     color: ColorChoice::RED,
                      ..Default::default()
                   },
                   path: LinesSetSet {
@@ -2870,7 +2892,7 @@ mod tests {
                drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
                   path_choices: PathChoices {
                      line_choice: LineChoice::Ordinary,
-                     color: ColorChoice::Blue,
+                     color: ColorChoice::BLUE,
                      ..Default::default()
                   },
                   path: LinesSetSet {
@@ -2909,9 +2931,9 @@ mod tests {
                drawable_diagram,
                &SampleCurveConfig {
                   main_color: None,
-                  points_color: Some(ColorChoice::Red),
+                  points_color: Some(ColorChoice::RED),
                   points_num_segments: 2,
-                  control_color: Some(ColorChoice::YellowBrown),
+                  control_color: Some(ColorChoice::YELLOW_BROWN),
                   control_point_choices: [PointChoice::Circle, PointChoice::Plus],
                   ..Default::default()
                },
@@ -2934,8 +2956,8 @@ mod tests {
                &managed_curve,
                drawable_diagram,
                &SampleCurveConfig {
-                  main_color: Some(ColorChoice::Green),
-                  points_color: Some(ColorChoice::Green),
+                  main_color: Some(ColorChoice::GREEN),
+                  points_color: Some(ColorChoice::GREEN),
                   points_num_segments: 2,
                   ..Default::default()
                },
@@ -2958,8 +2980,8 @@ mod tests {
                &managed_curve,
                drawable_diagram,
                &SampleCurveConfig {
-                  main_color: Some(ColorChoice::Blue),
-                  points_color: Some(ColorChoice::Blue),
+                  main_color: Some(ColorChoice::BLUE),
+                  points_color: Some(ColorChoice::BLUE),
                   points_num_segments: 2,
                   ..Default::default()
                },
@@ -2970,7 +2992,7 @@ mod tests {
                drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
                   path_choices: PathChoices {
                      line_choice: LineChoice::Ordinary,
-                     color: ColorChoice::Red,
+                     color: ColorChoice::RED,
                      ..Default::default()
                   },
                   path: LinesSetSet {
@@ -3031,10 +3053,10 @@ mod tests {
                &managed_curve,
                drawable_diagram,
                &SampleCurveConfig {
-                  main_color: Some(ColorChoice::Green),
-                  points_color: Some(ColorChoice::Blue),
+                  main_color: Some(ColorChoice::GREEN),
+                  points_color: Some(ColorChoice::BLUE),
                   points_choice: PointChoice::Circle,
-                  control_color: Some(ColorChoice::YellowBrown),
+                  control_color: Some(ColorChoice::YELLOW_BROWN),
                   control_point_choices: [PointChoice::Circle, PointChoice::Plus],
                   points_num_segments: 2,
                   ..Default::default()
@@ -3046,7 +3068,7 @@ mod tests {
                drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
                   path_choices: PathChoices {
                      line_choice: LineChoice::Ordinary,
-                     color: ColorChoice::Red,
+                     color: ColorChoice::RED,
                      ..Default::default()
                   },
                   path: LinesSetSet {
@@ -3085,7 +3107,7 @@ mod tests {
                drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
                   path_choices: PathChoices {
                      line_choice: LineChoice::Ordinary,
-                     color: ColorChoice::Blue,
+                     color: ColorChoice::BLUE,
                      ..Default::default()
                   },
                   path: LinesSetSet {
@@ -3127,10 +3149,10 @@ mod tests {
                &managed_curve,
                drawable_diagram,
                &SampleCurveConfig {
-                  main_color: Some(ColorChoice::Green),
-                  points_color: Some(ColorChoice::Blue),
+                  main_color: Some(ColorChoice::GREEN),
+                  points_color: Some(ColorChoice::BLUE),
                   points_choice: PointChoice::Circle,
-                  control_color: Some(ColorChoice::YellowBrown),
+                  control_color: Some(ColorChoice::YELLOW_BROWN),
                   control_point_choices: [PointChoice::Circle, PointChoice::Plus],
                   points_num_segments: 2,
                   ..Default::default()
@@ -3142,7 +3164,7 @@ mod tests {
                drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
                   path_choices: PathChoices {
                      line_choice: LineChoice::Ordinary,
-                     color: ColorChoice::Red,
+                     color: ColorChoice::RED,
                      ..Default::default()
                   },
                   path: LinesSetSet {
@@ -3198,9 +3220,9 @@ mod tests {
             &managed_curve_a,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::LightBlue),
-               control_color: Some(ColorChoice::YellowBrown),
-               points_color: Some(ColorChoice::LightGreen),
+               main_color: Some(ColorChoice::LIGHT_BLUE),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
+               points_color: Some(ColorChoice::LIGHT_GREEN),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -3209,8 +3231,8 @@ mod tests {
             &managed_curve_a,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Red),
-               points_color: Some(ColorChoice::BlueRed),
+               main_color: Some(ColorChoice::RED),
+               points_color: Some(ColorChoice::BLUE_RED),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -3228,9 +3250,9 @@ mod tests {
             &managed_curve_a,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::LightBlue),
-               control_color: Some(ColorChoice::YellowBrown),
-               points_color: Some(ColorChoice::LightGreen),
+               main_color: Some(ColorChoice::LIGHT_BLUE),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
+               points_color: Some(ColorChoice::LIGHT_GREEN),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -3239,8 +3261,8 @@ mod tests {
             &managed_curve_a,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Red),
-               points_color: Some(ColorChoice::BlueRed),
+               main_color: Some(ColorChoice::RED),
+               points_color: Some(ColorChoice::BLUE_RED),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -3259,9 +3281,9 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::LightBlue),
-               control_color: Some(ColorChoice::YellowBrown),
-               points_color: Some(ColorChoice::LightGreen),
+               main_color: Some(ColorChoice::LIGHT_BLUE),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
+               points_color: Some(ColorChoice::LIGHT_GREEN),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -3270,8 +3292,8 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Red),
-               points_color: Some(ColorChoice::BlueRed),
+               main_color: Some(ColorChoice::RED),
+               points_color: Some(ColorChoice::BLUE_RED),
                // points_color: None,
                points_num_segments: 12,
                ..Default::default()
@@ -3295,9 +3317,9 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::LightBlue),
-               control_color: Some(ColorChoice::YellowBrown),
-               points_color: Some(ColorChoice::LightGreen),
+               main_color: Some(ColorChoice::LIGHT_BLUE),
+               control_color: Some(ColorChoice::YELLOW_BROWN),
+               points_color: Some(ColorChoice::LIGHT_GREEN),
                points_num_segments: 12,
                ..Default::default()
             },
@@ -3306,8 +3328,8 @@ mod tests {
             &managed_curve,
             drawable_diagram,
             &SampleCurveConfig {
-               main_color: Some(ColorChoice::Red),
-               points_color: Some(ColorChoice::BlueRed),
+               main_color: Some(ColorChoice::RED),
+               points_color: Some(ColorChoice::BLUE_RED),
                // points_color: None,
                points_num_segments: 12,
                ..Default::default()
@@ -3360,7 +3382,7 @@ mod tests {
          }
          draw_sample_segment_sequence(
             &managed_segments,
-            PathChoices { color: ColorChoice::Blue, ..Default::default() },
+            PathChoices { color: ColorChoice::BLUE, ..Default::default() },
             PathCompletion::Closed,
             drawable_layer,
             drawable_diagram,
@@ -3383,7 +3405,7 @@ mod tests {
          }
          draw_sample_segment_sequence(
             &managed_segments,
-            PathChoices { color: ColorChoice::Green, ..Default::default() },
+            PathChoices { color: ColorChoice::GREEN, ..Default::default() },
             PathCompletion::Open,
             drawable_layer,
             drawable_diagram,
@@ -3415,7 +3437,7 @@ mod tests {
          }
          draw_sample_segment_sequence(
             &managed_segments,
-            PathChoices { color: ColorChoice::Blue, ..Default::default() },
+            PathChoices { color: ColorChoice::BLUE, ..Default::default() },
             PathCompletion::Closed,
             drawable_layer,
             drawable_diagram,
@@ -3447,7 +3469,7 @@ mod tests {
          }
          draw_sample_segment_sequence(
             &managed_segments,
-            PathChoices { color: ColorChoice::Green, ..Default::default() },
+            PathChoices { color: ColorChoice::GREEN, ..Default::default() },
             PathCompletion::Open,
             drawable_layer,
             drawable_diagram,
@@ -3488,7 +3510,7 @@ mod tests {
          }
          draw_sample_segment_sequence(
             &managed_segments,
-            PathChoices { color: ColorChoice::Blue, ..Default::default() },
+            PathChoices { color: ColorChoice::BLUE, ..Default::default() },
             PathCompletion::Closed,
             drawable_layer,
             drawable_diagram,
@@ -3529,7 +3551,7 @@ mod tests {
          }
          draw_sample_segment_sequence(
             &managed_segments,
-            PathChoices { color: ColorChoice::Green, ..Default::default() },
+            PathChoices { color: ColorChoice::GREEN, ..Default::default() },
             PathCompletion::Open,
             drawable_layer,
             drawable_diagram,
@@ -3552,7 +3574,7 @@ mod tests {
          }
          draw_sample_segment_sequence(
             &managed_segments,
-            PathChoices { color: ColorChoice::Green, ..Default::default() },
+            PathChoices { color: ColorChoice::GREEN, ..Default::default() },
             PathCompletion::Open,
             drawable_layer,
             drawable_diagram,
@@ -3575,15 +3597,1649 @@ mod tests {
          }
          draw_sample_segment_sequence(
             &managed_segments,
-            PathChoices { color: ColorChoice::Blue, ..Default::default() },
+            PathChoices { color: ColorChoice::BLUE, ..Default::default() },
+            PathCompletion::Closed,
+            drawable_layer,
+            drawable_diagram,
+         );
+
+         managed_segments.clear();
+      }
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn segment_sequence_b_test() {
+      let t_range = [-3.0, 3.0];
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [200.0, 150.0],
+         axes_range: vec![-1.2, -1.4, 3.1, 1.4],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            grid_precision: vec![1],
+            axis_numbering: AxisNumbering::None,
+            ..Default::default()
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("segment_sequence_b", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      let mut managed_segments: VecDeque<OneOfManagedSegment> = VecDeque::new();
+      // Rational cubics with the same control points, with increasing weights on the middle
+      // control points.  Equal weights are drawn exactly as a cubic.
+      for (shift_x, middle_weight) in [(-0.9, 1.0), (0.4, 2.5), (1.7, 6.0)] {
+         let shift = [shift_x, -1.1];
+         let (x, y) = translate_4_simply(([0.0, 0.0, 1.0, 1.0], [0.0, 1.6, 1.6, 0.0]), shift);
+
+         managed_segments.clear();
+         managed_segments.push_back(OneOfManagedSegment::ManagedRatCubic(
+            ManagedRatCubic::create_from_four_points(
+               &FourPointRatCubic {
+                  r: t_range,
+                  p: p_from_x_y_4(&x, &y),
+                  w: [1.0, middle_weight, middle_weight, 1.0],
+                  ..Default::default()
+               },
+               drawable_diagram.prep.axes_range,
+            ),
+         ));
+         managed_segments.push_back(OneOfManagedSegment::Polyline(translate_vec(
+            &[[1.0, 0.0], [0.0, 0.0]],
+            shift,
+         )));
+         draw_sample_segment_sequence(
+            &managed_segments,
+            PathChoices { color: ColorChoice::BLUE, ..Default::default() },
+            PathCompletion::Closed,
+            drawable_layer,
+            drawable_diagram,
+         );
+      }
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn color_alpha_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [200.0, 150.0],
+         axes_range: vec![-1.6, -1.2, 1.6, 1.2],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            grid_precision: vec![1],
+            axis_numbering: AxisNumbering::None,
+            ..Default::default()
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("color_alpha", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      // Overlapping translucent squares, stroked in CMYK.
+      let mut managed_segments: VecDeque<OneOfManagedSegment> = VecDeque::new();
+      for (shift, fill_color) in [
+         ([-1.0, -0.8], ColorChoice::Rgba(1.0, 0.0, 0.0, 0.5)),
+         ([-0.4, -0.3], ColorChoice::BRIGHT_GREEN.with_alpha(0.5)),
+         ([0.2, -0.8], ColorChoice::Rgba(0.0, 0.0, 1.0, 0.25)),
+      ] {
+         managed_segments.clear();
+         managed_segments.push_back(OneOfManagedSegment::Polyline(translate_vec(
+            &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]],
+            shift,
+         )));
+         draw_sample_segment_sequence(
+            &managed_segments,
+            PathChoices {
+               color: ColorChoice::Cmyk(0.0, 0.6, 0.9, 0.3),
+               fill_choices: FillChoices { color: fill_color, opacity: 1.0, ..Default::default() },
+               ..Default::default()
+            },
+            PathCompletion::Closed,
+            drawable_layer,
+            drawable_diagram,
+         );
+      }
+
+      render_and_check(&mut runner);
+   }
+
+   fn themed_diagram(filestem: &str, theme: ThemeChoice) {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [200.0, 150.0],
+         axes_range: vec![-1.6, -1.2, 1.6, 1.2],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            grid_precision: vec![1],
+            grid_interval: [0.8, 0.6],
+            axis_numbering: AxisNumbering::Before,
+         },
+         background_box: BackgroundBox::Shrink,
+         theme,
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing(filestem, &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      let mut managed_segments: VecDeque<OneOfManagedSegment> = VecDeque::new();
+      for (shift, color) in [
+         ([-1.2, -0.8], ColorChoice::Role(ColorRole::Primary)),
+         ([-0.5, -0.3], ColorChoice::Role(ColorRole::Secondary)),
+         ([0.2, -0.8], ColorChoice::Role(ColorRole::Accent)),
+      ] {
+         managed_segments.clear();
+         managed_segments.push_back(OneOfManagedSegment::Polyline(translate_vec(
+            &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]],
+            shift,
+         )));
+         draw_sample_segment_sequence(
+            &managed_segments,
+            PathChoices { color, ..Default::default() },
             PathCompletion::Closed,
             drawable_layer,
             drawable_diagram,
          );
+      }
+      // Explicit colour, converted only for greyscale.
+      managed_segments.clear();
+      managed_segments.push_back(OneOfManagedSegment::Polyline(vec![[-1.4, 1.0], [1.4, 1.0]]));
+      draw_sample_segment_sequence(
+         &managed_segments,
+         PathChoices { color: ColorChoice::BRIGHT_RED, ..Default::default() },
+         PathCompletion::Open,
+         drawable_layer,
+         drawable_diagram,
+      );
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn theme_dark_test() {
+      themed_diagram("theme_dark", ThemeChoice::Dark);
+   }
+
+   #[test]
+   fn theme_greyscale_test() {
+      themed_diagram("theme_greyscale", ThemeChoice::Greyscale);
+   }
+
+   #[test]
+   fn line_cap_join_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [200.0, 150.0],
+         axes_range: vec![-1.6, -1.2, 1.6, 1.2],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::None,
+            ..Default::default()
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("line_cap_join", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      // Zig-zags with sharp and shallow angles.  The tight miter limit bevels the sharp angles
+      // only.
+      let zig_zag = [[0.0, 0.0], [0.3, 0.6], [0.6, 0.0], [0.7, 0.6]];
+      let mut managed_segments: VecDeque<OneOfManagedSegment> = VecDeque::new();
+      for (shift, line_cap, line_join, miter_limit) in [
+         ([-1.4, 0.3], LineCapChoice::Butt, LineJoinChoice::Miter, 10.0),
+         ([-0.4, 0.3], LineCapChoice::Round, LineJoinChoice::Round, 10.0),
+         ([0.6, 0.3], LineCapChoice::Square, LineJoinChoice::Bevel, 10.0),
+         ([-0.4, -0.9], LineCapChoice::Butt, LineJoinChoice::Miter, 2.0),
+      ] {
+         managed_segments.clear();
+         managed_segments.push_back(OneOfManagedSegment::Polyline(translate_vec(&zig_zag, shift)));
+         draw_sample_segment_sequence(
+            &managed_segments,
+            PathChoices {
+               color: ColorChoice::BLUE,
+               line_cap,
+               line_join,
+               miter_limit,
+               ..Default::default()
+            },
+            PathCompletion::Open,
+            drawable_layer,
+            drawable_diagram,
+         );
+      }
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn path_markers_test() {
+      let t_range = [-3.0, 3.0];
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::None,
+            ..Default::default()
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("path_markers", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      // Sequence of cubic and polyline, with mid markers at the join and at polyline vertices.
+      let mut managed_segments: VecDeque<OneOfManagedSegment> = VecDeque::new();
+      managed_segments.push_back(OneOfManagedSegment::ManagedCubic(
+         ManagedCubic::create_from_control_points(
+            &CubicFourPoint {
+               r: t_range,
+               h: CubicHomog([[-2.2, -1.9, -1.3, -1.0], [0.4, 1.2, 1.2, 0.4]]),
+               sigma: (1.0, 1.0),
+            },
+            drawable_diagram.prep.axes_range,
+         ),
+      ));
+      managed_segments.push_back(OneOfManagedSegment::Polyline(vec![
+         [-1.0, 0.4],
+         [-0.6, 1.2],
+         [-0.2, 0.4],
+      ]));
+      draw_sample_segment_sequence(
+         &managed_segments,
+         PathChoices {
+            color: ColorChoice::BLUE,
+            markers: MarkerChoices {
+               start: MarkerChoice::Circle,
+               mid: MarkerChoice::Open,
+               end: MarkerChoice::Filled,
+               ..Default::default()
+            },
+            ..Default::default()
+         },
+         PathCompletion::Open,
+         drawable_layer,
+         drawable_diagram,
+      );
+
+      // Single segments, with larger barbed arrowheads.
+      let barbed_choices = PathChoices {
+         color: ColorChoice::GREEN,
+         markers: MarkerChoices {
+            mid: MarkerChoice::Filled,
+            end: MarkerChoice::Barbed,
+            size: 1.5,
+            ..Default::default()
+         },
+         ..Default::default()
+      };
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Arc(Strokeable::<ArcPath> {
+            path: ArcPath {
+               angle_range: [-0.25 * std::f64::consts::PI, 0.75 * std::f64::consts::PI],
+               center: [1.0, 0.6],
+               transform: [1.0, 0.0, 0.0, 0.6],
+            },
+            path_choices: barbed_choices.clone(),
+         }),
+      });
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
+            path: vec![[-2.0, -1.2], [-0.5, -0.6]],
+            path_choices: PathChoices {
+               line_choice: LineChoice::Light,
+               markers: MarkerChoices {
+                  start: MarkerChoice::Open,
+                  end: MarkerChoice::Open,
+                  ..Default::default()
+               },
+               ..Default::default()
+            },
+         }),
+      });
+
+      // Sharp cubic, so that the end tangent differs from the chord.
+      let cubic = ManagedCubic::create_from_control_points(
+         &CubicFourPoint {
+            r: t_range,
+            h: CubicHomog([[0.6, 2.0, 0.6, 2.0], [-1.4, -1.4, -0.4, -0.4]]),
+            sigma: (1.0, 1.0),
+         },
+         drawable_diagram.prep.axes_range,
+      );
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Cubic(Strokeable::<CubicPath> {
+            path: cubic.four_point,
+            path_choices: barbed_choices,
+         }),
+      });
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn line_weights_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [200.0, 150.0],
+         axes_range: vec![-1.6, -1.2, 1.6, 1.2],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::None,
+            ..Default::default()
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("line_weights", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
 
+      // Weight classes, then custom dash patterns, including dash-dot and an offset pattern.
+      let mut managed_segments: VecDeque<OneOfManagedSegment> = VecDeque::new();
+      for (y, line_choice) in [
+         (0.9, LineChoice::Heavy),
+         (0.6, LineChoice::Ordinary),
+         (0.3, LineChoice::Light),
+         (0.0, LineChoice::Hairline),
+         (-0.3, LineChoice::Custom(LineStyle { dashes: vec![4.0, 4.0], ..Default::default() })),
+         (
+            -0.6,
+            LineChoice::Custom(LineStyle {
+               weight: 1.5,
+               dashes: vec![12.0, 4.0, 2.0, 4.0],
+               dash_offset: 0.0,
+            }),
+         ),
+         (
+            -0.9,
+            LineChoice::Custom(LineStyle {
+               weight: 0.5,
+               dashes: vec![12.0, 4.0, 2.0, 4.0],
+               dash_offset: 8.0,
+            }),
+         ),
+      ] {
          managed_segments.clear();
+         managed_segments.push_back(OneOfManagedSegment::Polyline(vec![[-1.4, y], [1.4, y]]));
+         draw_sample_segment_sequence(
+            &managed_segments,
+            PathChoices { color: ColorChoice::BLUE, line_choice, ..Default::default() },
+            PathCompletion::Open,
+            drawable_layer,
+            drawable_diagram,
+         );
       }
 
       render_and_check(&mut runner);
    }
+
+   #[test]
+   fn fill_patterns_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::None,
+            ..Default::default()
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("fill_patterns", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      let square = [[0.0, 0.0], [1.2, 0.0], [1.2, 1.2], [0.0, 1.2]];
+      let mut managed_segments: VecDeque<OneOfManagedSegment> = VecDeque::new();
+      for (shift, fill_choices) in [
+         (
+            [-2.2, 0.2],
+            FillChoices {
+               opacity: 1.0,
+               pattern: FillPattern::Linear(LinearGradient {
+                  start: [-2.2, 0.2],
+                  end: [-1.0, 1.4],
+                  stops: vec![
+                     ColorStop { offset: 0.0, color: ColorChoice::BLUE },
+                     ColorStop { offset: 1.0, color: ColorChoice::LIGHT_GREEN },
+                  ],
+               }),
+               ..Default::default()
+            },
+         ),
+         (
+            [-0.6, 0.2],
+            FillChoices {
+               color: ColorChoice::RED,
+               opacity: 1.0,
+               pattern: FillPattern::Hatch(HatchChoices::default()),
+            },
+         ),
+         (
+            [1.0, 0.2],
+            FillChoices {
+               color: ColorChoice::BLUE_GREEN,
+               opacity: 1.0,
+               pattern: FillPattern::Hatch(HatchChoices { angle: 0.0, spacing: 0.5, cross: true }),
+            },
+         ),
+         // Partially transparent solid fill overlapping the circles.
+         (
+            [0.4, -1.2],
+            FillChoices { color: ColorChoice::BRIGHT_YELLOW, opacity: 0.5, ..Default::default() },
+         ),
+      ] {
+         managed_segments.clear();
+         managed_segments.push_back(OneOfManagedSegment::Polyline(translate_vec(&square, shift)));
+         draw_sample_segment_sequence(
+            &managed_segments,
+            PathChoices { fill_choices, ..Default::default() },
+            PathCompletion::Closed,
+            drawable_layer,
+            drawable_diagram,
+         );
+      }
+
+      // Circles with an off-center radial gradient that fades out.
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer - 1,
+         drawable: OneOfDrawable::Circles(Strokeable::<CirclesSet> {
+            path_choices: PathChoices {
+               color: ColorChoice::BLUE,
+               fill_choices: FillChoices {
+                  opacity: 1.0,
+                  pattern: FillPattern::Radial(RadialGradient {
+                     center: [-0.6, -0.7],
+                     radius: 1.4,
+                     focus: Some([-0.9, -0.4]),
+                     stops: vec![
+                        ColorStop { offset: 0.0, color: ColorChoice::LIGHT_BLUE },
+                        ColorStop { offset: 1.0, color: ColorChoice::BLUE.with_alpha(0.0) },
+                     ],
+                  }),
+                  ..Default::default()
+               },
+               ..Default::default()
+            },
+            path: CirclesSet {
+               radius: 0.6,
+               centers: vec![[-1.4, -0.7], [-0.2, -0.7], [1.0, -0.7]],
+            },
+         }),
+      });
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn clip_region_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.1],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::Before,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("clip_region", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      // Plot that overshoots the axes range, clipped to the axes box.
+      let overshoot: Vec<[f64; 2]> = (0..=40)
+         .map(|i| {
+            let x = -3.0 + 0.15 * f64::from(i);
+            [x, 0.5 * x * x - 1.2]
+         })
+         .collect();
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Clipped(ClippedDrawables {
+            clip: ClipRegion::Rectangle(drawable_diagram.prep.axes_range),
+            drawables: vec![QualifiedDrawable {
+               layer: drawable_layer,
+               drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
+                  path: overshoot,
+                  path_choices: PathChoices { color: ColorChoice::BLUE, ..Default::default() },
+               }),
+            }],
+         }),
+      });
+
+      // Circle clip path, within which filled squares are drawn by layer.
+      let clip_circle = ArcPath {
+         angle_range: [0.0, 2.0 * std::f64::consts::PI],
+         center: [0.8, 0.4],
+         transform: [0.9, 0.0, 0.0, 0.9],
+      };
+      let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+      let clipped = [
+         ([0.0, 0.0], ColorChoice::LIGHT_GREEN, drawable_layer + 1),
+         ([0.6, 0.2], ColorChoice::RED, drawable_layer),
+      ]
+      .into_iter()
+      .map(|(shift, color, layer)| QualifiedDrawable {
+         layer,
+         drawable: OneOfDrawable::SegmentSequence(SegmentSequence {
+            completion: PathCompletion::Closed,
+            path_choices: PathChoices {
+               color: color.clone(),
+               fill_choices: FillChoices { color, opacity: 1.0, ..Default::default() },
+               ..Default::default()
+            },
+            segments: vec![OneOfSegment::Polyline(translate_vec(&square, shift))],
+         }),
+      })
+      .collect();
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Clipped(ClippedDrawables {
+            clip: ClipRegion::Path(vec![OneOfSegment::Arc(clip_circle)]),
+            drawables: clipped,
+         }),
+      });
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn group_transform_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::None,
+            ..Default::default()
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("group_transform", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      // Node shape, in its own coordinates, with colours left unset to take the group style.  The
+      // circle is filled, in the group fill colour.
+      let node = vec![
+         QualifiedDrawable {
+            layer: 1,
+            drawable: OneOfDrawable::Circles(Strokeable::<CirclesSet> {
+               path: CirclesSet { radius: 0.3, centers: vec![[0.0, 0.0]] },
+               path_choices: PathChoices {
+                  fill_choices: FillChoices { opacity: 1.0, ..Default::default() },
+                  ..Default::default()
+               },
+            }),
+         },
+         QualifiedDrawable {
+            drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
+               path: vec![[0.3, 0.0], [0.8, 0.0], [0.8, 0.4]],
+               path_choices: PathChoices {
+                  markers: MarkerChoices { end: MarkerChoice::Filled, ..Default::default() },
+                  ..Default::default()
+               },
+            }),
+            ..Default::default()
+         },
+         QualifiedDrawable {
+            drawable: OneOfDrawable::Text(TextDrawable {
+               anchor_choice: TextAnchorChoice::ThreeByThree(
+                  TextAnchorHorizontal::Center,
+                  TextAnchorVertical::Middle,
+               ),
+               texts: vec![TextSingle { content: "N".to_string(), ..Default::default() }],
+               ..Default::default()
+            }),
+            ..Default::default()
+         },
+      ];
+
+      let (sin, cos) = (0.5_f64).sin_cos();
+      let mut instances: Vec<QualifiedDrawable> = [
+         ([1.0, 0.0, 0.0, 1.0], [-1.8, 0.8], ColorChoice::BLUE),
+         ([cos, sin, -sin, cos], [-0.4, 0.8], ColorChoice::RED),
+         ([1.5, 0.0, 0.0, 0.75], [1.2, 0.8], ColorChoice::GREEN),
+      ]
+      .into_iter()
+      .map(|(transform, offset, color)| QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Group(GroupDrawable {
+            transform,
+            offset,
+            style: PathChoices {
+               color: color.clone(),
+               fill_choices: FillChoices { color: color.with_alpha(0.2), ..Default::default() },
+               ..Default::default()
+            },
+            drawables: node.clone(),
+            ..Default::default()
+         }),
+      })
+      .collect();
+
+      // Nested groups: a row of nodes, translated and then scaled by the outer group.  The inner
+      // style overrides only the colour.
+      let row = (0..3)
+         .map(|i| QualifiedDrawable {
+            drawable: OneOfDrawable::Group(GroupDrawable {
+               offset: [f64::from(i), 0.0],
+               drawables: node.clone(),
+               ..Default::default()
+            }),
+            ..Default::default()
+         })
+         .collect();
+      instances.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Group(GroupDrawable {
+            id: Some("row".to_string()),
+            transform: [0.8, 0.0, 0.0, 0.8],
+            offset: [-1.6, -1.0],
+            style: PathChoices {
+               line_choice: LineChoice::Heavy,
+               fill_choices: FillChoices { color: ColorChoice::LIGHT_GRAY, ..Default::default() },
+               ..Default::default()
+            },
+            drawables: vec![QualifiedDrawable {
+               drawable: OneOfDrawable::Group(GroupDrawable {
+                  style: PathChoices { color: ColorChoice::BLUE_RED, ..Default::default() },
+                  drawables: row,
+                  ..Default::default()
+               }),
+               ..Default::default()
+            }],
+            ..Default::default()
+         }),
+      });
+      drawable_diagram.drawables.extend(instances);
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn font_choices_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.1],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::Before,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         // Diagram-wide font, used by the axis numbering.
+         font_choices: FontChoices { family: vec!["serif".to_string()], ..Default::default() },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("font_choices", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      for (y, size_choice, font_choices, content) in [
+         (
+            1.2,
+            TextSizeChoice::Large,
+            FontChoices {
+               family: vec!["sans".to_string()],
+               weight: FontWeightChoice::Bold,
+               ..Default::default()
+            },
+            "Bold sans title",
+         ),
+         (
+            0.6,
+            TextSizeChoice::Normal,
+            FontChoices { family: vec!["monospace".to_string()], ..Default::default() },
+            "let code = 42;",
+         ),
+         (
+            0.0,
+            TextSizeChoice::Normal,
+            FontChoices { slant: FontSlantChoice::Italic, ..Default::default() },
+            "x = f(y) + z",
+         ),
+         (
+            -0.6,
+            TextSizeChoice::Normal,
+            FontChoices {
+               family: vec!["No Such Font".to_string(), "sans".to_string()],
+               weight: FontWeightChoice::Light,
+               stretch: FontStretchChoice::Condensed,
+               ..Default::default()
+            },
+            "Fallback, light condensed",
+         ),
+         (-1.2, TextSizeChoice::Small, FontChoices::default(), "Diagram font, small"),
+      ] {
+         drawable_diagram.drawables.push(QualifiedDrawable {
+            layer: drawable_layer,
+            drawable: OneOfDrawable::Text(TextDrawable {
+               size_choice,
+               anchor_choice: TextAnchorChoice::ThreeByThree(
+                  TextAnchorHorizontal::Center,
+                  TextAnchorVertical::Middle,
+               ),
+               font_choices,
+               texts: vec![TextSingle {
+                  content: content.to_string(),
+                  location: [0.0, y],
+                  ..Default::default()
+               }],
+               ..Default::default()
+            }),
+         });
+      }
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn text_rotation_path_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.1],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::Before,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("text_rotation_path", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      // Rotated labels, anchored so that rotation is about the location.
+      for (location, rotation, horizontal, content) in [
+         ([-2.0, 0.0], 90.0, TextAnchorHorizontal::Center, "Vertical axis"),
+         ([-1.2, -1.2], 30.0, TextAnchorHorizontal::Left, "Tilted label"),
+      ] {
+         drawable_diagram.drawables.push(QualifiedDrawable {
+            layer: drawable_layer,
+            drawable: OneOfDrawable::Text(TextDrawable {
+               anchor_choice: TextAnchorChoice::ThreeByThree(
+                  horizontal,
+                  TextAnchorVertical::Middle,
+               ),
+               texts: vec![TextSingle {
+                  content: content.to_string(),
+                  location,
+                  rotation,
+                  ..Default::default()
+               }],
+               ..Default::default()
+            }),
+         });
+         drawable_diagram.drawables.push(QualifiedDrawable {
+            layer: drawable_layer,
+            drawable: OneOfDrawable::Points(PointsDrawable {
+               point_choice: PointChoice::Dot,
+               color_choice: ColorChoice::RED,
+               centers: vec![location],
+               ..Default::default()
+            }),
+         });
+      }
+
+      // Connector with labels at start, middle and end, above and below.
+      let cubic = ManagedCubic::create_from_control_points(
+         &CubicFourPoint {
+            r: [0.0, 1.0],
+            h: CubicHomog([[-1.2, -0.2, 0.6, 2.0], [0.0, 1.6, -0.8, 0.8]]),
+            sigma: (1.0, 1.0),
+         },
+         drawable_diagram.prep.axes_range,
+      );
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Cubic(Strokeable::<CubicPath> {
+            path: cubic.four_point.clone(),
+            path_choices: PathChoices { color: ColorChoice::BLUE, ..Default::default() },
+         }),
+      });
+      for (horizontal, vertical, content) in [
+         (TextAnchorHorizontal::Left, TextAnchorVertical::Bottom, "start"),
+         (TextAnchorHorizontal::Center, TextAnchorVertical::Bottom, "along the curve"),
+         (TextAnchorHorizontal::Right, TextAnchorVertical::Top, "end"),
+      ] {
+         drawable_diagram.drawables.push(QualifiedDrawable {
+            layer: drawable_layer,
+            drawable: OneOfDrawable::TextPath(TextPathDrawable {
+               offset_choice: TextOffsetChoice::Diagram,
+               anchor_choice: TextAnchorChoice::ThreeByThree(horizontal, vertical),
+               color_choice: ColorChoice::BLUE,
+               content: content.to_string(),
+               segments: vec![OneOfSegment::Cubic(cubic.four_point.clone())],
+               ..Default::default()
+            }),
+         });
+      }
+
+      // Anticlockwise arc, so that text follows the path direction and reads inverted.
+      let arc = ArcPath {
+         angle_range: [0.2 * std::f64::consts::PI, 0.8 * std::f64::consts::PI],
+         center: [0.4, -1.4],
+         transform: [1.2, 0.0, 0.0, 1.2],
+      };
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Arc(Strokeable::<ArcPath> {
+            path: arc.clone(),
+            path_choices: PathChoices { color: ColorChoice::GREEN, ..Default::default() },
+         }),
+      });
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::TextPath(TextPathDrawable {
+            anchor_choice: TextAnchorChoice::Centered,
+            color_choice: ColorChoice::GREEN,
+            content: "Arc text".to_string(),
+            segments: vec![OneOfSegment::Arc(arc)],
+            ..Default::default()
+         }),
+      });
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn text_paragraph_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [400.0, 300.0],
+         axes_range: vec![-2.4, -1.8, 2.4, 1.8],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::None,
+            grid_interval: [1.2, 1.2],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("text_paragraph", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      let wrapped = "Node labels can be wrapped automatically so that they fit within a width.";
+      for (location, alignment, max_width, line_spacing, content) in [
+         ([-1.2, 0.9], TextAlignChoice::Left, 0.0, 1.0, "Explicit\nline breaks,\nleft"),
+         ([1.2, 0.9], TextAlignChoice::Center, 0.0, 1.0, "Explicit\nline breaks,\ncentered"),
+         ([-1.2, -0.9], TextAlignChoice::Right, 140.0, 1.0, wrapped),
+         ([1.2, -0.9], TextAlignChoice::Justified, 140.0, 1.5, wrapped),
+      ] {
+         drawable_diagram.drawables.push(QualifiedDrawable {
+            layer: drawable_layer,
+            drawable: OneOfDrawable::Text(TextDrawable {
+               anchor_choice: TextAnchorChoice::ThreeByThree(
+                  TextAnchorHorizontal::Center,
+                  TextAnchorVertical::Middle,
+               ),
+               paragraph_choices: ParagraphChoices { alignment, max_width, line_spacing },
+               texts: vec![TextSingle {
+                  content: content.to_string(),
+                  location,
+                  ..Default::default()
+               }],
+               ..Default::default()
+            }),
+         });
+         drawable_diagram.drawables.push(QualifiedDrawable {
+            layer: drawable_layer,
+            drawable: OneOfDrawable::Points(PointsDrawable {
+               point_choice: PointChoice::Dot,
+               color_choice: ColorChoice::RED,
+               centers: vec![location],
+               ..Default::default()
+            }),
+         });
+      }
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn auto_markup_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::None,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("auto_markup", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      // Left column is auto, and right is the same content as plain text.
+      for (y, content) in [
+         (1.2, "x_1^2 + y_{ij}^{n+1}"),
+         (0.6, "\\alpha, \\beta, \\Omega_0"),
+         (0.0, "e^{-i\\omega t}"),
+         (-0.6, "<i>Pango</i> <b>markup</b>"),
+         (-1.2, "a < b, c\\_d"),
+      ] {
+         for (x, markup) in [(-1.2, MarkupChoice::Auto), (1.2, MarkupChoice::Plain)] {
+            drawable_diagram.drawables.push(QualifiedDrawable {
+               layer: drawable_layer,
+               drawable: OneOfDrawable::Text(TextDrawable {
+                  anchor_choice: TextAnchorChoice::ThreeByThree(
+                     TextAnchorHorizontal::Center,
+                     TextAnchorVertical::Middle,
+                  ),
+                  texts: vec![TextSingle {
+                     content: content.to_string(),
+                     location: [x, y],
+                     markup,
+                     ..Default::default()
+                  }],
+                  ..Default::default()
+               }),
+            });
+         }
+      }
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn capability_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [200.0, 150.0],
+         axes_range: vec![-1.6, -1.2, 1.6, 1.2],
+         padding: vec![0.05],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::None,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+
+      let mut runner = build_from_sizing("capability", &sizing);
+      let render_engine = &mut runner.combo.render_engine;
+
+      assert!(render_engine.supports::<SvgDocumentUnit>());
+      assert!(!render_engine.supports::<PangoFontDescription>());
+      assert!(render_engine.invoke::<PangoFontDescription>(()).is_err());
+      render_engine.invoke::<SvgDocumentUnit>(SvgUnit::Px).unwrap();
+
+      let mut text_layout = render_engine.create_text_layout();
+      assert!(text_layout.supports::<PangoFontDescription>());
+      text_layout.set_layout(
+         &FontChoices { weight: FontWeightChoice::Bold, ..Default::default() },
+         &ParagraphChoices::default(),
+         10.0,
+         &TextSingle { content: "Label".to_string(), ..Default::default() },
+      );
+      assert_eq!(text_layout.invoke::<PangoFontDescription>(()).unwrap(), "sans Bold 10px");
+      drop(text_layout);
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn image_drawable_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.1],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::Before,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("image_drawable", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      // Same heatmap, from file and smooth, and from data with nearest-pixel interpolation and
+      // partial opacity.
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Image(ImageDrawable {
+            source: ImageSource::PngFile("tests/inputs/heatmap.png".to_string()),
+            rectangle: [-2.0, -1.2, -0.4, 1.2],
+            alt_text: Some("Heatmap, smoothly interpolated".to_string()),
+            ..Default::default()
+         }),
+      });
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Image(ImageDrawable {
+            source: ImageSource::PngData(include_bytes!("inputs/heatmap.png").to_vec()),
+            rectangle: [0.4, -1.2, 2.0, 1.2],
+            interpolation: ImageInterpolation::Nearest,
+            opacity: 0.7,
+            ..Default::default()
+         }),
+      });
+
+      // Vector content over the images.
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer + 1,
+         drawable: OneOfDrawable::Circles(Strokeable::<CirclesSet> {
+            path_choices: PathChoices { color: ColorChoice::BLACK, ..Default::default() },
+            path: CirclesSet { radius: 0.5, centers: vec![[-1.2, 0.0], [1.2, 0.0]] },
+         }),
+      });
+
+      render_and_check(&mut runner);
+   }
+
+   // Images that cannot be read or decoded are reported as rendering errors.
+   #[test]
+   fn image_error_test() {
+      let preparation = create_sized_diagram(&TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         ..Default::default()
+      })
+      .prepare();
+
+      for source in [
+         ImageSource::PngFile("tests/inputs/missing.png".to_string()),
+         ImageSource::PngData(b"Not a PNG image.".to_vec()),
+      ] {
+         let mut combo = CairoSpartanCombo::create_for_stream(Vec::<u8>::new(), &preparation);
+         combo.drawable_diagram.drawables.push(QualifiedDrawable {
+            layer: 30,
+            drawable: OneOfDrawable::Image(ImageDrawable {
+               source,
+               rectangle: [-2.0, -1.2, 2.0, 1.2],
+               ..Default::default()
+            }),
+         });
+         assert!(combo.render_diagram().is_err());
+      }
+   }
+
+   #[test]
+   fn element_attributes_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.1],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::Before,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+      let faded_layer = 40;
+
+      let mut spartan = create_sized_diagram(&sizing);
+      spartan.layer_choices = LayerChoices::standard().with_layer(NamedLayer {
+         name: "faded".to_string(),
+         layer: faded_layer,
+         opacity: 0.5,
+         ..Default::default()
+      });
+      let mut runner = JsonSvgRunner::new("element_attributes", &spartan.prepare());
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+      sizing.axes_spec.generate_axes(drawable_diagram);
+
+      let node = |content: &str| {
+         vec![
+            QualifiedDrawable {
+               drawable: OneOfDrawable::Circles(Strokeable::<CirclesSet> {
+                  path_choices: PathChoices {
+                     fill_choices: FillChoices {
+                        color: ColorChoice::BLUE.with_alpha(0.2),
+                        ..Default::default()
+                     },
+                     ..Default::default()
+                  },
+                  path: CirclesSet { radius: 0.5, centers: vec![[0.0, 0.0]] },
+               }),
+               ..Default::default()
+            },
+            QualifiedDrawable {
+               drawable: OneOfDrawable::Text(TextDrawable {
+                  anchor_choice: TextAnchorChoice::ThreeByThree(
+                     TextAnchorHorizontal::Center,
+                     TextAnchorVertical::Middle,
+                  ),
+                  texts: vec![TextSingle { content: content.to_string(), ..Default::default() }],
+                  ..Default::default()
+               }),
+               ..Default::default()
+            },
+         ]
+      };
+
+      // Nodes with all attributes, with only an id and class, and with a tooltip including
+      // characters that must be escaped.
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Group(GroupDrawable {
+            id: Some("node-a".to_string()),
+            class: Some("node linked".to_string()),
+            link: Some("https://example.com/a?x=1&y=2".to_string()),
+            tooltip: Some("Node A".to_string()),
+            offset: [-1.4, 0.0],
+            drawables: node("A"),
+            ..Default::default()
+         }),
+      });
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Group(GroupDrawable {
+            id: Some("node-b".to_string()),
+            class: Some("node".to_string()),
+            drawables: node("B"),
+            ..Default::default()
+         }),
+      });
+
+      // Annotated group within a clipped drawable, itself nesting an annotated group.
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Clipped(ClippedDrawables {
+            clip: ClipRegion::Rectangle([0.8, -1.2, 2.0, 1.2]),
+            drawables: vec![QualifiedDrawable {
+               drawable: OneOfDrawable::Group(GroupDrawable {
+                  id: Some("node-c".to_string()),
+                  tooltip: Some("Node <C> & \"friends\"".to_string()),
+                  offset: [1.4, 0.0],
+                  drawables: vec![QualifiedDrawable {
+                     drawable: OneOfDrawable::Group(GroupDrawable {
+                        class: Some("inner".to_string()),
+                        drawables: node("C"),
+                        ..Default::default()
+                     }),
+                     ..Default::default()
+                  }],
+                  ..Default::default()
+               }),
+               ..Default::default()
+            }],
+         }),
+      });
+
+      // Paths with miter limits in the range reserved for markers are clamped, and so are not
+      // taken as markers.
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Group(GroupDrawable {
+            id: Some("edge".to_string()),
+            drawables: vec![QualifiedDrawable {
+               drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
+                  path_choices: PathChoices { miter_limit: 900_000.0, ..Default::default() },
+                  path: vec![[-1.4, -1.0], [-0.7, -1.4], [0.0, -1.0]],
+               }),
+               ..Default::default()
+            }],
+            ..Default::default()
+         }),
+      });
+
+      // Annotated group on a translucent layer, which is drawn as a whole with opacity.
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: faded_layer,
+         drawable: OneOfDrawable::Group(GroupDrawable {
+            id: Some("node-d".to_string()),
+            tooltip: Some("Node D".to_string()),
+            offset: [0.0, 1.1],
+            drawables: node("D"),
+            ..Default::default()
+         }),
+      });
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn accessible_diagram_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.1],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::None,
+            grid_interval: [0.8, 0.8],
+            ..Default::default()
+         },
+         ..Default::default()
+      };
+
+      let mut spartan = create_sized_diagram(&sizing);
+      spartan.title = Some("Signal path".to_string());
+      spartan.description = Some("A source feeding a filter & a sink.".to_string());
+      let preparation = spartan.prepare();
+
+      let mut runner = JsonSvgRunner::new("accessible_diagram", &preparation);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+      sizing.axes_spec.generate_axes(drawable_diagram);
+      let drawable_layer = 30;
+
+      // Labelled boxes, and an arrow group described by alternative text in place of its parts.
+      for (x, content) in [(-1.6, "Source"), (0.0, "<i>Filter</i>"), (1.6, "Sink")] {
+         drawable_diagram.drawables.push(QualifiedDrawable {
+            layer: drawable_layer,
+            drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
+               path: vec![
+                  [x - 0.6, -0.4],
+                  [x + 0.6, -0.4],
+                  [x + 0.6, 0.4],
+                  [x - 0.6, 0.4],
+                  [x - 0.6, -0.4],
+               ],
+               ..Default::default()
+            }),
+         });
+         drawable_diagram.drawables.push(QualifiedDrawable {
+            layer: drawable_layer,
+            drawable: OneOfDrawable::Text(TextDrawable {
+               anchor_choice: TextAnchorChoice::ThreeByThree(
+                  TextAnchorHorizontal::Center,
+                  TextAnchorVertical::Middle,
+               ),
+               texts: vec![TextSingle {
+                  content: content.to_string(),
+                  location: [x, 0.0],
+                  ..Default::default()
+               }],
+               ..Default::default()
+            }),
+         });
+      }
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Group(GroupDrawable {
+            alt_text: Some("Arrows from source to filter, and filter to sink".to_string()),
+            drawables: [-0.8, 0.8]
+               .into_iter()
+               .map(|x| QualifiedDrawable {
+                  drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
+                     path: vec![[x - 0.2, 0.0], [x + 0.2, 0.0]],
+                     path_choices: PathChoices {
+                        markers: MarkerChoices { end: MarkerChoice::Filled, ..Default::default() },
+                        ..Default::default()
+                     },
+                  }),
+                  ..Default::default()
+               })
+               .collect(),
+            ..Default::default()
+         }),
+      });
+
+      assert_eq!(
+         drawable_diagram.text_summary(),
+         "Signal path\n\
+          A source feeding a filter & a sink.\n\
+          Axes range: x from -2.4 to 2.4, y from -1.6 to 1.6.\n\
+          Labels:\n\
+          * Source\n\
+          * Filter\n\
+          * Sink\n\
+          * Arrows from source to filter, and filter to sink\n"
+      );
+
+      render_and_check(&mut runner);
+   }
+
+   #[test]
+   fn named_layers_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.1],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::Before,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+
+      // Construction lines are hidden in the presentation version, and shown translucent in the
+      // debug version, which also fades the grid.
+      let presentation_layers = LayerChoices::standard().with_layer(NamedLayer {
+         name: "construction".to_string(),
+         layer: 40,
+         hidden: true,
+         ..Default::default()
+      });
+      let mut debug_layers = presentation_layers.clone();
+      let construction = debug_layers.get_mut("construction").unwrap();
+      construction.hidden = false;
+      construction.opacity = 0.5;
+      debug_layers.get_mut("grid").unwrap().opacity = 0.3;
+
+      let content_layer = presentation_layers.number("content").unwrap();
+      let construction_layer = presentation_layers.number("construction").unwrap();
+      let annotation_layer = presentation_layers.number("annotation").unwrap();
+
+      // Overlapping circles, drawn in the order given within the content layer.
+      let mut drawables: Vec<QualifiedDrawable> = [
+         ([-0.5, 0.0], ColorChoice::BLUE),
+         ([0.5, 0.0], ColorChoice::RED),
+         ([0.0, 0.6], ColorChoice::GREEN),
+      ]
+      .into_iter()
+      .map(|(center, color)| QualifiedDrawable {
+         layer: content_layer,
+         drawable: OneOfDrawable::Circles(Strokeable::<CirclesSet> {
+            path_choices: PathChoices {
+               fill_choices: FillChoices { color, ..Default::default() },
+               ..Default::default()
+            },
+            path: CirclesSet { radius: 0.8, centers: vec![center] },
+         }),
+      })
+      .collect();
+      // Construction lines, given before content, and an annotation.
+      drawables.insert(
+         0,
+         QualifiedDrawable {
+            layer: construction_layer,
+            drawable: OneOfDrawable::Lines(Strokeable::<LinesSetSet> {
+               path: LinesSetSet {
+                  coords: vec![([-2.0, 0.0], [2.0, 0.0]), ([0.0, -1.4], [0.0, 1.4])],
+                  offsets: Some(vec![[0.0, 0.0]]),
+               },
+               path_choices: PathChoices {
+                  line_choice: LineChoice::Light,
+                  color: ColorChoice::BRIGHT_RED,
+                  ..Default::default()
+               },
+            }),
+         },
+      );
+      drawables.push(QualifiedDrawable {
+         layer: annotation_layer,
+         drawable: OneOfDrawable::Text(TextDrawable {
+            anchor_choice: TextAnchorChoice::ThreeByThree(
+               TextAnchorHorizontal::Left,
+               TextAnchorVertical::Middle,
+            ),
+            texts: vec![TextSingle {
+               content: "Overlap".to_string(),
+               location: [1.4, -1.0],
+               ..Default::default()
+            }],
+            ..Default::default()
+         }),
+      });
+
+      // Group of a box and its label, whose children are on the default layer, which is that of
+      // the grid.  They are drawn with the group, unaffected by the fading of the grid.
+      drawables.push(QualifiedDrawable {
+         layer: content_layer,
+         drawable: OneOfDrawable::Group(GroupDrawable {
+            offset: [-1.6, -1.0],
+            drawables: vec![
+               QualifiedDrawable {
+                  drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
+                     path: vec![[-0.5, -0.3], [0.5, -0.3], [0.5, 0.3], [-0.5, 0.3], [-0.5, -0.3]],
+                     ..Default::default()
+                  }),
+                  ..Default::default()
+               },
+               QualifiedDrawable {
+                  drawable: OneOfDrawable::Text(TextDrawable {
+                     anchor_choice: TextAnchorChoice::ThreeByThree(
+                        TextAnchorHorizontal::Center,
+                        TextAnchorVertical::Middle,
+                     ),
+                     texts: vec![TextSingle { content: "Key".to_string(), ..Default::default() }],
+                     ..Default::default()
+                  }),
+                  ..Default::default()
+               },
+            ],
+            ..Default::default()
+         }),
+      });
+
+      for (filestem, layer_choices) in
+         [("named_layers_presentation", presentation_layers), ("named_layers_debug", debug_layers)]
+      {
+         let mut spartan = create_sized_diagram(&sizing);
+         spartan.layer_choices = layer_choices;
+         let preparation = spartan.prepare();
+
+         let mut runner = JsonSvgRunner::new(filestem, &preparation);
+         let drawable_diagram = &mut runner.combo.drawable_diagram;
+         sizing.axes_spec.generate_axes(drawable_diagram);
+         drawable_diagram.drawables.extend(drawables.iter().cloned());
+
+         render_and_check(&mut runner);
+      }
+   }
+
+   #[test]
+   fn validation_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.1],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::Before,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+      let mut drawable_diagram =
+         DrawableDiagram { prep: create_sized_diagram(&sizing).prepare(), drawables: vec![] };
+      sizing.axes_spec.generate_axes(&mut drawable_diagram);
+      assert!(drawable_diagram.validate().is_empty());
+
+      let axes_count = drawable_diagram.drawables.len();
+      let polyline = |path: Vec<[f64; 2]>| QualifiedDrawable {
+         drawable: OneOfDrawable::Polyline(Strokeable::<PolylinePath> {
+            path,
+            ..Default::default()
+         }),
+         ..Default::default()
+      };
+      drawable_diagram.drawables.extend([
+         polyline(vec![]),
+         polyline(vec![[0.0, f64::NAN], [1.0, 1.0]]),
+         QualifiedDrawable {
+            drawable: OneOfDrawable::Circles(Strokeable::<CirclesSet> {
+               path: CirclesSet { radius: 0.0, centers: vec![[0.0, 0.0]] },
+               ..Default::default()
+            }),
+            ..Default::default()
+         },
+         QualifiedDrawable {
+            drawable: OneOfDrawable::Text(TextDrawable {
+               texts: vec![TextSingle { location: [0.5, 0.5], ..Default::default() }],
+               ..Default::default()
+            }),
+            ..Default::default()
+         },
+         // Inside the canvas locally, but offset outside by the group.
+         QualifiedDrawable {
+            drawable: OneOfDrawable::Group(GroupDrawable {
+               offset: [10.0, 0.0],
+               drawables: vec![
+                  polyline(vec![[0.0, 0.0], [1.0, 0.0]]),
+                  QualifiedDrawable {
+                     drawable: OneOfDrawable::Points(PointsDrawable::default()),
+                     ..Default::default()
+                  },
+               ],
+               ..Default::default()
+            }),
+            ..Default::default()
+         },
+      ]);
+
+      // Locations are given relative to the first added drawable.
+      let found: Vec<(Vec<usize>, DiagnosticSeverity, DiagnosticKind)> = drawable_diagram
+         .validate()
+         .into_iter()
+         .map(|mut d| {
+            d.location[0] -= axes_count;
+            (d.location, d.severity, d.kind)
+         })
+         .collect();
+      assert_eq!(
+         found,
+         vec![
+            (vec![0], DiagnosticSeverity::Error, DiagnosticKind::DegenerateGeometry),
+            (vec![1], DiagnosticSeverity::Error, DiagnosticKind::NonFiniteCoordinate),
+            (vec![2], DiagnosticSeverity::Warning, DiagnosticKind::DegenerateGeometry),
+            (vec![3], DiagnosticSeverity::Warning, DiagnosticKind::MissingText),
+            (vec![4, 0], DiagnosticSeverity::Warning, DiagnosticKind::OutsideCanvas),
+            (vec![4, 1], DiagnosticSeverity::Warning, DiagnosticKind::EmptySet),
+         ]
+      );
+      assert_eq!(
+         drawable_diagram.validate()[0].to_string(),
+         format!("error at drawable {axes_count}: Polyline has no points.")
+      );
+   }
+
+   #[test]
+   fn point_markers_test() {
+      let sizing = TestSizing {
+         sizing_scheme: SizingScheme::SquareCenter,
+         canvas_size: [300.0, 200.0],
+         axes_range: vec![-2.4, -1.6, 2.4, 1.6],
+         padding: vec![0.1],
+         axes_spec: AxesSpec {
+            axes_style: AxesStyle::Boxed,
+            axis_numbering: AxisNumbering::Before,
+            grid_interval: [0.8, 0.8],
+            grid_precision: vec![1],
+         },
+         ..Default::default()
+      };
+      let drawable_layer = 30;
+
+      let mut runner = build_from_sizing("point_markers", &sizing);
+      let drawable_diagram = &mut runner.combo.drawable_diagram;
+
+      // Built-in shapes, hollow above filled, at normal size and enlarged.
+      let shapes = [
+         (PointChoice::Circle, PointChoice::Dot),
+         (PointChoice::Square, PointChoice::FilledSquare),
+         (PointChoice::Triangle, PointChoice::FilledTriangle),
+         (PointChoice::TriangleDown, PointChoice::FilledTriangleDown),
+         (PointChoice::Diamond, PointChoice::FilledDiamond),
+         (PointChoice::Star, PointChoice::FilledStar),
+         (PointChoice::Plus, PointChoice::Times),
+      ];
+      for (i, (hollow, filled)) in shapes.into_iter().enumerate() {
+         let x = 0.6f64.mul_add(f64::from(u8::try_from(i).unwrap()), -1.8);
+         for (point_choice, y, size) in
+            [(hollow, 1.2, 1.0), (filled, 0.6, 1.0), (hollow, -0.1, 1.6), (filled, -0.9, 1.6)]
+         {
+            drawable_diagram.drawables.push(QualifiedDrawable {
+               layer: drawable_layer,
+               drawable: OneOfDrawable::Points(PointsDrawable {
+                  point_choice,
+                  color_choice: ColorChoice::BLUE,
+                  centers: vec![[x, y]],
+                  size,
+                  ..Default::default()
+               }),
+            });
+         }
+      }
+
+      // Custom filled arrowhead marker, in units of the marker radius.
+      drawable_diagram.drawables.push(QualifiedDrawable {
+         layer: drawable_layer,
+         drawable: OneOfDrawable::Points(PointsDrawable {
+            point_choice: PointChoice::Custom,
+            color_choice: ColorChoice::RED,
+            centers: vec![[-1.8, -1.4], [-0.6, -1.4], [0.6, -1.4], [1.8, -1.4]],
+            size: 1.2,
+            custom_marker: SegmentSequence {
+               completion: PathCompletion::Closed,
+               path_choices: PathChoices {
+                  fill_choices: FillChoices {
+                     color: ColorChoice::RED,
+                     opacity: 0.4,
+                     ..Default::default()
+                  },
+                  ..Default::default()
+               },
+               segments: vec![OneOfSegment::Polyline(vec![
+                  [1.5, 0.0],
+                  [-1.0, 1.0],
+                  [-0.5, 0.0],
+                  [-1.0, -1.0],
+               ])],
+            },
+         }),
+      });
+
+      render_and_check(&mut runner);
+   }
 }